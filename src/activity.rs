@@ -0,0 +1,139 @@
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use color_eyre::eyre::{Error, Result};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    events::{send_notification, Key, Message, Notification, Transition},
+    state::SharedSession,
+};
+
+/// The kinds of action ducker performs on the user's behalf
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityKind {
+    Start,
+    Stop,
+    Restart,
+    Delete,
+    Pull,
+    Prune,
+    /// An error which didn't come from any one action
+    Error,
+}
+
+impl ActivityKind {
+    pub fn verb(self) -> &'static str {
+        match self {
+            Self::Start => "start",
+            Self::Stop => "stop",
+            Self::Restart => "restart",
+            Self::Delete => "delete",
+            Self::Pull => "pull",
+            Self::Prune => "prune",
+            Self::Error => "error",
+        }
+    }
+
+    fn past_tense(self) -> &'static str {
+        match self {
+            Self::Start => "started",
+            Self::Stop => "stopped",
+            Self::Restart => "restarted",
+            Self::Delete => "deleted",
+            Self::Pull => "pulled",
+            Self::Prune => "pruned",
+            Self::Error => "failed",
+        }
+    }
+}
+
+/// An action ducker performed during the session, along with its outcome
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Activity {
+    pub kind: ActivityKind,
+    /// What was acted on, eg `container web`; empty for an error with no target
+    pub target: String,
+    pub started: DateTime<Local>,
+    pub duration: Duration,
+    /// The error if the action failed, followed by each of its causes in turn
+    pub outcome: Result<(), Vec<String>>,
+}
+
+impl Activity {
+    /// Starts timing an action; the activity is complete once it is finished
+    pub fn begin(kind: ActivityKind, target: impl Into<String>) -> PendingActivity {
+        PendingActivity {
+            kind,
+            target: target.into(),
+            started: Local::now(),
+            timer: Instant::now(),
+        }
+    }
+
+    /// An error which happened outside of any one action
+    pub fn error(chain: Vec<String>) -> Self {
+        Self {
+            kind: ActivityKind::Error,
+            target: String::new(),
+            started: Local::now(),
+            duration: Duration::ZERO,
+            outcome: Err(chain),
+        }
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.outcome.is_ok()
+    }
+
+    /// A notification of the outcome, eg `container web stopped`
+    pub fn notification(&self) -> Notification {
+        match &self.outcome {
+            Ok(_) => Notification::success(format!("{} {}", self.target, self.kind.past_tense())),
+            Err(chain) if self.kind == ActivityKind::Error => Notification::error(chain.join(": ")),
+            Err(chain) => Notification::error(format!(
+                "failed to {} {}: {}",
+                self.kind.verb(),
+                self.target,
+                chain.join(": ")
+            )),
+        }
+    }
+}
+
+/// An action which has started but not yet finished
+#[derive(Debug)]
+pub struct PendingActivity {
+    kind: ActivityKind,
+    target: String,
+    started: DateTime<Local>,
+    timer: Instant,
+}
+
+impl PendingActivity {
+    pub fn finish<T>(self, result: &Result<T>) -> Activity {
+        Activity {
+            kind: self.kind,
+            target: self.target,
+            started: self.started,
+            duration: self.timer.elapsed(),
+            outcome: result.as_ref().map(|_| ()).map_err(error_chain),
+        }
+    }
+}
+
+/// The message of an error followed by those of each of its causes
+pub fn error_chain(error: &Error) -> Vec<String> {
+    error.chain().map(|e| e.to_string()).collect()
+}
+
+/// Records the activity in the session's log and notifies the user of its outcome
+pub async fn report(
+    session: &SharedSession,
+    tx: &Sender<Message<Key, Transition>>,
+    activity: Activity,
+) -> Result<()> {
+    let notification = activity.notification();
+    session.lock().unwrap().record(activity);
+    send_notification(tx.clone(), notification).await
+}
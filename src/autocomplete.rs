@@ -1,28 +1,220 @@
-#[derive(Debug)]
-pub struct Autocomplete<'a> {
-    possibles: Vec<&'a str>,
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+
+use crate::{
+    commands::{ArgKind, CommandSpec, COMMANDS},
+    docker::{prune::PruneKind, store::ResourceStore},
+};
+
+/// Live values from the daemon (and the current page) which can be offered as
+/// completions for command arguments
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Resources {
+    /// Container names, along with whether the container is running
+    pub containers: Vec<(String, bool)>,
+    pub images: Vec<String>,
+    pub networks: Vec<String>,
+    pub volumes: Vec<String>,
+    /// Columns of the table on the current page
+    pub columns: Vec<String>,
+}
+
+impl Resources {
+    /// Takes the names of all containers, images, networks and volumes from the store
+    pub fn from_store(store: &ResourceStore) -> Self {
+        Self {
+            containers: store
+                .containers()
+                .into_iter()
+                .flat_map(|c| {
+                    c.names
+                        .split(", ")
+                        .map(|n| (n.to_string(), c.running))
+                        .collect::<Vec<_>>()
+                })
+                .collect(),
+            images: store
+                .images()
+                .into_iter()
+                .filter(|i| !i.is_dangling())
+                .flat_map(|i| i.tags)
+                .filter(|t| !t.contains("<none>"))
+                .collect(),
+            networks: store.networks().into_iter().map(|n| n.name).collect(),
+            volumes: store.volumes().into_iter().map(|v| v.name).collect(),
+            columns: vec![],
+        }
+    }
+}
+
+/// The candidates for completing the word under the cursor
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Completion {
+    /// Byte offset in the input at which the word being completed starts
+    pub start: usize,
+    /// Candidates, best match first
+    pub candidates: Vec<String>,
+}
+
+pub struct Autocomplete {
+    matcher: SkimMatcherV2,
+}
+
+impl std::fmt::Debug for Autocomplete {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Autocomplete").finish_non_exhaustive()
+    }
+}
+
+impl Default for Autocomplete {
+    fn default() -> Self {
+        Self {
+            matcher: SkimMatcherV2::default().smart_case(),
+        }
+    }
 }
 
-impl<'a> Autocomplete<'a> {
-    pub fn from(mut possibles: Vec<&'a str>) -> Self {
-        possibles.sort();
-        Self { possibles }
+impl Autocomplete {
+    /// Finds the candidates for the final word of the input, based on the position
+    /// of the word in the command and the given resources
+    pub fn complete(&self, input: &str, resources: &Resources) -> Completion {
+        // Whitespace may be more than one byte long, eg a non-breaking space
+        let start = input
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map(|(idx, c)| idx + c.len_utf8())
+            .unwrap_or(0);
+        let current = &input[start..];
+        let mut previous = input[..start].split_whitespace().collect::<Vec<&str>>();
+        // The command run in a new tab is completed as if it had been typed alone
+        if previous
+            .first()
+            .is_some_and(|name| CommandSpec::find(name).is_some_and(|c| c.name == "tabnew"))
+        {
+            previous.remove(0);
+        }
+
+        let possibles = match previous.split_first() {
+            // Only attempt to complete a command once something has been typed, to
+            // avoid the dropdown appearing as soon as the prompt is opened
+            None if current.is_empty() => vec![],
+            None => COMMANDS
+                .iter()
+                .flat_map(|c| std::iter::once(c.name).chain(c.aliases.iter().copied()))
+                .map(String::from)
+                .collect(),
+            Some((name, args)) => match CommandSpec::find(name).and_then(|c| c.arg(args.len())) {
+                Some(kind) => Self::possibles(kind, current, resources),
+                None => vec![],
+            },
+        };
+
+        Completion {
+            start,
+            candidates: self.rank(current, possibles),
+        }
     }
 
-    pub fn get_completion(&self, current: &str) -> Option<String> {
-        // Only want to attempt completion for commands with at least 2 letters
-        // as 1 letter commands are possible and it could create confusion
-        if current.len() < 2 {
-            return None;
+    fn possibles(kind: ArgKind, current: &str, resources: &Resources) -> Vec<String> {
+        // Values (eg `until=24h`) are free text, so only the keys are completed
+        let is_value = current.contains('=');
+
+        match kind {
+            ArgKind::RunningContainer => resources
+                .containers
+                .iter()
+                .filter(|(_, running)| *running)
+                .map(|(name, _)| name.clone())
+                .collect(),
+            ArgKind::Resource => resources
+                .containers
+                .iter()
+                .map(|(name, _)| name)
+                .chain(&resources.images)
+                .chain(&resources.networks)
+                .chain(&resources.volumes)
+                .cloned()
+                .collect(),
+            ArgKind::Column => resources.columns.clone(),
+            ArgKind::ColumnFilter if is_value => vec![],
+            ArgKind::ColumnFilter => resources.columns.iter().map(|c| format!("{c}=")).collect(),
+            ArgKind::PruneOption if is_value => vec![],
+            ArgKind::PruneOption => PruneKind::ALL
+                .iter()
+                .map(|k| k.key())
+                .chain(["--all", "until=", "label=", "label!="])
+                .map(String::from)
+                .collect(),
+            ArgKind::Literal(values) => values.iter().map(|v| v.to_string()).collect(),
         }
+    }
+
+    /// Orders the possibles by how well they match, then by length and finally
+    /// alphabetically, dropping any which do not match at all
+    fn rank(&self, current: &str, possibles: Vec<String>) -> Vec<String> {
+        let mut scored = possibles
+            .into_iter()
+            .filter_map(|p| {
+                if current.is_empty() {
+                    return Some((0, p));
+                }
+                self.matcher
+                    .fuzzy_match(&p, current)
+                    .map(|score| (score, p))
+            })
+            .collect::<Vec<(i64, String)>>();
 
-        for possible in &self.possibles {
-            if possible.starts_with(current) {
-                let candidate = String::from(*possible);
-                return Some(candidate);
-            }
+        scored.sort_by(|(a_score, a), (b_score, b)| {
+            b_score
+                .cmp(a_score)
+                .then(a.len().cmp(&b.len()))
+                .then(a.cmp(b))
+        });
+        scored.dedup_by(|(_, a), (_, b)| a == b);
+
+        scored
+            .into_iter()
+            .map(|(_, p)| p)
+            // There is nothing to complete if the word is already the only candidate
+            .filter(|p| p != current)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resources() -> Resources {
+        Resources {
+            containers: vec![("web".into(), true), ("worker".into(), false)],
+            networks: vec!["bridge".into()],
+            volumes: vec!["backups".into()],
+            ..Default::default()
         }
+    }
+
+    #[test]
+    fn completes_running_containers_for_logs() {
+        let completion = Autocomplete::default().complete("logs we", &resources());
+        assert_eq!(completion.start, 5);
+        assert_eq!(completion.candidates, vec!["web".to_string()]);
+    }
+
+    #[test]
+    fn completes_after_multi_byte_whitespace() {
+        let input = "logs\u{a0}we";
+        let completion = Autocomplete::default().complete(input, &resources());
+        assert_eq!(completion.start, "logs\u{a0}".len());
+        assert_eq!(&input[completion.start..], "we");
+        assert_eq!(completion.candidates, vec!["web".to_string()]);
+    }
 
-        None
+    #[test]
+    fn completes_networks_and_volumes_for_describe() {
+        let completion = Autocomplete::default().complete("describe b", &resources());
+        assert_eq!(completion.start, 9);
+        assert!(completion.candidates.contains(&"bridge".to_string()));
+        assert!(completion.candidates.contains(&"backups".to_string()));
     }
 }
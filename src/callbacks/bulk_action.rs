@@ -0,0 +1,217 @@
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use color_eyre::eyre::Result;
+use futures::future::{join_all, BoxFuture};
+use tokio::sync::mpsc::Sender;
+use tracing::{instrument, Instrument};
+
+use crate::{
+    activity::{Activity, ActivityKind},
+    events::{Key, Message, Transition},
+    state::SharedSession,
+    traits::Callback,
+};
+
+/// Maximum number of targets to list by name in confirmation and report messages
+const MAX_LISTED: usize = 10;
+
+pub type BulkTask = Box<dyn Fn() -> BoxFuture<'static, Result<()>> + Send + Sync>;
+
+/// The outcome of a bulk action, for each of its targets
+#[derive(Debug, Clone)]
+pub struct BulkReport {
+    verb: String,
+    results: Vec<(String, Result<(), String>)>,
+}
+
+impl BulkReport {
+    /// Whether the action failed for any of its targets
+    pub fn has_failures(&self) -> bool {
+        self.results.iter().any(|(_, r)| r.is_err())
+    }
+
+    /// A one line summary of an action which succeeded for every target
+    pub fn summary(&self, noun: &str) -> String {
+        format!(
+            "{} succeeded for {} {noun}",
+            capitalise(&self.verb),
+            self.results.len()
+        )
+    }
+
+    pub fn title(&self) -> String {
+        let n_failed = self.results.iter().filter(|(_, r)| r.is_err()).count();
+        if n_failed == 0 {
+            format!("{} succeeded", capitalise(&self.verb))
+        } else {
+            format!(
+                "{} failed for {n_failed} of {}",
+                capitalise(&self.verb),
+                self.results.len()
+            )
+        }
+    }
+
+    pub fn message(&self) -> String {
+        let lines = self
+            .results
+            .iter()
+            .map(|(target, result)| match result {
+                Ok(_) => format!("✔ {target}"),
+                Err(e) => format!("✘ {target}: {e}"),
+            })
+            .collect::<Vec<String>>();
+        truncated_list(&lines, "\n")
+    }
+}
+
+/// A callback which applies an action to several targets at once.  The targets are
+/// processed concurrently in the background; once all are complete a report of the
+/// outcome for each target is written to the shared report slot, and each is
+/// recorded in the session's activity
+pub struct BulkAction {
+    kind: ActivityKind,
+    /// What each target is, eg `container`
+    noun: &'static str,
+    targets: Vec<(String, BulkTask)>,
+    /// Shown after the targets in the confirmation prompt
+    warning: Option<String>,
+    report: Arc<Mutex<Option<BulkReport>>>,
+    session: SharedSession,
+    tx: Sender<Message<Key, Transition>>,
+}
+
+impl Debug for BulkAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BulkAction")
+            .field("kind", &self.kind)
+            .field(
+                "targets",
+                &self.targets.iter().map(|(t, _)| t).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl BulkAction {
+    pub fn new(
+        kind: ActivityKind,
+        noun: &'static str,
+        targets: Vec<(String, BulkTask)>,
+        report: Arc<Mutex<Option<BulkReport>>>,
+        session: SharedSession,
+        tx: Sender<Message<Key, Transition>>,
+    ) -> Self {
+        Self {
+            kind,
+            noun,
+            targets,
+            warning: None,
+            report,
+            session,
+            tx,
+        }
+    }
+
+    /// Warns about some of the targets, which are listed by name after the warning
+    /// in the confirmation prompt.  Nothing is added if there are no such targets
+    pub fn with_warning(mut self, warning: &str, targets: &[String]) -> Self {
+        if !targets.is_empty() {
+            self.warning = Some(format!("{warning}\n\n{}", truncated_list(targets, ", ")));
+        }
+        self
+    }
+
+    pub fn title(&self) -> String {
+        capitalise(self.kind.verb())
+    }
+
+    /// A confirmation prompt listing each of the targets
+    pub fn confirmation(&self) -> String {
+        let names = self
+            .targets
+            .iter()
+            .map(|(t, _)| t.clone())
+            .collect::<Vec<String>>();
+        let mut confirmation = format!(
+            "Are you sure you wish to {} {} {}s?\n\n{}",
+            self.kind.verb(),
+            self.targets.len(),
+            self.noun,
+            truncated_list(&names, ", ")
+        );
+        if let Some(warning) = &self.warning {
+            confirmation.push_str(&format!("\n\n{warning}"));
+        }
+        confirmation
+    }
+}
+
+#[async_trait]
+impl Callback for BulkAction {
+    #[instrument(name = "bulk_action", skip_all, fields(kind = ?self.kind, targets = self.targets.len()))]
+    async fn call(&self) -> Result<()> {
+        let names = self
+            .targets
+            .iter()
+            .map(|(t, _)| t.clone())
+            .collect::<Vec<String>>();
+        let futures = self
+            .targets
+            .iter()
+            .map(|(name, task)| {
+                let activity = Activity::begin(self.kind, format!("{} {name}", self.noun));
+                let future = task();
+                async move {
+                    let result = future.await;
+                    (activity.finish(&result), result)
+                }
+            })
+            .collect::<Vec<_>>();
+        let verb = self.kind.verb().to_string();
+        let report = self.report.clone();
+        let session = self.session.clone();
+        let tx = self.tx.clone();
+
+        tokio::spawn(
+            async move {
+                let mut results = vec![];
+                for (activity, result) in join_all(futures).await {
+                    session.lock().unwrap().record(activity);
+                    results.push(result.map_err(|e| format!("{e}")));
+                }
+                *report.lock().unwrap() = Some(BulkReport {
+                    verb,
+                    results: names.into_iter().zip(results).collect(),
+                });
+                let _ = tx.send(Message::Tick).await;
+            }
+            .in_current_span(),
+        );
+        Ok(())
+    }
+}
+
+fn truncated_list(items: &[String], separator: &str) -> String {
+    let mut listed = items
+        .iter()
+        .take(MAX_LISTED)
+        .cloned()
+        .collect::<Vec<String>>();
+    if items.len() > MAX_LISTED {
+        listed.push(format!("... and {} more", items.len() - MAX_LISTED));
+    }
+    listed.join(separator)
+}
+
+fn capitalise(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -1,17 +1,21 @@
 use crate::{
+    activity::{self, Activity, ActivityKind},
     docker::container::DockerContainer,
     events::{Key, Message, Transition},
+    state::SharedSession,
     traits::Callback,
 };
 use async_trait::async_trait;
 use color_eyre::eyre::Result;
 use tokio::sync::mpsc::Sender;
+use tracing::{instrument, Instrument};
 
 #[derive(Debug)]
 pub struct DeleteContainer {
     docker: bollard::Docker,
     container: DockerContainer,
     force: bool,
+    session: SharedSession,
     tx: Sender<Message<Key, Transition>>,
 }
 
@@ -20,32 +24,39 @@ impl DeleteContainer {
         docker: bollard::Docker,
         container: DockerContainer,
         force: bool,
+        session: SharedSession,
         tx: Sender<Message<Key, Transition>>,
     ) -> Self {
         Self {
             docker,
             container,
             force,
+            session,
             tx,
         }
     }
 }
 #[async_trait]
 impl Callback for DeleteContainer {
+    #[instrument(name = "delete_container", skip_all, fields(id = %self.container.id))]
     async fn call(&self) -> Result<()> {
         let container = self.container.clone();
         let docker = self.docker.clone();
         let force = self.force;
+        let session = self.session.clone();
         let tx = self.tx.clone();
-        tokio::spawn(async move {
-            let message = if container.delete(&docker, force).await.is_ok() {
-                Message::Tick
-            } else {
-                let msg = format!("Failed to delete container {}", container.id);
-                Message::Error(msg)
-            };
-            let _ = tx.send(message).await;
-        });
+        tokio::spawn(
+            async move {
+                let activity = Activity::begin(
+                    ActivityKind::Delete,
+                    format!("container {}", container.names),
+                );
+                let result = container.delete(&docker, force).await;
+                let _ = activity::report(&session, &tx, activity.finish(&result)).await;
+                let _ = tx.send(Message::Tick).await;
+            }
+            .in_current_span(),
+        );
         Ok(())
     }
 }
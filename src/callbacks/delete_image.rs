@@ -1,28 +1,51 @@
-use crate::{docker::image::DockerImage, traits::Callback};
+use crate::{
+    activity::{self, Activity, ActivityKind},
+    docker::image::DockerImage,
+    events::{Key, Message, Transition},
+    state::SharedSession,
+    traits::Callback,
+};
 use async_trait::async_trait;
 use color_eyre::eyre::Result;
+use tokio::sync::mpsc::Sender;
+use tracing::instrument;
 
 #[derive(Debug)]
 pub struct DeleteImage {
     docker: bollard::Docker,
     image: DockerImage,
     force: bool,
+    session: SharedSession,
+    tx: Sender<Message<Key, Transition>>,
 }
 
 impl DeleteImage {
-    pub fn new(docker: bollard::Docker, image: DockerImage, force: bool) -> Self {
+    pub fn new(
+        docker: bollard::Docker,
+        image: DockerImage,
+        force: bool,
+        session: SharedSession,
+        tx: Sender<Message<Key, Transition>>,
+    ) -> Self {
         Self {
             docker,
             image,
             force,
+            session,
+            tx,
         }
     }
 }
 
 #[async_trait]
 impl Callback for DeleteImage {
+    #[instrument(name = "delete_image", skip_all, fields(id = %self.image.id))]
     async fn call(&self) -> Result<()> {
-        let _ = self.image.delete(&self.docker, self.force).await?;
-        Ok(())
+        let activity = Activity::begin(
+            ActivityKind::Delete,
+            format!("image {}", self.image.get_full_name()),
+        );
+        let result = self.image.delete(&self.docker, self.force).await;
+        activity::report(&self.session, &self.tx, activity.finish(&result)).await
     }
 }
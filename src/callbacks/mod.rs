@@ -1,4 +1,8 @@
+pub mod bulk_action;
 pub mod delete_container;
 pub mod delete_image;
+pub mod prune;
 
+pub use bulk_action::BulkAction;
 pub use delete_container::DeleteContainer;
+pub use prune::PruneResources;
@@ -0,0 +1,72 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    activity::{Activity, ActivityKind},
+    docker::prune::{prune, PruneOptions, PruneReport},
+    events::{Key, Message, Transition},
+    state::SharedSession,
+    traits::Callback,
+};
+use async_trait::async_trait;
+use color_eyre::eyre::Result;
+use tokio::sync::mpsc::Sender;
+use tracing::{instrument, Instrument};
+
+/// The outcome of a prune; an error is kept as a message so it can be shown to the user
+pub type PruneOutcome = Result<PruneReport, String>;
+
+#[derive(Debug)]
+pub struct PruneResources {
+    docker: bollard::Docker,
+    /// The path of the daemon's socket, for the commands which use the docker CLI
+    docker_path: String,
+    options: PruneOptions,
+    outcome: Arc<Mutex<Option<PruneOutcome>>>,
+    session: SharedSession,
+    tx: Sender<Message<Key, Transition>>,
+}
+
+impl PruneResources {
+    pub fn new(
+        docker: bollard::Docker,
+        docker_path: String,
+        options: PruneOptions,
+        outcome: Arc<Mutex<Option<PruneOutcome>>>,
+        session: SharedSession,
+        tx: Sender<Message<Key, Transition>>,
+    ) -> Self {
+        Self {
+            docker,
+            docker_path,
+            options,
+            outcome,
+            session,
+            tx,
+        }
+    }
+}
+
+#[async_trait]
+impl Callback for PruneResources {
+    #[instrument(name = "prune_resources", skip_all, fields(kind = %self.options.kind))]
+    async fn call(&self) -> Result<()> {
+        let docker = self.docker.clone();
+        let docker_path = self.docker_path.clone();
+        let options = self.options.clone();
+        let outcome = self.outcome.clone();
+        let session = self.session.clone();
+        let tx = self.tx.clone();
+        tokio::spawn(
+            async move {
+                // The outcome is shown on the prune page, so it is only recorded here
+                let activity = Activity::begin(ActivityKind::Prune, options.kind.to_string());
+                let result = prune(&docker, &docker_path, &options).await;
+                session.lock().unwrap().record(activity.finish(&result));
+                *outcome.lock().unwrap() = Some(result.map_err(|e| format!("{e:#}")));
+                let _ = tx.send(Message::Tick).await;
+            }
+            .in_current_span(),
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,177 @@
+use std::{
+    env,
+    io::{self, Write},
+    path::Path,
+    process,
+};
+
+use clap::Subcommand;
+use color_eyre::eyre::{bail, Context, Result};
+
+use crate::config::{self, Config, Overrides};
+
+/// The `ducker config` subcommands
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Validate a config file, reporting unknown keys and invalid values
+    Check {
+        /// The file to check; defaults to the user and project config files
+        path: Option<std::path::PathBuf>,
+    },
+    /// Print the config in effect, including every default value
+    Show {
+        /// Show which layer of config set each value
+        #[arg(long)]
+        origin: bool,
+    },
+    /// Print the path of the user config file
+    Path,
+    /// Open the config file in $VISUAL or $EDITOR, validating it once the editor exits
+    Edit,
+}
+
+impl ConfigCommand {
+    /// Runs the command, returning whether or not it succeeded
+    pub fn run(self, overrides: Overrides) -> Result<bool> {
+        match self {
+            Self::Check { path: Some(path) } => check(&path),
+            Self::Check { path: None } => {
+                let paths = [
+                    Some(overrides.config_file()?),
+                    config::project_config_path(),
+                ]
+                .into_iter()
+                .flatten()
+                .filter(|path| path.exists())
+                .collect::<Vec<_>>();
+                if paths.is_empty() {
+                    println!("no config files found; the default config is in use");
+                    return Ok(true);
+                }
+
+                let mut valid = true;
+                for path in paths {
+                    valid &= check(&path)?;
+                }
+                Ok(valid)
+            }
+            Self::Show { origin: false } => {
+                let config = Config::new(overrides)?;
+                print!("{}", serde_yml::to_string(&config)?);
+                Ok(true)
+            }
+            Self::Show { origin: true } => {
+                show_origins(&Config::new(overrides)?)?;
+                Ok(true)
+            }
+            Self::Path => {
+                println!("{}", overrides.config_file()?.display());
+                Ok(true)
+            }
+            Self::Edit => edit(&overrides.config_file()?),
+        }
+    }
+}
+
+/// Writes the default config, first asking before overwriting an existing one
+pub fn export_default_config(overrides: &Overrides) -> Result<()> {
+    let path = overrides.config_file()?;
+    if path.exists()
+        && !confirm(
+            &format!("{} already exists; overwrite it?", path.display()),
+            false,
+        )?
+    {
+        return Ok(());
+    }
+    config::write_default_config(&path).context("failed to write default config")?;
+    println!("wrote default config to {}", path.display());
+    Ok(())
+}
+
+fn check(path: &Path) -> Result<bool> {
+    let problems = Config::check(path)?;
+    if problems.is_empty() {
+        println!("{} is valid", path.display());
+        return Ok(true);
+    }
+
+    for problem in &problems {
+        println!("{}: {problem}", path.display());
+    }
+    Ok(false)
+}
+
+/// Prints every value in the config, with the layer which set it lined up alongside
+fn show_origins(config: &Config) -> Result<()> {
+    let values = config
+        .values()?
+        .into_iter()
+        .map(|(path, value, origin)| (format!("{path}: {value}"), origin))
+        .collect::<Vec<_>>();
+    let width = values
+        .iter()
+        .map(|(line, _)| line.chars().count())
+        .max()
+        .unwrap_or_default();
+
+    for (line, origin) in values {
+        println!("{line:width$}  # {origin}");
+    }
+    Ok(())
+}
+
+fn edit(path: &Path) -> Result<bool> {
+    // Start from the defaults, so that the available options are laid out
+    if !path.exists() {
+        config::write_default_config(path).context("failed to write default config")?;
+    }
+
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".into());
+    // The editor may include arguments, eg `code --wait`
+    let mut args = editor.split_whitespace();
+    let Some(program) = args.next() else {
+        bail!("no editor set; set $VISUAL or $EDITOR")
+    };
+
+    loop {
+        let status = process::Command::new(program)
+            .args(args.clone())
+            .arg(path)
+            .status()
+            .with_context(|| format!("unable to run {editor}"))?;
+        if !status.success() {
+            bail!("{editor} exited with {status}");
+        }
+
+        if check(path)? {
+            return Ok(true);
+        }
+        if !confirm("edit the config again?", true)? {
+            return Ok(false);
+        }
+    }
+}
+
+/// Asks a yes or no question on the terminal, returning `default` if the user
+/// just presses enter and `false` if there is no input at all
+fn confirm(question: &str, default: bool) -> Result<bool> {
+    let options = if default { "[Y/n]" } else { "[y/N]" };
+    print!("{question} {options} ");
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    // Without a terminal to answer from, nothing should be done
+    if io::stdin().read_line(&mut answer)? == 0 {
+        println!();
+        return Ok(false);
+    }
+    let answer = match answer.trim().to_lowercase().as_str() {
+        "" => default,
+        "y" | "yes" => true,
+        _ => false,
+    };
+    Ok(answer)
+}
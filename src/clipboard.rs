@@ -0,0 +1,15 @@
+use std::io::{stdout, Write};
+
+use base64::Engine;
+use color_eyre::eyre::{Context, Result};
+
+/// Copies text to the system clipboard using the OSC 52 terminal escape sequence.
+/// This is supported by most modern terminal emulators (and tmux with
+/// `set-clipboard on`), and works over ssh without needing access to a display server
+pub fn copy(text: &str) -> Result<()> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut out = stdout();
+    write!(out, "\x1b]52;c;{encoded}\x07").context("unable to write to clipboard")?;
+    out.flush().context("unable to write to clipboard")?;
+    Ok(())
+}
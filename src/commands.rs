@@ -0,0 +1,395 @@
+use color_eyre::eyre::{bail, eyre, Result};
+
+use crate::{docker::prune::PruneOptions, state::SortDirection};
+
+/// Describes a command which can be entered at the prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    pub usage: &'static str,
+    pub description: &'static str,
+    /// The kind of each positional argument, used to offer completions
+    pub args: &'static [ArgKind],
+    /// Whether the final kind of argument may be repeated
+    pub variadic: bool,
+}
+
+/// The kind of value expected by an argument to a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgKind {
+    /// The name of a running container
+    RunningContainer,
+    /// The name of any describable resource; containers, images, networks or volumes
+    Resource,
+    /// A column of the current table
+    Column,
+    /// A column of the current table followed by `=`
+    ColumnFilter,
+    /// The kind of resource to prune, or a prune flag or filter
+    PruneOption,
+    /// One of a fixed set of values
+    Literal(&'static [&'static str]),
+}
+
+impl CommandSpec {
+    fn matches(&self, name: &str) -> bool {
+        self.name == name || self.aliases.contains(&name)
+    }
+
+    /// Finds the command with the given name or alias
+    pub fn find(name: &str) -> Option<&'static Self> {
+        COMMANDS.iter().find(|c| c.matches(name))
+    }
+
+    /// The kind of the argument at the given (zero-based) position
+    pub fn arg(&self, position: usize) -> Option<ArgKind> {
+        match self.args.get(position) {
+            Some(kind) => Some(*kind),
+            None if self.variadic => self.args.last().copied(),
+            None => None,
+        }
+    }
+}
+
+/// The registry of all commands supported by the prompt
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "containers",
+        aliases: &["container"],
+        usage: "containers",
+        description: "Open the Containers page",
+        args: &[],
+        variadic: false,
+    },
+    CommandSpec {
+        name: "images",
+        aliases: &["image"],
+        usage: "images",
+        description: "Open the Images page",
+        args: &[],
+        variadic: false,
+    },
+    CommandSpec {
+        name: "logs",
+        aliases: &["log"],
+        usage: "logs <container> [--since <time>]",
+        description: "View the logs of a container",
+        args: &[ArgKind::RunningContainer, ArgKind::Literal(&["--since"])],
+        variadic: false,
+    },
+    CommandSpec {
+        name: "describe",
+        aliases: &[],
+        usage: "describe <container|image|network|volume>",
+        description: "Describe a container, image, network or volume",
+        args: &[ArgKind::Resource],
+        variadic: false,
+    },
+    CommandSpec {
+        name: "exec",
+        aliases: &[],
+        usage: "exec <container> [command]",
+        description: "Exec into a running container",
+        args: &[ArgKind::RunningContainer],
+        variadic: false,
+    },
+    CommandSpec {
+        name: "filter",
+        aliases: &[],
+        usage: "filter [[<column>=]<text>]",
+        description: "Filter the current table, or clear the filter",
+        args: &[ArgKind::ColumnFilter],
+        variadic: false,
+    },
+    CommandSpec {
+        name: "sort",
+        aliases: &[],
+        usage: "sort <column> [asc|desc]",
+        description: "Sort the current table",
+        args: &[ArgKind::Column, ArgKind::Literal(&["asc", "desc"])],
+        variadic: false,
+    },
+    CommandSpec {
+        name: "prune",
+        aliases: &[],
+        usage: "prune [<kind>] [<filter>...] [--all]",
+        description: "Preview and prune unused resources",
+        args: &[ArgKind::PruneOption],
+        variadic: true,
+    },
+    CommandSpec {
+        name: "activity",
+        aliases: &[],
+        usage: "activity",
+        description: "Open the Activity page, listing everything done this session",
+        args: &[],
+        variadic: false,
+    },
+    CommandSpec {
+        name: "tabnew",
+        aliases: &[],
+        usage: "tabnew [<command>]",
+        description: "Open a new tab, running the given command in it",
+        args: &[],
+        variadic: false,
+    },
+    CommandSpec {
+        name: "history",
+        aliases: &[],
+        usage: "history",
+        description: "Show or hide the path taken to the current page",
+        args: &[],
+        variadic: false,
+    },
+    CommandSpec {
+        name: "debug",
+        aliases: &[],
+        usage: "debug",
+        description: "Show or hide recent trace events and Docker API timings",
+        args: &[],
+        variadic: false,
+    },
+    CommandSpec {
+        name: "quit",
+        aliases: &["q"],
+        usage: "quit",
+        description: "Close the application",
+        args: &[],
+        variadic: false,
+    },
+];
+
+/// A command entered at the prompt, along with its arguments
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Containers,
+    Images,
+    Logs {
+        container: String,
+        since: Option<String>,
+    },
+    Describe {
+        target: String,
+    },
+    Exec {
+        container: String,
+        command: Option<String>,
+    },
+    Filter {
+        query: String,
+    },
+    Sort {
+        column: String,
+        direction: Option<SortDirection>,
+    },
+    Prune(PruneOptions),
+    Activity,
+    /// Opens a new tab, running the command in it if given
+    TabNew(Option<Box<Command>>),
+    History,
+    Debug,
+    Quit,
+}
+
+impl Command {
+    /// Parses the input to the prompt, returning `None` if it is empty
+    pub fn parse(input: &str) -> Result<Option<Self>> {
+        Self::parse_tokens(&tokenise(input)?)
+    }
+
+    fn parse_tokens(tokens: &[String]) -> Result<Option<Self>> {
+        let Some((name, args)) = tokens.split_first() else {
+            return Ok(None);
+        };
+        let args = args.iter().map(String::as_str).collect::<Vec<&str>>();
+
+        let spec = CommandSpec::find(name).ok_or_else(|| eyre!("unknown command \"{name}\""))?;
+        let usage = || eyre!("usage: {}", spec.usage);
+
+        let command = match (spec.name, args.as_slice()) {
+            ("containers", []) => Self::Containers,
+            ("images", []) => Self::Images,
+            ("activity", []) => Self::Activity,
+            ("history", []) => Self::History,
+            ("debug", []) => Self::Debug,
+            ("quit", []) => Self::Quit,
+            ("logs", [container]) => Self::Logs {
+                container: container.to_string(),
+                since: None,
+            },
+            ("logs", [container, "--since", since]) | ("logs", ["--since", since, container]) => {
+                Self::Logs {
+                    container: container.to_string(),
+                    since: Some(since.to_string()),
+                }
+            }
+            ("describe", [target]) => Self::Describe {
+                target: target.to_string(),
+            },
+            ("exec", [container]) => Self::Exec {
+                container: container.to_string(),
+                command: None,
+            },
+            ("exec", [container, command]) => Self::Exec {
+                container: container.to_string(),
+                command: Some(command.to_string()),
+            },
+            ("filter", query) => Self::Filter {
+                query: query.join(" "),
+            },
+            ("sort", [column]) => Self::Sort {
+                column: column.to_string(),
+                direction: None,
+            },
+            ("sort", [column, direction]) => Self::Sort {
+                column: column.to_string(),
+                direction: Some(match *direction {
+                    "asc" | "ascending" => SortDirection::Ascending,
+                    "desc" | "descending" => SortDirection::Descending,
+                    _ => return Err(usage()),
+                }),
+            },
+            ("tabnew", []) => Self::TabNew(None),
+            ("tabnew", _) => match Self::parse_tokens(&tokens[1..])? {
+                Some(command) if command.opens_page() => Self::TabNew(Some(Box::new(command))),
+                _ => return Err(usage()),
+            },
+            ("prune", args) => {
+                Self::Prune(PruneOptions::parse(args).map_err(|e| eyre!("{e}\n\n{}", usage()))?)
+            }
+            _ => return Err(usage()),
+        };
+        Ok(Some(command))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Containers => "containers",
+            Self::Images => "images",
+            Self::Logs { .. } => "logs",
+            Self::Describe { .. } => "describe",
+            Self::Exec { .. } => "exec",
+            Self::Filter { .. } => "filter",
+            Self::Sort { .. } => "sort",
+            Self::Prune(_) => "prune",
+            Self::Activity => "activity",
+            Self::TabNew(_) => "tabnew",
+            Self::History => "history",
+            Self::Debug => "debug",
+            Self::Quit => "quit",
+        }
+    }
+
+    /// Whether the command opens a page or acts on the current page, rather than
+    /// on the app as a whole
+    fn opens_page(&self) -> bool {
+        !matches!(
+            self,
+            Self::TabNew(_) | Self::History | Self::Debug | Self::Quit
+        )
+    }
+}
+
+/// Splits the input into whitespace separated tokens, treating anything between
+/// single or double quotes as a single token
+fn tokenise(input: &str) -> Result<Vec<String>> {
+    let mut tokens = vec![];
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut quote = None;
+
+    for c in input.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_token = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_token = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        bail!("unterminated quote in command");
+    }
+    if in_token {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenise_keeps_quoted_text_together() {
+        assert_eq!(
+            tokenise(r#"exec web "sh -c 'ls -l'""#).unwrap(),
+            vec!["exec", "web", "sh -c 'ls -l'"]
+        );
+        assert_eq!(tokenise("  logs   web ").unwrap(), vec!["logs", "web"]);
+        assert_eq!(tokenise("filter ''").unwrap(), vec!["filter", ""]);
+        assert!(tokenise("exec web \"sh").is_err());
+    }
+
+    #[test]
+    fn parses_commands_and_their_arguments() {
+        assert_eq!(Command::parse("").unwrap(), None);
+        assert_eq!(Command::parse("q").unwrap(), Some(Command::Quit));
+        assert_eq!(
+            Command::parse("logs web --since 1h").unwrap(),
+            Some(Command::Logs {
+                container: "web".into(),
+                since: Some("1h".into()),
+            })
+        );
+        assert_eq!(
+            Command::parse("logs --since 1h web").unwrap(),
+            Command::parse("logs web --since 1h").unwrap()
+        );
+        assert_eq!(
+            Command::parse("sort created desc").unwrap(),
+            Some(Command::Sort {
+                column: "created".into(),
+                direction: Some(SortDirection::Descending),
+            })
+        );
+        assert_eq!(
+            Command::parse("filter name=web image").unwrap(),
+            Some(Command::Filter {
+                query: "name=web image".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn parses_a_command_to_run_in_a_new_tab() {
+        assert_eq!(
+            Command::parse("tabnew describe nginx").unwrap(),
+            Some(Command::TabNew(Some(Box::new(Command::Describe {
+                target: "nginx".into(),
+            }))))
+        );
+        assert!(Command::parse("tabnew quit").is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_commands_and_bad_arguments() {
+        assert!(Command::parse("launch").is_err());
+        assert!(Command::parse("logs").is_err());
+        assert!(Command::parse("sort created sideways").is_err());
+        assert!(Command::parse("quit now").is_err());
+    }
+}
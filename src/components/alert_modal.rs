@@ -11,6 +11,7 @@ use ratatui::{
 
 use crate::{
     events::{message::MessageResponse, Key},
+    theme::Theme,
     traits::{Component, ModalComponent},
     widgets::modal::ModalWidget,
 };
@@ -27,14 +28,16 @@ pub struct AlertModal<P> {
     pub discriminator: P,
     pub state: ModalState,
     title: String,
+    theme: Theme,
 }
 
 impl<P> AlertModal<P> {
-    pub fn new(title: String, discriminator: P) -> Self {
+    pub fn new(title: String, discriminator: P, theme: Theme) -> Self {
         Self {
             discriminator,
             state: ModalState::default(),
             title,
+            theme,
         }
     }
 
@@ -79,9 +82,9 @@ where
             .wrap(Wrap { trim: true })
             .centered();
 
-        let opt = Span::from("Press any key to continue...");
+        let opt = Span::styled("Press any key to continue...", self.theme.modal_options());
 
-        let modal = ModalWidget::new(title, message, vec![opt]);
+        let modal = ModalWidget::new(title, message, vec![opt]).border_style(self.theme.border());
 
         f.render_widget(modal, area);
     }
@@ -6,7 +6,6 @@ use itertools::Itertools;
 use color_eyre::eyre::Result;
 use ratatui::{
     layout::{Alignment, Rect},
-    style::{Modifier, Style},
     text::{Span, Text},
     widgets::{block::Title, Paragraph, Wrap},
     Frame,
@@ -14,6 +13,7 @@ use ratatui::{
 
 use crate::{
     events::{message::MessageResponse, Key},
+    theme::Theme,
     traits::{Callback, Component, ModalComponent},
     widgets::modal::ModalWidget,
 };
@@ -30,15 +30,17 @@ pub struct BooleanModal<P> {
     pub discriminator: P,
     pub state: ModalState,
     title: String,
+    theme: Theme,
     callback: Option<Arc<Mutex<dyn Callback>>>,
 }
 
 impl<P> BooleanModal<P> {
-    pub fn new(title: String, discriminator: P) -> Self {
+    pub fn new(title: String, discriminator: P, theme: Theme) -> Self {
         Self {
             discriminator,
             state: ModalState::default(),
             title,
+            theme,
             callback: None,
         }
     }
@@ -98,19 +100,13 @@ where
         let spans = [("Y/y/Enter", "Yes"), ("N/n", "No")]
             .iter()
             .flat_map(|(key, desc)| {
-                let key = Span::styled(
-                    format!(" <{key}> = "),
-                    Style::new().add_modifier(Modifier::ITALIC),
-                );
-                let desc = Span::styled(
-                    format!("{desc} "),
-                    Style::new().add_modifier(Modifier::ITALIC),
-                );
+                let key = Span::styled(format!(" <{key}> = "), self.theme.modal_options());
+                let desc = Span::styled(format!("{desc} "), self.theme.modal_options());
                 [key, desc]
             })
             .collect_vec();
 
-        let modal = ModalWidget::new(title, message, spans);
+        let modal = ModalWidget::new(title, message, spans).border_style(self.theme.border());
 
         f.render_widget(modal, area);
     }
@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+use tracing::Level;
+
+use crate::{config::Config, logging::TraceLog, traits::Component};
+
+/// Shows the most recent trace events alongside timings of calls to the Docker API,
+/// over the lower half of the page.  It is toggled with the `debug` command, and
+/// doesn't take any input so that ducker can be used as normal while it is open
+#[derive(Debug)]
+pub struct DebugOverlay {
+    config: Box<Config>,
+    trace_log: TraceLog,
+    visible: bool,
+}
+
+impl DebugOverlay {
+    pub fn new(config: Box<Config>, trace_log: TraceLog) -> Self {
+        Self {
+            config,
+            trace_log,
+            visible: false,
+        }
+    }
+
+    pub fn set_config(&mut self, config: Box<Config>) {
+        self.config = config;
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    fn level_style(&self, level: Level) -> Style {
+        match level {
+            Level::ERROR => self.config.theme.error(),
+            Level::WARN => self.config.theme.warning(),
+            Level::INFO => self.config.theme.info(),
+            _ => self.config.theme.footer(),
+        }
+    }
+
+    fn draw_events(&self, f: &mut Frame<'_>, area: Rect) {
+        let block = Block::bordered()
+            .title(" Events ")
+            .border_style(self.config.theme.border());
+        let height = block.inner(area).height as usize;
+
+        let events = self.trace_log.events();
+        let lines = events
+            .iter()
+            .skip(events.len().saturating_sub(height))
+            .map(|e| {
+                Line::from(vec![
+                    Span::from(e.time.format("%H:%M:%S%.3f ").to_string())
+                        .style(self.config.theme.footer()),
+                    Span::from(format!("{:<5} ", e.level)).style(self.level_style(e.level)),
+                    Span::from(format!("{}: ", e.target.trim_start_matches("ducker::")))
+                        .style(self.config.theme.footer()),
+                    Span::from(e.message.clone()),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn draw_calls(&self, f: &mut Frame<'_>, area: Rect) {
+        let rows = self.trace_log.call_stats().into_iter().map(|s| {
+            let failures = Cell::from(s.failures.to_string());
+            Row::new(vec![
+                Cell::from(s.name.clone()),
+                Cell::from(s.count.to_string()),
+                if s.failures > 0 {
+                    failures.style(self.config.theme.error())
+                } else {
+                    failures
+                },
+                Cell::from(format_duration(s.last)),
+                Cell::from(format_duration(s.mean())),
+                Cell::from(format_duration(s.max)),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Length(5),
+                Constraint::Length(5),
+                Constraint::Length(8),
+                Constraint::Length(8),
+                Constraint::Length(8),
+            ],
+        )
+        .header(
+            Row::new(vec!["Call", "Count", "Fail", "Last", "Mean", "Max"])
+                .style(self.config.theme.table_header()),
+        )
+        .block(
+            Block::bordered()
+                .title(" Docker API ")
+                .border_style(self.config.theme.border()),
+        );
+
+        f.render_widget(table, area);
+    }
+}
+
+impl Component for DebugOverlay {
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let [_, overlay] =
+            Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(area);
+        let [events, calls] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(overlay);
+
+        f.render_widget(Clear, overlay);
+        self.draw_events(f, events);
+        self.draw_calls(f, calls);
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_millis(1) {
+        format!("{}µs", duration.as_micros())
+    } else if duration < Duration::from_secs(1) {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{:.1}s", duration.as_secs_f64())
+    }
+}
@@ -0,0 +1,339 @@
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    Frame,
+};
+use regex::Regex;
+
+use crate::{
+    config::Config,
+    events::{message::MessageResponse, Key},
+    keymap::{Action, Input, Scope},
+    traits::Component,
+};
+
+const ESC_KEY: Key = Key::Esc;
+const ENTER_KEY: Key = Key::Enter;
+const TAB_KEY: Key = Key::Tab;
+
+/// The strategy used to match the filter input against table cells
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FilterMode {
+    #[default]
+    Substring,
+    Fuzzy,
+    Regex,
+}
+
+impl FilterMode {
+    fn next(self) -> Self {
+        match self {
+            Self::Substring => Self::Fuzzy,
+            Self::Fuzzy => Self::Regex,
+            Self::Regex => Self::Substring,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Substring => "substring",
+            Self::Fuzzy => "fuzzy",
+            Self::Regex => "regex",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum FilterState {
+    #[default]
+    Inactive,
+    Editing,
+    Applied,
+}
+
+/// A vim-style filter which narrows the rows of a table as the user types.
+///
+/// The `table.filter` key (`/` by default) opens the filter, `Tab` cycles between
+/// substring, fuzzy and regex matching while editing, `Enter` applies the filter
+/// and `Esc` clears it.  Prefixing the input with a column key, eg `status=exited`,
+/// only matches against that column.
+pub struct Filter {
+    config: Box<Config>,
+    columns: Vec<String>,
+    input: String,
+    /// The index of the column the filter is restricted to, if any
+    scope: Option<usize>,
+    mode: FilterMode,
+    state: FilterState,
+    regex: Option<Regex>,
+    regex_error: bool,
+    matcher: SkimMatcherV2,
+}
+
+impl std::fmt::Debug for Filter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Filter")
+            .field("input", &self.input)
+            .field("scope", &self.scope)
+            .field("mode", &self.mode)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl Filter {
+    pub fn new(config: Box<Config>) -> Self {
+        Self {
+            config,
+            columns: vec![],
+            input: String::new(),
+            scope: None,
+            mode: FilterMode::default(),
+            state: FilterState::default(),
+            regex: None,
+            regex_error: false,
+            matcher: SkimMatcherV2::default().smart_case(),
+        }
+    }
+
+    /// Sets the keys of the table's columns, in order, so the filter can be restricted
+    /// to a single column
+    pub fn with_columns(mut self, columns: Vec<String>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Applies a reloaded config, along with the table's columns which may have
+    /// changed with it
+    pub fn set_config(&mut self, config: Box<Config>, columns: Vec<String>) {
+        self.config = config;
+        self.columns = columns;
+        self.compile();
+    }
+
+    /// Whether or not the filter should be drawn
+    pub fn is_open(&self) -> bool {
+        self.state != FilterState::Inactive
+    }
+
+    /// Whether or not the filter input is being typed into
+    pub fn is_editing(&self) -> bool {
+        self.state == FilterState::Editing
+    }
+
+    /// Whether or not the filter is currently narrowing rows
+    pub fn is_active(&self) -> bool {
+        self.is_open() && !self.needle().is_empty()
+    }
+
+    /// Applies the given query as though it had been typed in, or clears the filter
+    /// if the query is empty
+    pub fn set(&mut self, query: &str) {
+        self.input = query.into();
+        self.compile();
+        self.state = if self.input.is_empty() {
+            FilterState::Inactive
+        } else {
+            FilterState::Applied
+        };
+    }
+
+    /// The part of the input to match against, excluding any column prefix
+    fn needle(&self) -> &str {
+        match self.scope {
+            Some(_) => self
+                .input
+                .split_once('=')
+                .map(|(_, needle)| needle)
+                .unwrap_or_default(),
+            None => &self.input,
+        }
+    }
+
+    /// Processes a key, returning whether or not the filter has consumed it.
+    /// The caller should re-apply the filter to its rows whenever a key is consumed
+    pub fn update(&mut self, input: Input) -> MessageResponse {
+        match self.state {
+            FilterState::Editing => match input.key() {
+                Some(Key::Char(c)) => {
+                    self.input.push(c);
+                    self.compile();
+                }
+                Some(Key::Backspace) => {
+                    self.input.pop();
+                    self.compile();
+                }
+                Some(TAB_KEY) => {
+                    self.mode = self.mode.next();
+                    self.compile();
+                }
+                Some(ENTER_KEY) => {
+                    self.state = if self.input.is_empty() {
+                        FilterState::Inactive
+                    } else {
+                        FilterState::Applied
+                    }
+                }
+                Some(ESC_KEY) => self.clear(),
+                _ => return MessageResponse::NotConsumed,
+            },
+            FilterState::Applied | FilterState::Inactive => {
+                match self.config.keybindings.action(Scope::Table, input) {
+                    Some(Action::Filter) => self.state = FilterState::Editing,
+                    Some(Action::Clear) if self.state == FilterState::Applied => self.clear(),
+                    _ => return MessageResponse::NotConsumed,
+                }
+            }
+        }
+        MessageResponse::Consumed
+    }
+
+    pub fn clear(&mut self) {
+        self.input = String::new();
+        self.scope = None;
+        self.state = FilterState::Inactive;
+        self.regex = None;
+        self.regex_error = false;
+    }
+
+    fn compile(&mut self) {
+        self.scope = self.input.split_once('=').and_then(|(column, _)| {
+            let column = column.to_lowercase();
+            self.columns.iter().position(|c| *c == column)
+        });
+        self.regex = None;
+        self.regex_error = false;
+        if self.mode == FilterMode::Regex && !self.needle().is_empty() {
+            match Regex::new(self.needle()) {
+                Ok(r) => self.regex = Some(r),
+                Err(_) => self.regex_error = true,
+            }
+        }
+    }
+
+    /// Returns the indices of the characters in `text` matched by the filter, or
+    /// `None` if the text doesn't match.  When the filter is inactive every text matches
+    pub fn match_indices(&self, text: &str) -> Option<Vec<usize>> {
+        if !self.is_active() {
+            return Some(vec![]);
+        }
+
+        let input = self.needle();
+        match self.mode {
+            FilterMode::Substring => {
+                let smart_case = input.chars().any(|c| c.is_uppercase());
+                let (haystack, needle): (Vec<char>, Vec<char>) = if smart_case {
+                    (text.chars().collect(), input.chars().collect())
+                } else {
+                    (
+                        text.chars().flat_map(|c| c.to_lowercase().next()).collect(),
+                        input.to_lowercase().chars().collect(),
+                    )
+                };
+                if needle.len() > haystack.len() {
+                    return None;
+                }
+                let start = haystack
+                    .windows(needle.len())
+                    .position(|window| window == needle.as_slice())?;
+                Some((start..start + needle.len()).collect())
+            }
+            FilterMode::Fuzzy => self
+                .matcher
+                .fuzzy_indices(text, input)
+                .map(|(_, indices)| indices),
+            FilterMode::Regex => {
+                // An invalid regex shouldn't hide everything while the user is mid-way
+                // through typing one
+                let Some(regex) = &self.regex else {
+                    return Some(vec![]);
+                };
+                let mut indices = vec![];
+                for m in regex.find_iter(text).filter(|m| !m.is_empty()) {
+                    let start = text[..m.start()].chars().count();
+                    let len = m.as_str().chars().count();
+                    indices.extend(start..start + len);
+                }
+                if indices.is_empty() {
+                    None
+                } else {
+                    Some(indices)
+                }
+            }
+        }
+    }
+
+    /// Returns whether or not any of the given cells, in column order, match the filter
+    pub fn matches_row<S: AsRef<str>>(&self, cells: &[S]) -> bool {
+        match self.scope {
+            Some(idx) if self.is_active() => cells
+                .get(idx)
+                .is_some_and(|c| self.match_indices(c.as_ref()).is_some()),
+            _ => cells
+                .iter()
+                .any(|c| self.match_indices(c.as_ref()).is_some()),
+        }
+    }
+
+    /// Builds a line from the text of the given column, highlighting any characters
+    /// matched by the filter
+    pub fn highlight(&self, column: usize, text: &str) -> Line<'static> {
+        if self.scope.is_some_and(|idx| idx != column) {
+            return Line::from(text.to_string());
+        }
+        let indices = match self.match_indices(text) {
+            Some(indices) if !indices.is_empty() => indices,
+            _ => return Line::from(text.to_string()),
+        };
+
+        let match_style = self.config.theme.search_match();
+
+        let mut spans = vec![];
+        let mut current = String::new();
+        let mut current_matched = false;
+        for (idx, c) in text.chars().enumerate() {
+            let matched = indices.contains(&idx);
+            if matched != current_matched && !current.is_empty() {
+                spans.push(span(
+                    std::mem::take(&mut current),
+                    current_matched,
+                    match_style,
+                ));
+            }
+            current_matched = matched;
+            current.push(c);
+        }
+        if !current.is_empty() {
+            spans.push(span(current, current_matched, match_style));
+        }
+
+        Line::from(spans)
+    }
+}
+
+fn span(text: String, matched: bool, match_style: Style) -> Span<'static> {
+    if matched {
+        Span::styled(text, match_style)
+    } else {
+        Span::raw(text)
+    }
+}
+
+impl Component for Filter {
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        let hint_style = self.config.theme.footer();
+
+        let mut spans = vec![Span::from("/"), Span::from(self.input.clone())];
+        if self.state == FilterState::Editing {
+            spans.push(Span::from("█").style(self.config.theme.cursor()));
+        }
+        spans.push(Span::from(format!("  [{}]", self.mode.name())).style(hint_style));
+        if self.regex_error {
+            spans.push(Span::from(" invalid regex").style(self.config.theme.error()));
+        }
+
+        f.render_widget(Line::from(spans), area);
+    }
+}
@@ -1,53 +1,71 @@
 use itertools::Itertools;
 use ratatui::{
     layout::Rect,
-    style::{Modifier, Style},
+    style::Style,
     text::{Line, Span},
     Frame,
 };
 
-use crate::{config::Config, traits::Component};
+use crate::{config::Config, events::Mouse, keymap::Action, traits::Component};
 
 #[derive(Debug)]
 pub struct Footer {
     config: Box<Config>,
+    /// Where each key was last drawn, along with its action
+    hits: Vec<(Rect, Action)>,
 }
 
 impl Footer {
     pub fn new(config: Box<Config>) -> Self {
-        Self { config }
+        Self {
+            config,
+            hits: vec![],
+        }
+    }
+
+    /// The action of the key under the mouse, if any
+    pub fn action_at(&self, mouse: Mouse) -> Option<Action> {
+        self.hits
+            .iter()
+            .find(|(area, _)| mouse.is_in(*area))
+            .map(|(_, action)| *action)
     }
 }
 
 impl Component for Footer {
     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        let keymap = &self.config.keybindings;
         let keys = [
-            ("K/↑", "Up"),
-            ("J/↓", "Down"),
-            ("Q/q", "Quit"),
-            (":", "Command"),
+            (Action::Up, "Up"),
+            (Action::Down, "Down"),
+            (Action::Quit, "Quit"),
+            (Action::Command, "Command"),
         ];
         let spans = keys
             .iter()
-            .flat_map(|(key, desc)| {
-                let key = Span::styled(
-                    format!(" <{key}> = "),
-                    Style::default()
-                        .fg(self.config.theme.footer())
-                        .add_modifier(Modifier::ITALIC),
-                );
-                let desc = Span::styled(
-                    format!("{desc} "),
-                    Style::default()
-                        .fg(self.config.theme.footer())
-                        .add_modifier(Modifier::ITALIC),
-                );
+            .flat_map(|(action, desc)| {
+                let key = keymap.display(*action);
+                let key = Span::styled(format!(" <{key}> = "), self.config.theme.footer());
+                let desc = Span::styled(format!("{desc} "), self.config.theme.footer());
                 [key, desc]
             })
             .collect_vec();
 
         let footer = Line::from(spans).centered().style(Style::new());
 
+        // The line is centred, so each key is found by working along from its start
+        let mut x = area.x + area.width.saturating_sub(footer.width() as u16) / 2;
+        self.hits = keys
+            .iter()
+            .zip(footer.spans.chunks(2))
+            .map(|((action, _), spans)| {
+                let width = spans.iter().map(|s| s.width() as u16).sum::<u16>();
+                let hit = Rect::new(x, area.y, width, 1).intersection(area);
+                x += width;
+                (hit, *action)
+            })
+            .collect();
+
         f.render_widget(footer, area)
     }
 }
@@ -1,20 +1,25 @@
 use ratatui::{
-    layout::{self, Margin, Rect},
-    style::Style,
+    layout::{self, Constraint, Layout, Margin, Rect},
+    text::Line,
     Frame,
 };
 use tui_big_text::{BigText, PixelSize};
 
-use crate::{config::Config, traits::Component};
+use crate::{
+    config::Config,
+    docker::store::{ResourceCounts, ResourceStore},
+    traits::Component,
+};
 
 #[derive(Debug)]
 pub struct Header {
     config: Box<Config>,
+    store: ResourceStore,
 }
 
 impl Header {
-    pub fn new(config: Box<Config>) -> Self {
-        Self { config }
+    pub fn new(config: Box<Config>, store: ResourceStore) -> Self {
+        Self { config, store }
     }
 }
 
@@ -22,7 +27,7 @@ impl Component for Header {
     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
         let big_text = match BigText::builder()
             .pixel_size(PixelSize::HalfHeight)
-            .style(Style::default().fg(self.config.theme.title()))
+            .style(self.config.theme.title())
             .lines(vec!["Ducker".into()])
             .alignment(layout::Alignment::Center)
             .build()
@@ -35,7 +40,47 @@ impl Component for Header {
             vertical: 0,
             horizontal: 2,
         });
+        let [title, counts] =
+            Layout::vertical([Constraint::Length(4), Constraint::Length(1)]).areas(area);
+
+        f.render_widget(big_text, title);
 
-        f.render_widget(big_text, area);
+        // Nothing is shown until the store has heard from the daemon, rather than
+        // claiming that there are no resources
+        if let Some(c) = self.store.counts() {
+            let line = format_counts(&c, false);
+            let line = if line.chars().count() > counts.width as usize {
+                format_counts(&c, true)
+            } else {
+                line
+            };
+            f.render_widget(
+                Line::from(line)
+                    .style(self.config.theme.footer())
+                    .centered(),
+                counts,
+            );
+        }
     }
 }
+
+/// Lists the number of each kind of resource, with abbreviated names if `short`
+fn format_counts(c: &ResourceCounts, short: bool) -> String {
+    let names = if short {
+        ["ctr", "img", "vol", "net"]
+    } else {
+        ["containers", "images", "volumes", "networks"]
+    };
+    format!(
+        "{}/{} {} · {} {} · {} {} · {} {}",
+        c.running,
+        c.containers,
+        names[0],
+        c.images,
+        names[1],
+        c.volumes,
+        names[2],
+        c.networks,
+        names[3],
+    )
+}
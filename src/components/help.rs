@@ -1,26 +1,30 @@
 use itertools::Itertools;
 use ratatui::{
-    layout::{Constraint, Layout},
-    style::{Modifier, Style},
+    layout::{Constraint, Layout, Rect},
+    style::Style,
     text::{Line, Span},
     widgets::Paragraph,
 };
 
-use crate::{config::Config, traits::Component};
+use crate::{config::Config, events::Mouse, keymap::Action, traits::Component};
 
 #[derive(Debug, Clone)]
 pub struct PageHelp {
     name: String,
     config: Box<Config>,
     displays: Vec<String>,
+    /// The action fired by clicking each entry of `displays`
+    actions: Vec<Option<Action>>,
     width: usize,
+    /// Where each entry was last drawn, along with its action
+    hits: Vec<(Rect, Action)>,
 }
 
 #[derive(Debug, Clone)]
 pub struct PageHelpBuilder {
     name: String,
     config: Box<Config>,
-    inputs: Vec<(String, String)>,
+    inputs: Vec<(String, String, Option<Action>)>,
 }
 
 impl PageHelpBuilder {
@@ -32,20 +36,30 @@ impl PageHelpBuilder {
         }
     }
 
-    pub fn add_input(mut self, trigger: String, description: String) -> Self {
-        self.inputs.push((trigger, description));
+    /// Adds the keys bound to the action, which is fired when the entry is clicked
+    pub fn add_action(self, action: Action, description: &str) -> Self {
+        self.add_actions(&[action], description)
+    }
+
+    /// Adds the keys bound to each of the actions, separated by `/`; clicking the
+    /// entry fires the first of them
+    pub fn add_actions(mut self, actions: &[Action], description: &str) -> Self {
+        let keymap = &self.config.keybindings;
+        let trigger = actions.iter().map(|a| keymap.display(*a)).join("/");
+        self.inputs
+            .push((trigger, description.into(), actions.first().copied()));
         self
     }
 
     pub fn build(mut self) -> PageHelp {
-        self.inputs.sort_by_key(|(first, _)| first.to_owned());
+        self.inputs.sort_by_key(|(first, _, _)| first.to_owned());
 
         let mut width = 0;
 
         let displays = self
             .inputs
             .iter()
-            .map(|(key, desc)| {
+            .map(|(key, desc, _)| {
                 let disp = format!(" <{key}> = {desc} ");
                 if disp.len() > width {
                     width = disp.len();
@@ -59,7 +73,9 @@ impl PageHelpBuilder {
             name: self.name.clone(),
             config: self.config.clone(),
             displays,
+            actions: self.inputs.iter().map(|(_, _, action)| *action).collect(),
             width,
+            hits: vec![],
         }
     }
 }
@@ -68,6 +84,14 @@ impl PageHelp {
     pub fn get_name(&self) -> String {
         self.name.clone()
     }
+
+    /// The action of the entry under the mouse, if any
+    pub fn action_at(&self, mouse: Mouse) -> Option<Action> {
+        self.hits
+            .iter()
+            .find(|(area, _)| mouse.is_in(*area))
+            .map(|(_, action)| *action)
+    }
 }
 
 impl Component for PageHelp {
@@ -77,8 +101,7 @@ impl Component for PageHelp {
         let group_height = area.height - 1;
 
         // Integer division - round up
-        let n_blocks =
-            (self.displays.len() + (group_height as usize) - 1) / (group_height as usize);
+        let n_blocks = self.displays.len().div_ceil(group_height as usize);
 
         let displays = self.displays.clone();
         let width = self.width;
@@ -92,6 +115,18 @@ impl Component for PageHelp {
         }
         let columns = Layout::horizontal(constraints).split(f.size());
 
+        self.hits = self
+            .actions
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, action)| {
+                let column = columns[idx / group_height as usize + 1];
+                let row = (idx % group_height as usize) as u16;
+                let area = Rect::new(column.x, column.y + row, column.width, 1);
+                action.map(|a| (area.intersection(column), a))
+            })
+            .collect();
+
         // This slight monstrosity iterates over each chunk, builds the column then writes it to the
         // relevant buffer
         for (idx, display) in chunked_displays.iter().enumerate() {
@@ -99,15 +134,9 @@ impl Component for PageHelp {
                 display
                     .iter()
                     .map(|v| {
-                        Line::from(
-                            Span::from(format!("{v}\n")).style(
-                                Style::default()
-                                    .add_modifier(Modifier::ITALIC)
-                                    .fg(self.config.theme.help()),
-                            ),
-                        )
-                        .left_aligned()
-                        .style(Style::new())
+                        Line::from(Span::from(format!("{v}\n")).style(self.config.theme.help()))
+                            .left_aligned()
+                            .style(Style::new())
                     })
                     .collect_vec(),
             );
@@ -2,93 +2,131 @@ use color_eyre::eyre::{Context, Result};
 use itertools::min;
 use ratatui::{
     layout::{Margin, Rect},
-    prelude::*,
     style::Style,
     text::{Line, Span},
-    widgets::{Block, Padding, Paragraph},
+    widgets::{Block, Clear, List, ListState, Padding, Paragraph},
     Frame,
 };
-use std::{collections::VecDeque, fmt::Debug};
+use std::{collections::VecDeque, fmt::Debug, fs, io::ErrorKind, path::PathBuf};
 
 use tokio::sync::mpsc::Sender;
 
 use crate::{
-    autocomplete::Autocomplete,
+    autocomplete::{Autocomplete, Completion, Resources},
+    commands::Command,
+    config::Config,
     context::AppContext,
+    docker::store::ResourceStore,
     events::transition::send_transition,
     events::{message::MessageResponse, Key, Message, Transition},
+    theme::Theme,
     traits::Component,
 };
 
-const QUIT: &str = "quit";
-const Q: &str = "q";
-const IMAGE: &str = "image";
-const IMAGES: &str = "images";
-const CONTAINER: &str = "container";
-const CONTAINERS: &str = "containers";
+/// The maximum number of candidates shown in the dropdown at once
+const MAX_CANDIDATES: u16 = 8;
 
 #[derive(Debug)]
 pub struct InputField {
     input: String,
     prompt: String,
+    theme: Theme,
     tx: Sender<Message<Key, Transition>>,
-    candidate: Option<String>,
-    ac: Autocomplete<'static>,
+    store: ResourceStore,
+    ac: Autocomplete,
+    resources: Resources,
+    completion: Completion,
+    selected: Option<usize>,
     history: History,
+    search: Option<Search>,
+}
+
+/// State of a reverse incremental search through the history
+#[derive(Debug, Default)]
+struct Search {
+    query: String,
+    /// Index in the history of the current match
+    idx: Option<usize>,
+    failed: bool,
 }
 
 impl InputField {
-    pub fn new(tx: Sender<Message<Key, Transition>>, prompt: String) -> Self {
-        Self {
+    pub fn new(
+        tx: Sender<Message<Key, Transition>>,
+        store: ResourceStore,
+        config: &Config,
+    ) -> Result<Self> {
+        let history = History::load(config.history.path()?, config.history.size)
+            .context("unable to load command history")?;
+
+        Ok(Self {
             input: String::new(),
-            prompt,
+            prompt: config.prompt.clone(),
+            theme: config.theme,
             tx,
-            candidate: None,
-            ac: Autocomplete::from(vec![QUIT, Q, IMAGE, IMAGES, CONTAINER, CONTAINERS]),
-            history: History::new(),
-        }
+            store,
+            ac: Autocomplete::default(),
+            resources: Resources::default(),
+            completion: Completion::default(),
+            selected: None,
+            history,
+            search: None,
+        })
+    }
+
+    /// Applies a reloaded config; the history is kept as it is
+    pub fn set_config(&mut self, config: &Config) {
+        self.prompt = config.prompt.clone();
+        self.theme = config.theme;
     }
 
-    pub fn initialise(&mut self) {
+    /// Clears the input and refreshes the resources used for completion; `columns`
+    /// are the columns of the table on the current page
+    pub fn initialise(&mut self, columns: Vec<String>) {
         self.input = String::new();
-        self.candidate = None;
+        self.completion = Completion::default();
+        self.selected = None;
+        self.search = None;
         self.history.reset_idx();
+        self.resources = Resources {
+            columns,
+            ..Resources::from_store(&self.store)
+        };
     }
 
     pub async fn update(&mut self, message: Key) -> Result<MessageResponse> {
         match message {
+            Key::Null => return Ok(MessageResponse::NotConsumed),
+            _ if self.search.is_some() => return self.update_search(message).await,
+            Key::Ctrl('r') => {
+                self.search = Some(Search::default());
+                self.clear_completion();
+            }
             Key::Char(c) => {
                 self.history.reset_idx();
                 self.input.push(c);
-                let input = &self.input;
-                self.candidate = self.ac.get_completion(input);
-            }
-            Key::Tab => {
-                if let Some(candidate) = &self.candidate {
-                    self.input.clone_from(candidate)
-                }
+                self.refresh_completion();
             }
+            Key::Tab => self.cycle_completion(true),
+            Key::BackTab => self.cycle_completion(false),
             Key::Up => {
                 self.history.conditional_set_working_buffer(&self.input);
                 if let Some(v) = &self.history.next() {
                     self.input.clone_from(v);
+                    self.clear_completion();
                 }
             }
             Key::Down => {
                 if let Some(v) = &self.history.previous() {
                     self.input.clone_from(v);
+                    self.clear_completion();
                 }
             }
             Key::Backspace => {
                 self.input.pop();
+                self.refresh_completion();
             }
-            Key::Enter => {
-                self.history.add_value(&self.input);
-                self.history.reset_idx();
-                self.submit()
-                    .await
-                    .context("unable to submit user command")?;
-            }
+            Key::Enter => self.submit().await?,
 
             _ => return Ok(MessageResponse::NotConsumed),
         }
@@ -96,14 +134,113 @@ impl InputField {
         Ok(MessageResponse::Consumed)
     }
 
-    async fn submit(&mut self) -> Result<()> {
-        let transition = match &*self.input {
-            Q | QUIT => Some(Transition::Quit),
-            IMAGE | IMAGES => Some(Transition::ToImagePage(AppContext::default())),
-            CONTAINER | CONTAINERS => Some(Transition::ToContainerPage(AppContext::default())),
-            _ => None,
+    /// Handles input while searching the history, in the manner of a shell's
+    /// reverse-i-search
+    async fn update_search(&mut self, message: Key) -> Result<MessageResponse> {
+        let Some(search) = self.search.as_mut() else {
+            return Ok(MessageResponse::NotConsumed);
         };
 
+        let from = match message {
+            Key::Ctrl('r') => search.idx.map(|idx| idx + 1).unwrap_or_default(),
+            Key::Char(c) => {
+                search.query.push(c);
+                search.idx.unwrap_or_default()
+            }
+            Key::Backspace => {
+                search.query.pop();
+                0
+            }
+            Key::Esc | Key::Ctrl('g') => {
+                self.search = None;
+                return Ok(MessageResponse::Consumed);
+            }
+            Key::Enter => {
+                self.accept_search();
+                self.submit().await?;
+                return Ok(MessageResponse::Consumed);
+            }
+            _ => {
+                self.accept_search();
+                return Ok(MessageResponse::Consumed);
+            }
+        };
+
+        if search.query.is_empty() {
+            search.idx = None;
+            search.failed = false;
+        } else {
+            match self.history.search(&search.query, from) {
+                Some(idx) => {
+                    search.idx = Some(idx);
+                    search.failed = false;
+                }
+                None => search.failed = true,
+            }
+        }
+        Ok(MessageResponse::Consumed)
+    }
+
+    /// Ends the search, replacing the input with the current match
+    fn accept_search(&mut self) {
+        let Some(search) = self.search.take() else {
+            return;
+        };
+        if let Some(value) = search.idx.and_then(|idx| self.history.get(idx)) {
+            self.input.clone_from(value);
+        }
+        self.history.reset_idx();
+    }
+
+    fn refresh_completion(&mut self) {
+        self.selected = None;
+        self.completion = self.ac.complete(&self.input, &self.resources);
+    }
+
+    fn clear_completion(&mut self) {
+        self.selected = None;
+        self.completion = Completion::default();
+    }
+
+    /// Replaces the word being completed with the next (or previous) candidate,
+    /// leaving the candidates untouched so that they can continue to be cycled
+    fn cycle_completion(&mut self, forward: bool) {
+        let n_candidates = self.completion.candidates.len();
+        if n_candidates == 0 {
+            return;
+        }
+
+        let idx = match (self.selected, forward) {
+            (None, true) => 0,
+            (None, false) => n_candidates - 1,
+            (Some(idx), true) => (idx + 1) % n_candidates,
+            (Some(idx), false) => (idx + n_candidates - 1) % n_candidates,
+        };
+        self.selected = Some(idx);
+
+        self.input.truncate(self.completion.start);
+        self.input.push_str(&self.completion.candidates[idx]);
+    }
+
+    async fn submit(&mut self) -> Result<()> {
+        let saved = self.history.add_value(&self.input);
+        self.history.reset_idx();
+
+        // Errors are raised before the input is cleared, so that the user is able to
+        // correct the command once they have dismissed the error
+        let transition = Command::parse(&self.input)?.map(|command| match command {
+            Command::Quit => Transition::Quit,
+            Command::Images => Transition::ToImagePage(AppContext::default()),
+            Command::Containers => Transition::ToContainerPage(AppContext::default()),
+            Command::Activity => Transition::ToActivityPage(AppContext::default()),
+            Command::Debug => Transition::ToggleDebug,
+            Command::Prune(options) => Transition::ToPrunePage(AppContext {
+                prune_options: Some(options),
+                ..Default::default()
+            }),
+            command => Transition::RunCommand(command),
+        });
+
         if let Some(t) = transition {
             send_transition(self.tx.clone(), Transition::ToViewMode)
                 .await
@@ -112,13 +249,54 @@ impl InputField {
                 .await
                 .context("unable to send transition")?;
         }
-        // At some point I want it to pop up a modal
-        // else {
-        //     panic!("")
-        // }
+        self.input = String::new();
+        self.clear_completion();
+        saved.context("unable to save command history")
+    }
+
+    /// Draws the candidates in a dropdown below the word being completed
+    fn draw_candidates(&self, f: &mut Frame<'_>, area: Rect, text_x: u16) {
+        let candidates = &self.completion.candidates;
+        if candidates.is_empty() {
+            return;
+        }
 
-        self.initialise();
-        Ok(())
+        let frame = f.size();
+        let width = candidates
+            .iter()
+            .map(|c| c.chars().count() as u16)
+            .max()
+            .unwrap_or_default()
+            .saturating_add(4)
+            .min(frame.width);
+        let before = self
+            .input
+            .get(..self.completion.start)
+            .unwrap_or(&self.input);
+        let x = (text_x + before.chars().count() as u16)
+            .saturating_sub(2)
+            .min(frame.right().saturating_sub(width));
+        let y = area.bottom();
+        let height = (candidates.len() as u16)
+            .min(MAX_CANDIDATES)
+            .saturating_add(2)
+            .min(frame.bottom().saturating_sub(y));
+        if height < 3 {
+            return;
+        }
+
+        let dropdown = Rect::new(x, y, width, height);
+        let list = List::new(candidates.iter().map(String::as_str))
+            .block(
+                Block::bordered()
+                    .border_style(self.theme.border())
+                    .padding(Padding::horizontal(1)),
+            )
+            .highlight_style(self.theme.selected());
+        let mut state = ListState::default().with_selected(self.selected);
+
+        f.render_widget(Clear, dropdown);
+        f.render_stateful_widget(list, dropdown, &mut state);
     }
 }
 
@@ -126,6 +304,7 @@ impl Component for InputField {
     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
         let block = Block::bordered()
             .border_type(ratatui::widgets::BorderType::Plain)
+            .border_style(self.theme.border())
             .padding(Padding::left(300));
 
         f.render_widget(block, area);
@@ -133,60 +312,133 @@ impl Component for InputField {
         let inner_body_margin = Margin::new(2, 1);
         let body_inner = area.inner(inner_body_margin);
 
+        if let Some(search) = &self.search {
+            let label = if search.failed {
+                "failed reverse-i-search"
+            } else {
+                "reverse-i-search"
+            };
+            let value = search
+                .idx
+                .and_then(|idx| self.history.get(idx))
+                .cloned()
+                .unwrap_or_default();
+            let p = Paragraph::new(Line::from(vec![
+                Span::styled(
+                    format!("({label})`{}': ", search.query),
+                    self.theme.prompt(),
+                ),
+                Span::raw(value),
+            ]));
+            f.render_widget(p, body_inner);
+            return;
+        }
+
+        let prompt = format!("{} ", self.prompt);
+        let text_x = body_inner.x + prompt.chars().count() as u16;
+
         let mut input_text = vec![
-            Span::styled::<String, Style>(format!("{} ", self.prompt), Style::new().green()),
+            Span::styled::<String, Style>(prompt, self.theme.prompt()),
             Span::raw(self.input.clone()),
         ];
 
-        if let Some(candidate) = &self.candidate {
-            if let Some(delta) = candidate.strip_prefix(&self.input as &str) {
-                input_text
-                    .push(Span::raw(delta).style(Style::default().add_modifier(Modifier::DIM)))
+        // Hint at the best candidate when it extends what has already been typed
+        if self.selected.is_none() {
+            let current = &self.input[self.completion.start.min(self.input.len())..];
+            if let Some(delta) = self
+                .completion
+                .candidates
+                .first()
+                .and_then(|c| c.strip_prefix(current))
+            {
+                input_text.push(Span::raw(delta.to_string()).style(self.theme.hint()))
             }
         }
 
         let p = Paragraph::new(Line::from(input_text));
-        f.render_widget(p, body_inner)
+        f.render_widget(p, body_inner);
+
+        self.draw_candidates(f, area, text_x);
     }
 }
 
-const MAX_HISTORY_SIZE: usize = 100;
-
 #[derive(Debug)]
 struct History {
     values: VecDeque<String>,
     working_buffer: Option<String>,
     idx: Option<usize>,
+    max_size: usize,
+    path: PathBuf,
 }
 
-impl Default for History {
-    fn default() -> Self {
-        Self {
-            values: VecDeque::with_capacity(MAX_HISTORY_SIZE),
+impl History {
+    /// Loads the history from the given file, which holds one command per line,
+    /// oldest first.  A missing file is treated as an empty history
+    pub fn load(path: PathBuf, max_size: usize) -> Result<Self> {
+        let mut values = VecDeque::with_capacity(max_size);
+        match fs::read_to_string(&path) {
+            Ok(contents) => {
+                for line in contents.lines() {
+                    Self::push(&mut values, line, max_size);
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e).with_context(|| format!("unable to read {}", path.display())),
+        }
+
+        Ok(Self {
+            values,
             working_buffer: None,
             idx: None,
+            max_size,
+            path,
+        })
+    }
+
+    /// Adds a value to the front of the history and saves it, ignoring blank
+    /// values and repeats of the most recent value
+    pub fn add_value(&mut self, v: &str) -> Result<()> {
+        if !Self::push(&mut self.values, v, self.max_size) {
+            return Ok(());
         }
+        self.save()
     }
-}
 
-impl History {
-    pub fn new() -> Self {
-        let v = VecDeque::with_capacity(MAX_HISTORY_SIZE);
-        Self {
-            values: v,
-            working_buffer: None,
-            idx: None,
+    fn push(values: &mut VecDeque<String>, v: &str, max_size: usize) -> bool {
+        if v.trim().is_empty() || values.front().is_some_and(|front| front == v) {
+            return false;
         }
+        values.push_front(v.into());
+        values.truncate(max_size);
+        true
     }
 
-    pub fn add_value(&mut self, v: &str) {
-        if v.trim().is_empty() {
-            return;
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
         }
-        if self.values.len() == MAX_HISTORY_SIZE {
-            self.values.pop_back();
+        let mut contents = String::new();
+        for value in self.values.iter().rev() {
+            contents.push_str(value);
+            contents.push('\n');
         }
-        self.values.push_front(v.into());
+        fs::write(&self.path, contents)
+            .with_context(|| format!("unable to write {}", self.path.display()))
+    }
+
+    /// Finds the most recent value, starting from the given index, which contains
+    /// the query
+    pub fn search(&self, query: &str, from: usize) -> Option<usize> {
+        self.values
+            .iter()
+            .enumerate()
+            .skip(from)
+            .find(|(_, v)| v.contains(query))
+            .map(|(idx, _)| idx)
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&String> {
+        self.values.get(idx)
     }
 
     pub fn reset_idx(&mut self) {
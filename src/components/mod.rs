@@ -1,7 +1,13 @@
 pub mod alert_modal;
 pub mod boolean_modal;
+pub mod debug_overlay;
+pub mod filter;
 pub mod footer;
 pub mod header;
 pub mod help;
 pub mod input_field;
+pub mod preview;
 pub mod resize_notice;
+pub mod toasts;
+pub mod tree_view;
+pub mod which_key;
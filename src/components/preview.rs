@@ -0,0 +1,240 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use ansi_to_tui::IntoText;
+use bollard::Docker;
+use futures::StreamExt;
+use ratatui::{
+    layout::Rect,
+    text::{Line, Text},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+use tokio::{task::JoinHandle, time::sleep};
+
+use crate::{
+    config::Config,
+    context::AppContext,
+    docker::{logs::DockerLogs, stats::ContainerStats},
+    traits::Component,
+};
+
+/// How long the selection has to stay on a resource before it is previewed, so that
+/// scrolling through a table doesn't start a request for every row passed over
+const SETTLE_DELAY: Duration = Duration::from_millis(150);
+
+/// Identifies what is being previewed; a container is previewed again when it is
+/// started or stopped, as its logs and stats streams end when it stops
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Target {
+    id: String,
+    kind: PreviewKind,
+    running: bool,
+}
+
+/// What is shown about the selected resource
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PreviewKind {
+    #[default]
+    Logs,
+    Stats,
+    Describe,
+}
+
+impl PreviewKind {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Logs => Self::Stats,
+            Self::Stats => Self::Describe,
+            Self::Describe => Self::Logs,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Logs => "logs",
+            Self::Stats => "stats",
+            Self::Describe => "describe",
+        }
+    }
+}
+
+/// A live view of the resource selected in a table; the most recent logs or the
+/// resource usage of a container, or the description of any resource.  Whenever
+/// the selection changes the previous preview is stopped and a new one started
+#[derive(Debug)]
+pub struct Preview {
+    docker: Docker,
+    config: Box<Config>,
+    kind: PreviewKind,
+    /// The resource being previewed and the kind of preview shown for it
+    target: Option<Target>,
+    /// The name of the resource being previewed, shown in the title
+    name: String,
+    lines: Arc<Mutex<VecDeque<String>>>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl Preview {
+    pub fn new(docker: Docker, config: Box<Config>, kind: PreviewKind) -> Self {
+        Self {
+            docker,
+            config,
+            kind,
+            target: None,
+            name: String::new(),
+            lines: Arc::new(Mutex::new(VecDeque::new())),
+            task: None,
+        }
+    }
+
+    pub fn set_config(&mut self, config: Box<Config>) {
+        self.config = config;
+    }
+
+    pub fn kind(&self) -> PreviewKind {
+        self.kind
+    }
+
+    /// Switches to the next kind of preview
+    pub fn cycle(&mut self) {
+        self.kind = self.kind.next();
+    }
+
+    /// Previews the given selection, if it isn't already being previewed.  Only
+    /// containers have logs and stats, so anything else is always described
+    pub fn follow(&mut self, selection: Option<AppContext>) {
+        // Anything which can't be described, eg an empty table, clears the preview
+        let Some((cx, describable)) =
+            selection.and_then(|cx| cx.describable.clone().map(|d| (cx, d)))
+        else {
+            self.stop();
+            self.name = String::new();
+            self.lines.lock().unwrap().clear();
+            return;
+        };
+        let kind = match cx.docker_container {
+            Some(_) => self.kind,
+            None => PreviewKind::Describe,
+        };
+        let target = Target {
+            id: describable.get_id(),
+            kind,
+            running: cx.docker_container.as_ref().is_some_and(|c| c.running),
+        };
+        if self.target.as_ref() == Some(&target) {
+            return;
+        }
+
+        self.stop();
+        self.target = Some(target);
+        self.name = describable.get_name();
+        self.lines = Arc::new(Mutex::new(VecDeque::new()));
+
+        let docker = self.docker.clone();
+        let lines = self.lines.clone();
+        let log_lines = self.config.preview.log_lines;
+        self.task = Some(tokio::spawn(async move {
+            sleep(SETTLE_DELAY).await;
+            match (kind, cx.docker_container) {
+                (PreviewKind::Logs, Some(container)) => {
+                    let mut logs =
+                        DockerLogs::from(container).get_log_stream(&docker, log_lines, None);
+                    while let Some(line) = logs.next().await {
+                        let mut lines = lines.lock().unwrap();
+                        lines.extend(line.lines().map(String::from));
+                        while lines.len() > log_lines as usize {
+                            lines.pop_front();
+                        }
+                    }
+                }
+                (PreviewKind::Stats, Some(container)) if !container.running => {
+                    set_lines(&lines, vec!["The container is not running".into()]);
+                }
+                (PreviewKind::Stats, Some(container)) => {
+                    let mut stats = ContainerStats::stream(&docker, &container);
+                    while let Some(result) = stats.next().await {
+                        match result {
+                            Ok(stats) => set_lines(&lines, stats.lines()),
+                            Err(e) => set_lines(&lines, vec![format!("{e:#}")]),
+                        }
+                    }
+                }
+                _ => {
+                    let description = describable.describe(&docker).await.and_then(|d| {
+                        serde_yml::to_string(&d).map_err(color_eyre::eyre::Error::from)
+                    });
+                    match description {
+                        Ok(yaml) => set_lines(&lines, yaml.lines().map(String::from).collect()),
+                        Err(e) => set_lines(&lines, vec![format!("{e:#}")]),
+                    }
+                }
+            }
+        }));
+    }
+
+    /// Whether the preview is still being updated
+    pub fn is_running(&self) -> bool {
+        self.task.as_ref().is_some_and(|t| !t.is_finished())
+    }
+
+    /// Stops updating the preview; it is started again by the next call to `follow`
+    pub fn stop(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        self.target = None;
+    }
+}
+
+impl Drop for Preview {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn set_lines(lines: &Mutex<VecDeque<String>>, new: Vec<String>) {
+    *lines.lock().unwrap() = new.into();
+}
+
+impl Component for Preview {
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        let kind = self.target.as_ref().map_or(self.kind, |t| t.kind);
+        let title = match self.name.as_str() {
+            "" => format!(" {} ", kind.name()),
+            name => format!(" {}: {name} ", kind.name()),
+        };
+        let block = Block::bordered()
+            .title(title)
+            .border_style(self.config.theme.border());
+        let height = block.inner(area).height as usize;
+
+        let lines = self.lines.lock().unwrap();
+        let text = match kind {
+            // The most recent logs are at the bottom, so that is what is kept in view
+            PreviewKind::Logs => {
+                let lines = lines
+                    .iter()
+                    .skip(lines.len().saturating_sub(height))
+                    .flat_map(|l| {
+                        l.into_text()
+                            .map(|t| t.lines)
+                            .unwrap_or_else(|_| vec![Line::from(l.clone())])
+                    })
+                    .collect::<Vec<_>>();
+                Text::from(lines)
+            }
+            _ => Text::from(
+                lines
+                    .iter()
+                    .map(|l| Line::from(l.clone()))
+                    .collect::<Vec<_>>(),
+            ),
+        };
+
+        f.render_widget(Paragraph::new(text).block(block), area);
+    }
+}
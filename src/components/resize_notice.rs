@@ -1,6 +1,5 @@
 use ratatui::{
     layout::{self, Constraint, Layout},
-    style::Style,
     text::{Line, Span, Text},
     widgets::{block::Title, Block},
 };
@@ -49,9 +48,9 @@ impl Component for ResizeScreen {
         let mut height_span = Span::from(format!("{}", size.height));
 
         let height_style = if height >= self.min_height {
-            Style::default().fg(self.config.theme.success())
+            self.config.theme.success()
         } else {
-            Style::default().fg(self.config.theme.error())
+            self.config.theme.error()
         };
         height_span = height_span.style(height_style);
 
@@ -59,9 +58,9 @@ impl Component for ResizeScreen {
         let mut width_span = Span::from(format!("{}", size.width));
 
         let width_style = if width >= self.min_width {
-            Style::default().fg(self.config.theme.success())
+            self.config.theme.success()
         } else {
-            Style::default().fg(self.config.theme.error())
+            self.config.theme.error()
         };
         width_span = width_span.style(width_style);
 
@@ -87,7 +86,7 @@ impl Component for ResizeScreen {
 
         let block = Block::bordered()
             .title(Title::from("< Terminal Too Small >").alignment(layout::Alignment::Center))
-            .border_style(Style::default().fg(self.config.theme.negative_highlight()));
+            .border_style(self.config.theme.negative_highlight());
 
         let [_, inner_area, _] = Layout::vertical(vec![
             Constraint::Min(0),
@@ -0,0 +1,138 @@
+use std::{collections::VecDeque, time::Instant};
+
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::Line,
+    widgets::{Block, Clear, Padding, Paragraph},
+    Frame,
+};
+
+use crate::{
+    config::Config,
+    events::{Notification, Severity},
+    traits::Component,
+};
+
+/// The most toasts shown at once; older toasts are dropped to make room
+const MAX_TOASTS: usize = 5;
+const MAX_WIDTH: u16 = 60;
+/// Longer messages are cut short, as a toast is no place to read a whole report
+const MAX_LINES: usize = 4;
+
+/// Notifications stacked in the top right corner of the page, newest first.  They
+/// don't take any input, and each is dismissed once its severity's timeout passes
+#[derive(Debug)]
+pub struct Toasts {
+    config: Box<Config>,
+    toasts: VecDeque<(Notification, Instant)>,
+}
+
+impl Toasts {
+    pub fn new(config: Box<Config>) -> Self {
+        Self {
+            config,
+            toasts: VecDeque::new(),
+        }
+    }
+
+    pub fn set_config(&mut self, config: Box<Config>) {
+        self.config = config;
+    }
+
+    pub fn push(&mut self, notification: Notification) {
+        self.toasts.push_front((notification, Instant::now()));
+        self.toasts.truncate(MAX_TOASTS);
+    }
+
+    fn style(&self, severity: Severity) -> Style {
+        match severity {
+            Severity::Info => self.config.theme.info(),
+            Severity::Success => self.config.theme.success(),
+            Severity::Warning => self.config.theme.warning(),
+            Severity::Error => self.config.theme.error(),
+        }
+    }
+}
+
+impl Component for Toasts {
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        self.toasts
+            .retain(|(notification, shown)| shown.elapsed() < notification.severity.timeout());
+
+        let max_width = MAX_WIDTH.min(area.width);
+        let mut top = area.top();
+        for (notification, _) in &self.toasts {
+            let lines = wrap(
+                &notification.message,
+                max_width.saturating_sub(4).max(1) as usize,
+            );
+            let title = format!(" {} ", notification.severity.title());
+
+            let width = lines
+                .iter()
+                .map(|l| l.chars().count())
+                .chain([title.chars().count()])
+                .max()
+                .unwrap_or_default() as u16;
+            let width = width.saturating_add(4).min(max_width);
+            let height = lines.len() as u16 + 2;
+            if top + height > area.bottom() {
+                break;
+            }
+
+            let toast = Rect::new(area.right().saturating_sub(width), top, width, height);
+            let style = self.style(notification.severity);
+            let block = Block::bordered()
+                .title(title)
+                .border_style(style)
+                .title_style(style)
+                .padding(Padding::horizontal(1));
+            let text = lines.into_iter().map(Line::from).collect::<Vec<_>>();
+
+            f.render_widget(Clear, toast);
+            f.render_widget(Paragraph::new(text).block(block), toast);
+            top += height;
+        }
+    }
+}
+
+/// Splits `text` into lines of at most `width` characters, breaking between words
+/// where possible
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let mut word = word.to_string();
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            // A word too long for a line of its own is split wherever it runs out
+            while word.chars().count() > width {
+                let rest = word.split_off(word.char_indices().nth(width).unwrap().0);
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                lines.push(word);
+                word = rest;
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&word);
+        }
+        lines.push(line);
+    }
+
+    if lines.len() > MAX_LINES {
+        lines.truncate(MAX_LINES);
+        if let Some(last) = lines.last_mut() {
+            if last.chars().count() >= width {
+                last.pop();
+            }
+            last.push('…');
+        }
+    }
+    lines
+}
@@ -0,0 +1,618 @@
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{List, ListState},
+    Frame,
+};
+
+use crate::{config::Config, context::PageView, traits::Component};
+
+/// A single node in a flattened tree.  Nodes are stored in pre-order, so the
+/// descendants of a node are always the contiguous range `idx + 1..subtree_end`
+#[derive(Debug, Clone)]
+struct TreeNode {
+    key: String,
+    value: Option<String>,
+    n_children: usize,
+    is_array: bool,
+    depth: usize,
+    parent: Option<usize>,
+    subtree_end: usize,
+    expanded: bool,
+    path: String,
+}
+
+/// A foldable tree view over a structured value, eg the output of a docker inspect
+#[derive(Debug)]
+pub struct TreeView {
+    config: Box<Config>,
+    nodes: Vec<TreeNode>,
+    list_state: ListState,
+    search: Option<String>,
+}
+
+impl TreeView {
+    pub fn new(config: Box<Config>) -> Self {
+        Self {
+            config,
+            nodes: vec![],
+            list_state: ListState::default(),
+            search: None,
+        }
+    }
+
+    pub fn set_config(&mut self, config: Box<Config>) {
+        self.config = config;
+    }
+
+    pub fn set_value(&mut self, value: &serde_json::Value) {
+        self.nodes = vec![];
+        match value {
+            serde_json::Value::Object(fields) => {
+                for (k, v) in fields {
+                    self.push_node(k.clone(), &join_key("", k), v, 0, None);
+                }
+            }
+            serde_json::Value::Array(values) => {
+                for (idx, v) in values.iter().enumerate() {
+                    self.push_node(format!("[{idx}]"), &format!(".[{idx}]"), v, 0, None);
+                }
+            }
+            other => self.push_node(String::new(), ".", other, 0, None),
+        }
+        self.list_state = ListState::default();
+        self.list_state.select(Some(0));
+    }
+
+    fn push_node(
+        &mut self,
+        key: String,
+        path: &str,
+        value: &serde_json::Value,
+        depth: usize,
+        parent: Option<usize>,
+    ) {
+        let idx = self.nodes.len();
+        let (scalar, n_children, is_array) = match value {
+            serde_json::Value::Object(o) => (None, o.len(), false),
+            serde_json::Value::Array(a) => (None, a.len(), true),
+            serde_json::Value::String(s) => (Some(s.clone()), 0, false),
+            serde_json::Value::Null => (Some("~".into()), 0, false),
+            other => (Some(other.to_string()), 0, false),
+        };
+        self.nodes.push(TreeNode {
+            key,
+            value: scalar,
+            n_children,
+            is_array,
+            depth,
+            parent,
+            subtree_end: idx + 1,
+            expanded: true,
+            path: path.into(),
+        });
+
+        match value {
+            serde_json::Value::Object(fields) => {
+                for (k, v) in fields {
+                    self.push_node(k.clone(), &join_key(path, k), v, depth + 1, Some(idx));
+                }
+            }
+            serde_json::Value::Array(values) => {
+                for (child_idx, v) in values.iter().enumerate() {
+                    let child_path = format!("{path}[{child_idx}]");
+                    self.push_node(
+                        format!("[{child_idx}]"),
+                        &child_path,
+                        v,
+                        depth + 1,
+                        Some(idx),
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        self.nodes[idx].subtree_end = self.nodes.len();
+    }
+
+    /// Indices of all nodes which are not hidden by a collapsed ancestor
+    fn visible(&self) -> Vec<usize> {
+        let mut visible = vec![];
+        let mut idx = 0;
+        while idx < self.nodes.len() {
+            visible.push(idx);
+            let node = &self.nodes[idx];
+            idx = if node.expanded {
+                idx + 1
+            } else {
+                node.subtree_end
+            };
+        }
+        visible
+    }
+
+    fn selected_node(&self) -> Option<usize> {
+        let visible = self.visible();
+        self.list_state
+            .selected()
+            .and_then(|idx| visible.get(idx).copied())
+    }
+
+    fn select_node(&mut self, node_idx: usize) {
+        if let Some(pos) = self.visible().iter().position(|idx| *idx == node_idx) {
+            self.list_state.select(Some(pos));
+        }
+    }
+
+    /// The selection and scroll position amongst the visible nodes
+    pub fn view(&self) -> PageView {
+        PageView::from_list(&self.list_state)
+    }
+
+    pub fn restore_view(&mut self, view: PageView) {
+        let n_visible = self.visible().len();
+        view.restore_list(&mut self.list_state, n_visible);
+    }
+
+    pub fn down(&mut self) {
+        let n_visible = self.visible().len();
+        match self.list_state.selected() {
+            None => self.list_state.select(Some(0)),
+            Some(idx) => {
+                if idx + 1 < n_visible {
+                    self.list_state.select(Some(idx + 1))
+                }
+            }
+        }
+    }
+
+    pub fn up(&mut self) {
+        self.list_state.select_previous();
+    }
+
+    pub fn top(&mut self) {
+        self.list_state.select_first();
+    }
+
+    pub fn bottom(&mut self) {
+        let n_visible = self.visible().len();
+        self.list_state.select(Some(n_visible.saturating_sub(1)));
+    }
+
+    pub fn toggle(&mut self) {
+        if let Some(idx) = self.selected_node() {
+            let expanded = self.nodes[idx].expanded;
+            self.set_expanded(idx, !expanded);
+        }
+    }
+
+    /// Expands the focused node, or moves to its first child if already expanded
+    pub fn expand(&mut self) {
+        let Some(idx) = self.selected_node() else {
+            return;
+        };
+        if self.nodes[idx].expanded {
+            self.down()
+        } else {
+            self.set_expanded(idx, true)
+        }
+    }
+
+    /// Collapses the focused node, or moves to its parent if already collapsed
+    pub fn collapse(&mut self) {
+        let Some(idx) = self.selected_node() else {
+            return;
+        };
+        let node = &self.nodes[idx];
+        if node.expanded && node.n_children > 0 {
+            self.set_expanded(idx, false)
+        } else if let Some(parent) = node.parent {
+            self.select_node(parent)
+        }
+    }
+
+    pub fn expand_all(&mut self) {
+        let selected = self.selected_node();
+        self.nodes.iter_mut().for_each(|n| n.expanded = true);
+        if let Some(idx) = selected {
+            self.select_node(idx)
+        }
+    }
+
+    pub fn collapse_all(&mut self) {
+        let mut selected = self.selected_node();
+        while let Some(parent) = selected.and_then(|idx| self.nodes[idx].parent) {
+            selected = Some(parent)
+        }
+        self.nodes
+            .iter_mut()
+            .for_each(|n| n.expanded = n.n_children == 0);
+        if let Some(idx) = selected {
+            self.select_node(idx)
+        }
+    }
+
+    fn set_expanded(&mut self, idx: usize, expanded: bool) {
+        if self.nodes[idx].n_children == 0 {
+            return;
+        }
+        self.nodes[idx].expanded = expanded;
+        self.select_node(idx);
+    }
+
+    /// The jq-style path of the focused node, eg `.NetworkSettings.Networks.bridge.IPAddress`
+    pub fn selected_path(&self) -> Option<String> {
+        self.selected_node().map(|idx| self.nodes[idx].path.clone())
+    }
+
+    pub fn set_search(&mut self, search: Option<String>) {
+        self.search = search.filter(|s| !s.is_empty());
+    }
+
+    /// Moves the selection to the next node matching the current search, expanding
+    /// any collapsed ancestors of the match.  Returns whether or not a match was found
+    pub fn next_match(&mut self, reverse: bool, include_current: bool) -> bool {
+        let Some(search) = self.search.clone() else {
+            return false;
+        };
+        let n_nodes = self.nodes.len();
+        if n_nodes == 0 {
+            return false;
+        }
+        let current = self.selected_node().unwrap_or_default();
+
+        let start = if include_current { 0 } else { 1 };
+        for offset in start..=n_nodes {
+            let idx = if reverse {
+                (current + n_nodes * 2 - offset) % n_nodes
+            } else {
+                (current + offset) % n_nodes
+            };
+            if self.node_matches(idx, &search) {
+                let mut parent = self.nodes[idx].parent;
+                while let Some(p) = parent {
+                    self.nodes[p].expanded = true;
+                    parent = self.nodes[p].parent;
+                }
+                self.select_node(idx);
+                return true;
+            }
+        }
+        false
+    }
+
+    fn node_matches(&self, idx: usize, search: &str) -> bool {
+        let node = &self.nodes[idx];
+        let search = search.to_lowercase();
+        node.key.to_lowercase().contains(&search)
+            || node
+                .value
+                .as_ref()
+                .is_some_and(|v| v.to_lowercase().contains(&search))
+    }
+
+    fn node_line(&self, node: &TreeNode) -> Line<'static> {
+        let key_style = self.config.theme.key();
+        let match_style = self.config.theme.search_match();
+        let search = self.search.as_deref();
+
+        let marker = if node.n_children == 0 {
+            "  "
+        } else if node.expanded {
+            "▾ "
+        } else {
+            "▸ "
+        };
+
+        let mut spans = vec![Span::from(format!("{}{marker}", "  ".repeat(node.depth)))];
+        spans.extend(highlight(&node.key, search, key_style, match_style));
+        spans.push(Span::from(": "));
+
+        match &node.value {
+            Some(v) => spans.extend(highlight(v, search, Style::default(), match_style)),
+            None => {
+                let summary = if node.is_array {
+                    format!("[{}]", node.n_children)
+                } else {
+                    format!("{{{}}}", node.n_children)
+                };
+                spans.push(Span::from(summary).style(self.config.theme.hint()));
+            }
+        }
+
+        Line::from(spans)
+    }
+}
+
+impl Component for TreeView {
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        let lines: Vec<Line> = self
+            .visible()
+            .into_iter()
+            .map(|idx| self.node_line(&self.nodes[idx]))
+            .collect();
+
+        let list = List::new(lines).highlight_style(self.config.theme.selected());
+
+        f.render_stateful_widget(list, area, &mut self.list_state)
+    }
+}
+
+/// Splits `text` into spans, styling any case-insensitive occurrences of `search`
+pub fn highlight(
+    text: &str,
+    search: Option<&str>,
+    style: Style,
+    match_style: Style,
+) -> Vec<Span<'static>> {
+    let search = match search {
+        Some(s) if !s.is_empty() => lowercase_chars(s),
+        _ => return vec![Span::styled(text.to_string(), style)],
+    };
+
+    // Matches are found on characters rather than bytes, as lowercasing can change
+    // the number of bytes in a character, so are mapped back to byte offsets in `text`
+    let chars = lowercase_chars(text);
+    let offsets: Vec<usize> = text
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain([text.len()])
+        .collect();
+
+    let mut spans = vec![];
+    let mut last = 0;
+    let mut idx = 0;
+    while idx + search.len() <= chars.len() {
+        if chars[idx..idx + search.len()] != search[..] {
+            idx += 1;
+            continue;
+        }
+        let end = idx + search.len();
+        if idx > last {
+            spans.push(Span::styled(
+                text[offsets[last]..offsets[idx]].to_string(),
+                style,
+            ));
+        }
+        spans.push(Span::styled(
+            text[offsets[idx]..offsets[end]].to_string(),
+            match_style,
+        ));
+        last = end;
+        idx = end;
+    }
+    if last < chars.len() {
+        spans.push(Span::styled(text[offsets[last]..].to_string(), style));
+    }
+    spans
+}
+
+/// Lowercases each character on its own, so that the result lines up with the
+/// characters of the original text
+fn lowercase_chars(text: &str) -> Vec<char> {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+fn join_key(path: &str, key: &str) -> String {
+    let plain = !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        format!("{path}.{key}")
+    } else {
+        format!("{path}.{key:?}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Modifier;
+    use serde_json::json;
+
+    use super::*;
+
+    fn tree(value: serde_json::Value) -> TreeView {
+        let mut tree = TreeView::new(Box::default());
+        tree.set_value(&value);
+        tree
+    }
+
+    fn visible_keys(tree: &TreeView) -> Vec<&str> {
+        tree.visible()
+            .into_iter()
+            .map(|idx| tree.nodes[idx].key.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn flattens_nested_values_in_order() {
+        let tree = tree(json!({
+            "Id": "abc",
+            "Mounts": [{"Source": "/data"}],
+            "State": {"Running": true, "Pid": null},
+        }));
+
+        assert_eq!(
+            visible_keys(&tree),
+            ["Id", "Mounts", "[0]", "Source", "State", "Running", "Pid"]
+        );
+        let depths: Vec<usize> = tree.nodes.iter().map(|n| n.depth).collect();
+        assert_eq!(depths, [0, 0, 1, 2, 0, 1, 1]);
+
+        let mounts = &tree.nodes[1];
+        assert!(mounts.is_array);
+        assert_eq!(mounts.n_children, 1);
+        assert_eq!(mounts.subtree_end, 4);
+        assert_eq!(tree.nodes[5].value.as_deref(), Some("true"));
+        assert_eq!(tree.nodes[6].value.as_deref(), Some("~"));
+    }
+
+    #[test]
+    fn toggling_hides_and_shows_descendants() {
+        let mut tree = tree(json!({
+            "Id": "abc",
+            "State": {"Running": true, "Health": {"Status": "healthy"}},
+            "Name": "web",
+        }));
+
+        tree.down();
+        tree.toggle();
+        assert_eq!(visible_keys(&tree), ["Id", "State", "Name"]);
+        assert_eq!(tree.selected_node(), Some(1));
+
+        tree.down();
+        assert_eq!(tree.nodes[tree.selected_node().unwrap()].key, "Name");
+
+        tree.up();
+        tree.toggle();
+        assert_eq!(
+            visible_keys(&tree),
+            ["Id", "State", "Running", "Health", "Status", "Name"]
+        );
+    }
+
+    #[test]
+    fn leaves_cannot_be_collapsed() {
+        let mut tree = tree(json!({"Id": "abc", "Name": "web"}));
+
+        tree.toggle();
+        assert!(tree.nodes[0].expanded);
+        assert_eq!(visible_keys(&tree), ["Id", "Name"]);
+    }
+
+    #[test]
+    fn selection_stays_within_the_visible_nodes() {
+        let mut tree = tree(json!({"Id": "abc", "State": {"Running": true}}));
+
+        tree.bottom();
+        assert_eq!(tree.selected_node(), Some(2));
+        tree.down();
+        assert_eq!(tree.selected_node(), Some(2));
+
+        tree.top();
+        tree.up();
+        assert_eq!(tree.selected_node(), Some(0));
+    }
+
+    #[test]
+    fn expands_and_collapses_the_focused_node() {
+        let mut tree = tree(json!({"State": {"Health": {"Status": "healthy"}}}));
+        tree.bottom();
+
+        // Collapsing a leaf moves to its parent, then collapses that parent
+        tree.collapse();
+        assert_eq!(tree.nodes[tree.selected_node().unwrap()].key, "Health");
+        tree.collapse();
+        assert_eq!(visible_keys(&tree), ["State", "Health"]);
+
+        // Expanding an expanded node moves to its first child
+        tree.expand();
+        assert_eq!(visible_keys(&tree), ["State", "Health", "Status"]);
+        tree.expand();
+        assert_eq!(tree.nodes[tree.selected_node().unwrap()].key, "Status");
+    }
+
+    #[test]
+    fn collapse_all_keeps_the_top_level_ancestor_selected() {
+        let mut tree = tree(json!({"Id": "abc", "State": {"Running": true}}));
+        tree.bottom();
+
+        tree.collapse_all();
+        assert_eq!(visible_keys(&tree), ["Id", "State"]);
+        assert_eq!(tree.selected_node(), Some(1));
+
+        tree.expand_all();
+        assert_eq!(visible_keys(&tree), ["Id", "State", "Running"]);
+        assert_eq!(tree.selected_node(), Some(1));
+    }
+
+    #[test]
+    fn searches_forwards_and_backwards_expanding_matches() {
+        let mut tree = tree(json!({
+            "Name": "web",
+            "Config": {"Hostname": "web-1"},
+            "State": {"Status": "running"},
+        }));
+        tree.collapse_all();
+        tree.set_search(Some("WEB".into()));
+
+        assert!(tree.next_match(false, true));
+        assert_eq!(tree.selected_node(), Some(0));
+        assert!(tree.next_match(false, false));
+        assert_eq!(tree.selected_path().as_deref(), Some(".Config.Hostname"));
+        assert!(tree.nodes[1].expanded);
+
+        // Wraps around in both directions
+        assert!(tree.next_match(false, false));
+        assert_eq!(tree.selected_node(), Some(0));
+        assert!(tree.next_match(true, false));
+        assert_eq!(tree.selected_path().as_deref(), Some(".Config.Hostname"));
+
+        tree.set_search(Some("missing".into()));
+        assert!(!tree.next_match(false, false));
+        tree.set_search(Some(String::new()));
+        assert!(!tree.next_match(false, false));
+    }
+
+    #[test]
+    fn paths_quote_keys_which_are_not_identifiers() {
+        let tree = tree(json!({
+            "Config": {"Labels": {"com.docker.compose.service": "web"}},
+            "Mounts": [{"Source": "/data"}],
+        }));
+
+        let paths: Vec<&str> = tree.nodes.iter().map(|n| n.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                ".Config",
+                ".Config.Labels",
+                ".Config.Labels.\"com.docker.compose.service\"",
+                ".Mounts",
+                ".Mounts[0]",
+                ".Mounts[0].Source",
+            ]
+        );
+        assert_eq!(join_key(".Labels", ""), ".Labels.\"\"");
+    }
+
+    #[test]
+    fn paths_into_a_top_level_array_are_valid_jq() {
+        let tree = tree(json!([{"Id": "abc"}]));
+
+        let paths: Vec<&str> = tree.nodes.iter().map(|n| n.path.as_str()).collect();
+        assert_eq!(paths, [".[0]", ".[0].Id"]);
+    }
+
+    #[test]
+    fn highlights_case_insensitive_matches() {
+        let style = Style::default();
+        let match_style = Style::default().add_modifier(Modifier::BOLD);
+
+        let spans = highlight("Web-web", Some("WEB"), style, match_style);
+        let parts: Vec<(&str, Style)> = spans.iter().map(|s| (&*s.content, s.style)).collect();
+        assert_eq!(
+            parts,
+            [("Web", match_style), ("-", style), ("web", match_style)]
+        );
+
+        assert_eq!(highlight("web", None, style, match_style).len(), 1);
+    }
+
+    #[test]
+    fn highlights_text_whose_characters_change_size_when_lowercased() {
+        let style = Style::default();
+        let match_style = Style::default().add_modifier(Modifier::BOLD);
+
+        // Both are 5 bytes, but `ẞ` shrinks and `Ⱥ` grows when lowercased
+        let spans = highlight("ẞȺ", Some("ⱥ"), style, match_style);
+        let parts: Vec<(&str, Style)> = spans.iter().map(|s| (&*s.content, s.style)).collect();
+        assert_eq!(parts, [("ẞ", style), ("Ⱥ", match_style)]);
+
+        let spans = highlight("İstanbul", Some("stan"), style, match_style);
+        let parts: Vec<&str> = spans.iter().map(|s| &*s.content).collect();
+        assert_eq!(parts, ["İ", "stan", "bul"]);
+    }
+}
@@ -0,0 +1,159 @@
+use std::time::{Duration, Instant};
+
+use itertools::Itertools;
+use ratatui::{
+    layout::Rect,
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    config::Config,
+    events::Key,
+    keymap::{Binding, Input, Scope},
+    traits::Component,
+};
+
+/// Collects keys which form part of a sequence, such as `gg` or `<Ctrl+w>h`, and
+/// shows the possible continuations in a popup while the sequence is incomplete.
+///
+/// If no further key is pressed before the timeout, the keys so far are resolved
+/// on their own, so that a key can be bound both alone and as a prefix.
+#[derive(Debug)]
+pub struct WhichKey {
+    config: Box<Config>,
+    page: Scope,
+    pending: Vec<Key>,
+    since: Instant,
+}
+
+impl WhichKey {
+    pub fn new(config: Box<Config>) -> Self {
+        Self {
+            config,
+            page: Scope::Containers,
+            pending: vec![],
+            since: Instant::now(),
+        }
+    }
+
+    pub fn set_config(&mut self, config: Box<Config>) {
+        self.config = config;
+        self.clear();
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Processes a key pressed on the given page, returning what should then be
+    /// handled; a completed sequence is returned as the action it is bound to
+    pub fn update(&mut self, page: Scope, key: Key) -> Vec<Input> {
+        let mut inputs = vec![];
+
+        if !self.pending.is_empty() && self.since.elapsed() >= self.timeout() {
+            inputs.extend(self.flush());
+        }
+        if key == Key::Null {
+            inputs.push(key.into());
+            return inputs;
+        }
+
+        let keymap = &self.config.keybindings;
+        let mut sequence = self.pending.clone();
+        sequence.push(key);
+
+        if !keymap.continuations(page, &sequence).is_empty() {
+            if self.pending.is_empty() {
+                self.page = page;
+            }
+            self.pending = sequence;
+            self.since = Instant::now();
+        } else if self.pending.is_empty() {
+            inputs.push(key.into());
+        } else {
+            // The key either completes the sequence, or breaks it in which case the
+            // keys so far are discarded, as vim does
+            self.pending.clear();
+            match keymap.sequence(page, &sequence) {
+                Some(action) => inputs.push(action.into()),
+                None => inputs.push(key.into()),
+            }
+        }
+        inputs
+    }
+
+    /// Resolves the pending keys as they are, without waiting for any more
+    fn flush(&mut self) -> Option<Input> {
+        let pending = std::mem::take(&mut self.pending);
+        match pending.as_slice() {
+            [] => None,
+            [key] => Some(Input::Key(*key)),
+            keys => self
+                .config
+                .keybindings
+                .sequence(self.page, keys)
+                .map(Input::Action),
+        }
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_millis(self.config.key_sequence_timeout)
+    }
+}
+
+impl Component for WhichKey {
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let continuations = self
+            .config
+            .keybindings
+            .continuations(self.page, &self.pending)
+            .into_iter()
+            .map(|(binding, action)| (binding.to_string(), action.name()))
+            .sorted()
+            .dedup()
+            .collect::<Vec<_>>();
+        let key_width = continuations
+            .iter()
+            .map(|(key, _)| key.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let style = self.config.theme.help();
+        let lines = continuations
+            .iter()
+            .map(|(key, action)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{key:<key_width$}"),
+                        style.add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!("  {action}"), style),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        let width = (lines.iter().map(Line::width).max().unwrap_or_default() as u16)
+            .saturating_add(4)
+            .min(area.width);
+        let height = (lines.len() as u16).saturating_add(2).min(area.height);
+        let popup = Rect::new(
+            area.right().saturating_sub(width),
+            area.bottom().saturating_sub(height),
+            width,
+            height,
+        );
+
+        let title = format!(" {}… ", Binding::from(self.pending.clone()));
+        let block = Block::bordered().title(title).border_style(style);
+
+        f.render_widget(Clear, popup);
+        f.render_widget(Paragraph::new(lines).block(block), popup);
+    }
+}
@@ -1,13 +1,22 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
 use std::fs;
-use std::io::BufReader;
-use std::str::FromStr;
-use std::{fs::File, path::PathBuf};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
 
-use ratatui::style::Color;
+use ratatui::layout::Constraint;
 use serde::{Deserialize, Serialize};
+use serde_yml::{Mapping, Value};
 
 use color_eyre::eyre::{bail, Context, Result};
 
+use crate::{
+    docker::{container::ContainerField, image::ImageField},
+    keymap::Keymap,
+    theme::{Theme, ThemeConfig},
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default)]
@@ -19,33 +28,539 @@ pub struct Config {
     #[serde(default = "default_docker_path")]
     pub docker_path: String,
 
-    #[serde(default)]
+    /// A built-in theme or one in the `themes` directory, optionally with changes
+    /// to some of its styles
+    #[serde(default, rename = "theme")]
+    pub theme_config: ThemeConfig,
+
+    /// The styles resolved from `theme_config`
+    #[serde(skip)]
     pub theme: Theme,
+
+    #[serde(default)]
+    pub columns: ColumnsConfig,
+
+    #[serde(default)]
+    pub history: HistoryConfig,
+
+    #[serde(default)]
+    pub preview: PreviewConfig,
+
+    #[serde(default)]
+    pub tabs: TabsConfig,
+
+    #[serde(default)]
+    pub keybindings: Keymap,
+
+    /// Whether the mouse can be used to select rows, scroll and click on the keys
+    /// shown in the help; while it is on, selecting text in the terminal usually
+    /// needs `Shift` to be held
+    #[serde(default)]
+    pub mouse: bool,
+
+    /// How long to wait, in milliseconds, for the next key of a sequence such as `gg`
+    #[serde(default = "default_key_sequence_timeout")]
+    pub key_sequence_timeout: u64,
+
+    /// The command line settings the config was built with, so that it can be
+    /// rebuilt in the same way when a file changes
+    #[serde(skip)]
+    pub overrides: Overrides,
+
+    /// Which layer set each value
+    #[serde(skip)]
+    pub origins: Origins,
 }
 
 impl Config {
-    pub fn new(write: &bool, docker_path: Option<String>) -> Result<Self> {
-        let config_path = get_app_config_path()?.join("config.yaml");
-        if *write {
-            write_default_config(&config_path).context("failed to write default config")?;
+    /// Builds the config from each of its layers in turn, each taking precedence
+    /// over the last: the defaults, the user config file, the project config file,
+    /// `DUCKER_*` environment variables and finally the command line
+    pub fn new(overrides: Overrides) -> Result<Self> {
+        let mut layers = vec![];
+
+        let user = overrides.config_file()?;
+        // The default config file is optional, but one given on the command line isn't
+        if let Some(layer) = read_layer(&user, overrides.config_file.is_some())? {
+            layers.push((Origin::User(user), layer));
+        }
+        if let Some(project) = project_config_path() {
+            if let Some(layer) = read_layer(&project, true)? {
+                layers.push((Origin::Project(project), layer));
+            }
+        }
+        layers.extend(env_layers(env::vars())?);
+        layers.extend(overrides.layers());
+
+        let mut value = serde_yml::to_value(Config::default())?;
+        let mut origins = Origins::default();
+        for (origin, layer) in layers {
+            merge(&mut value, layer, &origin, &mut vec![], &mut origins);
         }
 
-        let mut config: Config;
+        let mut config: Config = serde_yml::from_value(value).context("unable to parse config")?;
+        config.columns.validate().context("invalid column config")?;
+        config
+            .keybindings
+            .validate()
+            .context("invalid keybindings config")?;
+        config.theme =
+            Theme::load(&config.theme_config, &themes_path()?).context("invalid theme config")?;
+        config.overrides = overrides;
+        config.origins = origins;
+
+        Ok(config)
+    }
+
+    /// Every value in the config, keyed by its path (eg `history.size`) and written
+    /// as inline yaml, along with the layer which set it
+    pub fn values(&self) -> Result<Vec<(String, String, Origin)>> {
+        let mut values = vec![];
+        leaves(
+            &serde_yml::to_value(self)?,
+            &mut vec![],
+            &self.origins,
+            &mut values,
+        )?;
+        Ok(values)
+    }
+
+    /// Checks the config file at `path`, reporting any keys which would be ignored
+    /// as well as anything which would stop it from loading
+    pub fn check(path: &Path) -> Result<Vec<Problem>> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("unable to read {}", path.display()))?;
 
-        if let Ok(f) = File::open(config_path) {
-            config = serde_yml::from_reader(BufReader::new(f)).context("unable to parse config")?;
-        } else {
-            config = Config::default()
+        // Nothing else can be checked until the file parses
+        let config: Config = match serde_yml::from_str(&text) {
+            Ok(config) => config,
+            Err(e) => return Ok(vec![Problem::from(e)]),
+        };
+
+        let mut problems = vec![];
+
+        let file: serde_yml::Value = serde_yml::from_str(&text)?;
+        let known = serde_yml::to_value(&config)?;
+        unknown_keys(&text, &file, &known, &mut vec![], &mut problems);
+
+        let themes = themes_path()?;
+        for (section, result) in [
+            ("columns", config.columns.validate()),
+            ("keybindings", config.keybindings.validate()),
+            (
+                "theme",
+                Theme::load(&config.theme_config, &themes).map(|_| ()),
+            ),
+        ] {
+            if let Err(e) = result {
+                problems.push(Problem {
+                    line: line_of(&text, &[PathSegment::Key(section.into())]),
+                    message: format!("invalid {section}: {e:#}"),
+                });
+            }
         }
 
-        if let Some(p) = docker_path {
-            config.docker_path = p;
+        Ok(problems)
+    }
+}
+
+/// Settings given on the command line, which take precedence over every other
+/// layer of config
+#[derive(Debug, Clone, Default)]
+pub struct Overrides {
+    /// A config file to read in place of the user config file
+    pub config_file: Option<PathBuf>,
+    pub theme: Option<String>,
+    pub exec: Option<String>,
+    pub docker_path: Option<String>,
+}
+
+impl Overrides {
+    /// The user config file; the one given on the command line if there is one
+    pub fn config_file(&self) -> Result<PathBuf> {
+        match &self.config_file {
+            Some(path) => Ok(path.clone()),
+            None => config_file_path(),
         }
+    }
 
-        Ok(config)
+    /// Every file which may hold config, whether or not it exists
+    pub fn config_files(&self) -> Result<Vec<PathBuf>> {
+        let mut files = vec![self.config_file()?];
+        files.extend(project_config_candidates());
+        Ok(files)
+    }
+
+    fn layers(&self) -> Vec<(Origin, Value)> {
+        [
+            ("--theme", &["theme", "name"][..], &self.theme),
+            ("--exec", &["default_exec"], &self.exec),
+            ("--docker-path", &["docker_path"], &self.docker_path),
+        ]
+        .into_iter()
+        .filter_map(|(flag, path, value)| {
+            let value = value.as_ref()?;
+            Some((
+                Origin::Cli(flag),
+                nested(path, Value::String(value.clone())),
+            ))
+        })
+        .collect()
+    }
+}
+
+/// The layer of config which set a value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    Default,
+    User(PathBuf),
+    Project(PathBuf),
+    Env(String),
+    Cli(&'static str),
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::User(path) => write!(f, "user config {}", path.display()),
+            Self::Project(path) => write!(f, "project config {}", path.display()),
+            Self::Env(var) => write!(f, "environment variable {var}"),
+            Self::Cli(flag) => write!(f, "command line {flag}"),
+        }
+    }
+}
+
+/// The layer which set each value, keyed by the path of the value.  Anything
+/// beneath a path was set along with it, and anything not found is a default
+#[derive(Debug, Clone, Default)]
+pub struct Origins(BTreeMap<String, Origin>);
+
+impl Origins {
+    pub fn of(&self, path: &str) -> Origin {
+        self.0
+            .iter()
+            .filter(|(set, _)| is_within(path, set))
+            .max_by_key(|(set, _)| set.len())
+            .map(|(_, origin)| origin.clone())
+            .unwrap_or(Origin::Default)
+    }
+
+    fn set(&mut self, path: &str, origin: &Origin) {
+        self.0.retain(|set, _| !is_within(set, path));
+        self.0.insert(path.into(), origin.clone());
+    }
+}
+
+/// Whether `path` is `parent` or anything beneath it
+fn is_within(path: &str, parent: &str) -> bool {
+    path.strip_prefix(parent)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+}
+
+/// Merges `layer` into `base`; maps are merged key by key while anything else,
+/// including lists, replaces what was there before.  Null values are left unset
+fn merge(
+    base: &mut Value,
+    layer: Value,
+    origin: &Origin,
+    path: &mut Vec<PathSegment>,
+    origins: &mut Origins,
+) {
+    match (base, layer) {
+        (_, Value::Null) => {}
+        (Value::Mapping(base), Value::Mapping(layer)) => {
+            for (key, value) in layer {
+                let Some(name) = key.as_str() else {
+                    continue;
+                };
+                path.push(PathSegment::Key(name.into()));
+                match base.get_mut(&key) {
+                    Some(base) => merge(base, value, origin, path, origins),
+                    None if value.is_null() => {}
+                    None => {
+                        origins.set(&display_path(path), origin);
+                        base.insert(key, value);
+                    }
+                }
+                path.pop();
+            }
+        }
+        (base, layer) => {
+            origins.set(&display_path(path), origin);
+            *base = layer;
+        }
+    }
+}
+
+/// Flattens `value` into the paths of the values it holds
+fn leaves(
+    value: &Value,
+    path: &mut Vec<PathSegment>,
+    origins: &Origins,
+    values: &mut Vec<(String, String, Origin)>,
+) -> Result<()> {
+    match value {
+        Value::Mapping(map) if !map.is_empty() => {
+            for (key, value) in map {
+                path.push(PathSegment::Key(key.as_str().unwrap_or_default().into()));
+                leaves(value, path, origins, values)?;
+                path.pop();
+            }
+        }
+        Value::Sequence(seq) if !seq.is_empty() => {
+            for (idx, value) in seq.iter().enumerate() {
+                path.push(PathSegment::Index(idx));
+                leaves(value, path, origins, values)?;
+                path.pop();
+            }
+        }
+        value => {
+            let display = display_path(path);
+            let origin = origins.of(&display);
+            let text = match value {
+                Value::Mapping(_) => "{}".into(),
+                Value::Sequence(_) => "[]".into(),
+                value => serde_yml::to_string(value)?.trim_end().to_string(),
+            };
+            values.push((display, text, origin));
+        }
+    }
+    Ok(())
+}
+
+/// Builds a value which sets `value` at `path`, eg `history: {size: 10}`
+fn nested(path: &[&str], value: Value) -> Value {
+    path.iter().rev().fold(value, |value, key| {
+        let mut map = Mapping::new();
+        map.insert(Value::String((*key).into()), value);
+        Value::Mapping(map)
+    })
+}
+
+/// Reads a config file as one layer of config.  A file which doesn't exist is only
+/// an error if it is `required`
+fn read_layer(path: &Path, required: bool) -> Result<Option<Value>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == ErrorKind::NotFound && !required => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("unable to open {}", path.display())),
+    };
+
+    let value: Value = serde_yml::from_str(&text)
+        .with_context(|| format!("unable to parse {}", path.display()))?;
+    if value.is_null() {
+        return Ok(None);
+    }
+    // Parsed on its own as well, so that any error points at this file
+    serde_yml::from_str::<Config>(&text)
+        .with_context(|| format!("unable to parse {}", path.display()))?;
+    Ok(Some(value))
+}
+
+const ENV_PREFIX: &str = "DUCKER_";
+
+/// A layer for each `DUCKER_*` environment variable which names a config key, with
+/// `__` separating nested keys, eg `DUCKER_HISTORY__SIZE`
+fn env_layers(vars: impl IntoIterator<Item = (String, String)>) -> Result<Vec<(Origin, Value)>> {
+    let known = serde_yml::to_value(Config::default())?;
+    let mut vars = vars
+        .into_iter()
+        .filter(|(var, _)| var.starts_with(ENV_PREFIX))
+        .collect::<Vec<(String, String)>>();
+    vars.sort();
+
+    let mut layers = vec![];
+    for (var, raw) in vars {
+        let key = var[ENV_PREFIX.len()..].to_lowercase();
+        let mut path = key.split("__").collect::<Vec<&str>>();
+        // Other variables may belong to ducker, or to other tools
+        if known.get(path[0]).is_none() {
+            continue;
+        }
+        // A theme named on its own keeps any changes made to its styles by the files
+        if path == ["theme"] && !raw.trim_start().starts_with('{') {
+            path.push("name");
+        }
+
+        // The value is read as yaml so that numbers, lists and maps can be set, but
+        // falls back to plain text so that eg a prompt of `[docker]` is still a string
+        let parsed = serde_yml::from_str::<Value>(&raw)
+            .map(|value| nested(&path, value))
+            .map_err(color_eyre::eyre::Error::from)
+            .and_then(|layer| {
+                serde_yml::from_value::<Config>(layer.clone())?;
+                Ok(layer)
+            });
+        let layer = match parsed {
+            Ok(layer) => layer,
+            Err(e) => {
+                let layer = nested(&path, Value::String(raw));
+                if serde_yml::from_value::<Config>(layer.clone()).is_err() {
+                    return Err(e).with_context(|| format!("invalid value for {var}"));
+                }
+                layer
+            }
+        };
+        layers.push((Origin::Env(var), layer));
+    }
+    Ok(layers)
+}
+
+const PROJECT_CONFIG_FILE: &str = ".ducker.yaml";
+
+/// The places a project config may be: the current directory and each of its
+/// parents up to the root of the git repository it is in, nearest first
+fn project_config_candidates() -> Vec<PathBuf> {
+    let Ok(cwd) = env::current_dir() else {
+        return vec![];
+    };
+    let mut candidates = vec![];
+    for dir in cwd.ancestors() {
+        candidates.push(dir.join(PROJECT_CONFIG_FILE));
+        if dir.join(".git").exists() {
+            return candidates;
+        }
+    }
+    // Outside of a repository only the current directory is used
+    candidates.truncate(1);
+    candidates
+}
+
+/// The nearest project config file, if there is one
+pub fn project_config_path() -> Option<PathBuf> {
+    project_config_candidates()
+        .into_iter()
+        .find(|path| path.is_file())
+}
+
+/// Something wrong with a config file, along with the line it is on if known
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl From<serde_yml::Error> for Problem {
+    fn from(value: serde_yml::Error) -> Self {
+        let mut message = value.to_string();
+        let line = value.location().map(|l| {
+            // The location is given separately, so is removed from the message
+            message = message.replacen(
+                &format!(" at line {} column {}", l.line(), l.column()),
+                "",
+                1,
+            );
+            l.line()
+        });
+        Self { line, message }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+fn display_path(path: &[PathSegment]) -> String {
+    let mut display = String::new();
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) if display.is_empty() => display.push_str(key),
+            PathSegment::Key(key) => display.push_str(&format!(".{key}")),
+            PathSegment::Index(idx) => display.push_str(&format!("[{idx}]")),
+        }
+    }
+    display
+}
+
+/// Finds keys in the file which are absent from the config it was parsed into,
+/// and so were ignored
+fn unknown_keys(
+    text: &str,
+    file: &serde_yml::Value,
+    known: &serde_yml::Value,
+    path: &mut Vec<PathSegment>,
+    problems: &mut Vec<Problem>,
+) {
+    match (file, known) {
+        (serde_yml::Value::Mapping(file), serde_yml::Value::Mapping(known)) => {
+            for (key, value) in file {
+                let Some(name) = key.as_str() else {
+                    continue;
+                };
+                path.push(PathSegment::Key(name.into()));
+                match known.get(key) {
+                    Some(known) => unknown_keys(text, value, known, path, problems),
+                    // Unset optional values aren't written back out
+                    None if value.is_null() => {}
+                    None => problems.push(Problem {
+                        line: line_of(text, path),
+                        message: format!("unknown key \"{}\"", display_path(path)),
+                    }),
+                }
+                path.pop();
+            }
+        }
+        (serde_yml::Value::Sequence(file), serde_yml::Value::Sequence(known)) => {
+            for (idx, (file, known)) in file.iter().zip(known).enumerate() {
+                path.push(PathSegment::Index(idx));
+                unknown_keys(text, file, known, path, problems);
+                path.pop();
+            }
+        }
+        _ => {}
     }
 }
 
+/// Finds the line of the given key by searching for each part of its path in
+/// turn.  Only block style yaml is understood, which covers almost all configs
+fn line_of(text: &str, path: &[PathSegment]) -> Option<usize> {
+    let lines = text.lines().collect::<Vec<&str>>();
+    let mut start = 0;
+    let mut found = None;
+
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) => {
+                let n = (start..lines.len()).find(|n| is_key_line(lines[*n], key))?;
+                found = Some(n);
+                start = n + 1;
+            }
+            PathSegment::Index(idx) => {
+                let n = (start..lines.len())
+                    .filter(|n| lines[*n].trim_start().starts_with("- "))
+                    .nth(*idx)?;
+                found = Some(n);
+                // The first key of an item is on the same line as its dash
+                start = n;
+            }
+        }
+    }
+
+    found.map(|n| n + 1)
+}
+
+fn is_key_line(line: &str, key: &str) -> bool {
+    let line = line.trim_start().trim_start_matches("- ");
+    [key.to_string(), format!("\"{key}\""), format!("'{key}'")]
+        .iter()
+        .any(|k| {
+            line.strip_prefix(k.as_str())
+                .is_some_and(|rest| rest.trim_start().starts_with(':'))
+        })
+}
+
 fn default_prompt() -> String {
     "🦆".into()
 }
@@ -62,8 +577,8 @@ fn default_docker_path() -> String {
     return "npipe:////./pipe/docker_engine".into();
 }
 
-fn default_use_theme() -> bool {
-    false
+fn default_key_sequence_timeout() -> u64 {
+    1000
 }
 
 impl Default for Config {
@@ -72,140 +587,245 @@ impl Default for Config {
             prompt: default_prompt(),
             default_exec: default_exec(),
             docker_path: default_docker_path(),
+            theme_config: ThemeConfig::default(),
             theme: Theme::default(),
+            columns: ColumnsConfig::default(),
+            history: HistoryConfig::default(),
+            preview: PreviewConfig::default(),
+            tabs: TabsConfig::default(),
+            keybindings: Keymap::default(),
+            mouse: false,
+            key_sequence_timeout: default_key_sequence_timeout(),
+            overrides: Overrides::default(),
+            origins: Origins::default(),
         }
     }
 }
 
+/// How the command prompt's history is kept between sessions
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Theme {
-    /// Test
-    #[serde(default = "default_use_theme")]
-    use_theme: bool,
+pub struct HistoryConfig {
+    /// The maximum number of commands to keep
+    #[serde(default = "default_history_size")]
+    pub size: usize,
 
-    #[serde(default = "default_title_colour")]
-    title: Color,
+    /// The file in which to keep the history; defaults to `history` in the ducker
+    /// data directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
 
-    #[serde(default = "default_help_colour")]
-    help: Color,
+impl HistoryConfig {
+    pub fn path(&self) -> Result<PathBuf> {
+        match &self.path {
+            Some(path) => Ok(path.clone()),
+            None => Ok(get_app_data_path()?.join("history")),
+        }
+    }
+}
 
-    #[serde(default = "default_background_colour")]
-    background: Color,
+fn default_history_size() -> usize {
+    100
+}
 
-    #[serde(default = "default_success_colour")]
-    footer: Color,
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            size: default_history_size(),
+            path: None,
+        }
+    }
+}
 
-    #[serde(default = "default_footer_colour")]
-    success: Color,
+/// The preview of the selected resource shown alongside the `Containers` and
+/// `Images` tables
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewConfig {
+    /// Whether the preview is shown when ducker starts
+    #[serde(default)]
+    pub open: bool,
 
-    #[serde(default = "default_error_colour")]
-    error: Color,
+    /// Where the preview is placed relative to the table
+    #[serde(default)]
+    pub layout: PreviewLayout,
 
-    #[serde(default = "default_positive_highlight_colour")]
-    positive_highlight: Color,
+    /// The number of the most recent log lines shown in the logs preview
+    #[serde(default = "default_preview_log_lines")]
+    pub log_lines: u8,
+}
 
-    #[serde(default = "default_negative_highlight_colour")]
-    negative_highlight: Color,
+fn default_preview_log_lines() -> u8 {
+    20
 }
 
-impl Theme {
-    pub fn title(&self) -> Color {
-        if self.use_theme {
-            self.title
-        } else {
-            Color::Green
-        }
-    }
-    pub fn help(&self) -> Color {
-        if self.use_theme {
-            self.help
-        } else {
-            Color::Red
-        }
-    }
-    pub fn background(&self) -> Color {
-        if self.use_theme {
-            self.background
-        } else {
-            Color::Reset
-        }
-    }
-    pub fn footer(&self) -> Color {
-        if self.use_theme {
-            self.footer
-        } else {
-            Color::Cyan
+impl Default for PreviewConfig {
+    fn default() -> Self {
+        Self {
+            open: false,
+            layout: PreviewLayout::default(),
+            log_lines: default_preview_log_lines(),
         }
     }
-    pub fn success(&self) -> Color {
-        if self.use_theme {
-            self.success
-        } else {
-            Color::Green
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewLayout {
+    /// To the right of the table on wide terminals, otherwise below it
+    #[default]
+    Auto,
+    Right,
+    Below,
+}
+
+/// The pages kept open as tabs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TabsConfig {
+    /// The most tabs in the background which keep streaming, eg the logs of a
+    /// container; any others are stopped until they are next shown
+    #[serde(default = "default_background_streams")]
+    pub background_streams: usize,
+}
+
+fn default_background_streams() -> usize {
+    5
+}
+
+impl Default for TabsConfig {
+    fn default() -> Self {
+        Self {
+            background_streams: default_background_streams(),
         }
     }
-    pub fn error(&self) -> Color {
-        if self.use_theme {
-            self.error
-        } else {
-            Color::Red
+}
+
+/// The columns to display in each of the resource tables
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnsConfig {
+    #[serde(default = "default_container_columns")]
+    pub containers: Vec<ColumnConfig>,
+
+    #[serde(default = "default_image_columns")]
+    pub images: Vec<ColumnConfig>,
+}
+
+impl ColumnsConfig {
+    fn validate(&self) -> Result<()> {
+        for column in &self.containers {
+            column
+                .field
+                .parse::<ContainerField>()
+                .context("invalid containers column")?;
+            column.constraint().context("invalid containers column")?;
         }
-    }
-    pub fn positive_highlight(&self) -> Color {
-        if self.use_theme {
-            self.positive_highlight
-        } else {
-            Color::Green
+        for column in &self.images {
+            column
+                .field
+                .parse::<ImageField>()
+                .context("invalid images column")?;
+            column.constraint().context("invalid images column")?;
         }
+        Ok(())
     }
-    pub fn negative_highlight(&self) -> Color {
-        if self.use_theme {
-            self.negative_highlight
-        } else {
-            Color::Magenta
+}
+
+impl Default for ColumnsConfig {
+    fn default() -> Self {
+        Self {
+            containers: default_container_columns(),
+            images: default_image_columns(),
         }
     }
 }
 
-fn default_title_colour() -> Color {
-    Color::from_str("#96e072").unwrap()
-}
-fn default_success_colour() -> Color {
-    Color::from_str("#96e072").unwrap()
-}
-fn default_help_colour() -> Color {
-    Color::from_str("#ee5d43").unwrap()
-}
-fn default_background_colour() -> Color {
-    Color::from_str("#23262e").unwrap()
-}
-fn default_footer_colour() -> Color {
-    Color::from_str("#00e8c6").unwrap()
-}
-fn default_error_colour() -> Color {
-    Color::from_str("#ee5d43").unwrap()
-}
-fn default_positive_highlight_colour() -> Color {
-    Color::from_str("#96e072").unwrap()
+/// A single column in a resource table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnConfig {
+    /// The field to display, eg `created` or `label:com.docker.compose.service`
+    pub field: String,
+
+    /// Header to display instead of the field's default header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+
+    /// Either a percentage of the table width (eg `20%`) or a number of characters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<ColumnWidth>,
+
+    /// Minimum number of characters; the column grows to fill any spare space
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<u16>,
+
+    /// Maximum number of characters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<u16>,
 }
-fn default_negative_highlight_colour() -> Color {
-    Color::from_str("#ff00aa").unwrap()
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ColumnWidth {
+    Length(u16),
+    Text(String),
 }
 
-impl Default for Theme {
-    fn default() -> Self {
+impl ColumnConfig {
+    fn new(field: &str, percentage: u16) -> Self {
         Self {
-            use_theme: default_use_theme(),
-            title: default_title_colour(),
-            help: default_help_colour(),
-            success: default_success_colour(),
-            background: default_background_colour(),
-            footer: default_footer_colour(),
-            error: default_error_colour(),
-            positive_highlight: default_positive_highlight_colour(),
-            negative_highlight: default_negative_highlight_colour(),
+            field: field.into(),
+            header: None,
+            width: Some(ColumnWidth::Text(format!("{percentage}%"))),
+            min: None,
+            max: None,
         }
     }
+
+    /// The layout constraint for the column; columns without a width, min or max
+    /// share any space left over by the other columns
+    pub fn constraint(&self) -> Result<Constraint> {
+        let constraint = match (&self.width, self.min, self.max) {
+            (None, None, None) => Constraint::Fill(1),
+            (Some(ColumnWidth::Length(l)), None, None) => Constraint::Length(*l),
+            (Some(ColumnWidth::Text(w)), None, None) => match w.trim().strip_suffix('%') {
+                Some(p) => match p.trim().parse::<u16>() {
+                    Ok(p) if p <= 100 => Constraint::Percentage(p),
+                    _ => bail!("invalid percentage width `{w}` for column `{}`", self.field),
+                },
+                None => match w.trim().parse::<u16>() {
+                    Ok(l) => Constraint::Length(l),
+                    Err(_) => bail!("invalid width `{w}` for column `{}`", self.field),
+                },
+            },
+            (None, Some(min), None) => Constraint::Min(min),
+            (None, None, Some(max)) => Constraint::Max(max),
+            _ => bail!(
+                "only one of width, min and max can be set for column `{}`",
+                self.field
+            ),
+        };
+        Ok(constraint)
+    }
+}
+
+fn default_container_columns() -> Vec<ColumnConfig> {
+    vec![
+        ColumnConfig::new("id", 12),
+        ColumnConfig::new("image", 20),
+        ColumnConfig::new("command", 20),
+        ColumnConfig::new("created", 10),
+        ColumnConfig::new("status", 13),
+        ColumnConfig::new("ports", 10),
+        ColumnConfig::new("name", 10),
+    ]
+}
+
+fn default_image_columns() -> Vec<ColumnConfig> {
+    vec![
+        ColumnConfig::new("id", 20),
+        ColumnConfig::new("name", 20),
+        ColumnConfig::new("tag", 20),
+        ColumnConfig::new("created", 20),
+        ColumnConfig::new("size", 20),
+    ]
 }
 
 fn get_app_config_path() -> Result<std::path::PathBuf> {
@@ -223,8 +843,181 @@ fn get_app_config_path() -> Result<std::path::PathBuf> {
     Ok(path)
 }
 
-fn write_default_config(path: &PathBuf) -> Result<()> {
+/// The config file, eg `~/.config/ducker/config.yaml`
+pub fn config_file_path() -> Result<PathBuf> {
+    Ok(get_app_config_path()?.join("config.yaml"))
+}
+
+/// The directory holding the theme files which the config may refer to by name
+pub fn themes_path() -> Result<PathBuf> {
+    Ok(get_app_config_path()?.join("themes"))
+}
+
+/// The directory to which traces are written, eg `~/.local/share/ducker/logs`
+pub fn logs_path() -> Result<PathBuf> {
+    let path = get_app_data_path()?.join("logs");
+    std::fs::create_dir_all(&path)?;
+    Ok(path)
+}
+
+fn get_app_data_path() -> Result<std::path::PathBuf> {
+    let Some(mut path) = dirs_next::data_dir() else {
+        bail!("unable to find data path")
+    };
+    path.push("ducker");
+    Ok(path)
+}
+
+/// Writes the default config to `path`, overwriting anything already there
+pub fn write_default_config(path: &Path) -> Result<()> {
     let config = Config::default();
     fs::write(path, serde_yml::to_string(&config)?)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Merges each layer over the defaults in turn, as `Config::new` does
+    fn build(layers: Vec<(Origin, &str)>) -> (Config, Origins) {
+        let mut value = serde_yml::to_value(Config::default()).unwrap();
+        let mut origins = Origins::default();
+        for (origin, layer) in layers {
+            let layer = serde_yml::from_str(layer).unwrap();
+            merge(&mut value, layer, &origin, &mut vec![], &mut origins);
+        }
+        (serde_yml::from_value(value).unwrap(), origins)
+    }
+
+    #[test]
+    fn later_layers_take_precedence() {
+        let user = Origin::User("config.yaml".into());
+        let env = Origin::Env("DUCKER_HISTORY__SIZE".into());
+        let (config, origins) = build(vec![
+            (user.clone(), "prompt: '>'\nhistory:\n  size: 10\n"),
+            (env.clone(), "history:\n  size: 20\n"),
+            (Origin::Cli("--exec"), "default_exec: /bin/sh\n"),
+        ]);
+
+        assert_eq!(config.prompt, ">");
+        assert_eq!(config.history.size, 20);
+        assert_eq!(config.default_exec, "/bin/sh");
+        assert_eq!(config.docker_path, default_docker_path());
+
+        assert_eq!(origins.of("prompt"), user);
+        assert_eq!(origins.of("history.size"), env);
+        assert_eq!(origins.of("default_exec"), Origin::Cli("--exec"));
+        assert_eq!(origins.of("docker_path"), Origin::Default);
+    }
+
+    #[test]
+    fn null_values_do_not_unset_earlier_layers() {
+        let user = Origin::User("config.yaml".into());
+        let (config, origins) = build(vec![
+            (user.clone(), "prompt: '>'\n"),
+            (Origin::Project("project.yaml".into()), "prompt: ~\n"),
+        ]);
+        assert_eq!(config.prompt, ">");
+        assert_eq!(origins.of("prompt"), user);
+    }
+
+    #[test]
+    fn origins_cover_everything_beneath_a_path() {
+        let user = Origin::User("config.yaml".into());
+        let env = Origin::Env("DUCKER_THEME".into());
+        let mut origins = Origins::default();
+        origins.set("theme.name", &env);
+        assert_eq!(origins.of("theme.name"), env);
+        assert_eq!(origins.of("theme"), Origin::Default);
+
+        // Setting a whole section replaces anything set within it
+        origins.set("theme", &user);
+        assert_eq!(origins.of("theme.name"), user);
+        assert_eq!(origins.of("themes"), Origin::Default);
+    }
+
+    #[test]
+    fn finds_the_line_of_a_key() {
+        let text =
+            "prompt: '>'\ncolumns:\n  containers:\n    - field: name\n    - field: image\n      width: 10\n";
+        let key = |k: &str| PathSegment::Key(k.into());
+        assert_eq!(line_of(text, &[key("prompt")]), Some(1));
+        assert_eq!(line_of(text, &[key("columns"), key("containers")]), Some(3));
+        assert_eq!(
+            line_of(
+                text,
+                &[
+                    key("columns"),
+                    key("containers"),
+                    PathSegment::Index(1),
+                    key("width")
+                ]
+            ),
+            Some(6)
+        );
+        assert_eq!(line_of(text, &[key("history")]), None);
+    }
+
+    #[test]
+    fn check_reports_the_line_of_each_problem() {
+        let path = env::temp_dir().join(format!("ducker-check-{}.yaml", std::process::id()));
+        let check = |text: &str| {
+            fs::write(&path, text).unwrap();
+            Config::check(&path).unwrap()
+        };
+
+        let problems = check(
+            "prompt: '>'\nhistory:\n  size: 10\n  colour: red\nkeybindings:\n  containers.exec: [!Char d]\n",
+        );
+        let unparseable = check("history:\n  size: lots\n");
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(problems.len(), 2);
+        assert_eq!(
+            problems[0],
+            Problem {
+                line: Some(4),
+                message: "unknown key \"history.colour\"".into(),
+            }
+        );
+        assert_eq!(problems[1].line, Some(5));
+        assert!(problems[1].message.starts_with("invalid keybindings"));
+
+        assert_eq!(unparseable.len(), 1);
+        assert_eq!(unparseable[0].line, Some(2));
+    }
+
+    #[test]
+    fn env_layers_set_nested_keys() {
+        let vars = |vars: &[(&str, &str)]| {
+            vars.iter()
+                .map(|(var, value)| (var.to_string(), value.to_string()))
+                .collect::<Vec<_>>()
+        };
+        let layers = env_layers(vars(&[
+            ("DUCKER_HISTORY__SIZE", "25"),
+            ("DUCKER_PROMPT", "[docker]"),
+            ("DUCKER_THEME", "dracula"),
+            ("DUCKER_NOT_A_KEY", "ignored"),
+            ("HOME", "/root"),
+        ]))
+        .unwrap();
+        let layer = |var: &str| {
+            layers
+                .iter()
+                .find(|(origin, _)| *origin == Origin::Env(var.into()))
+                .map(|(_, layer)| serde_yml::to_string(layer).unwrap())
+        };
+        assert_eq!(
+            layer("DUCKER_HISTORY__SIZE").unwrap(),
+            "history:\n  size: 25\n"
+        );
+        // Not valid as a prompt when read as yaml, so kept as text
+        assert_eq!(layer("DUCKER_PROMPT").unwrap(), "prompt: '[docker]'\n");
+        assert_eq!(layer("DUCKER_THEME").unwrap(), "theme:\n  name: dracula\n");
+        assert_eq!(layer("DUCKER_NOT_A_KEY"), None);
+
+        assert!(env_layers(vars(&[("DUCKER_HISTORY__SIZE", "lots")])).is_err());
+    }
+}
@@ -1,26 +1,25 @@
-use crate::{
-    docker::{container::DockerContainer, image::DockerImage, traits::Describe},
-    events::Transition,
+use ratatui::widgets::{ListState, TableState};
+
+use crate::docker::{
+    container::DockerContainer, image::DockerImage, prune::PruneOptions, traits::Describe,
 };
 
 /// AppContext is used to share context between pages
 /// Includes a set of optional fields that can be sent to the
 /// next page.
-/// Where `then` is set, the next page should use that as the
-/// transition on completion instead of any other transition
+/// Returning to the previous page is handled by the navigation history in the
+/// page manager, so pages don't need to know where they were opened from
 #[derive(Clone, Debug, Default)]
 pub struct AppContext {
-    pub then: Option<Box<Transition>>,
     pub list_idx: Option<usize>,
     pub docker_container: Option<DockerContainer>,
     pub docker_image: Option<DockerImage>,
     pub describable: Option<Box<dyn Describe>>,
-}
-
-impl AppContext {
-    pub fn next(&self) -> Option<Transition> {
-        self.then.as_ref().map(|t| *t.clone())
-    }
+    pub prune_options: Option<PruneOptions>,
+    /// Only show logs since the given unix timestamp
+    pub log_since: Option<i64>,
+    /// The command to exec into a container with, instead of the configured default
+    pub exec_command: Option<String>,
 }
 
 impl PartialEq for AppContext {
@@ -37,24 +36,25 @@ impl PartialEq for AppContext {
             return false;
         }
 
-        // Describe doesn't have derived PartialEqual trait
-        // We can assume that if both offer the same description,
-        // then they are equal
-        if (self.describable.is_some() && other.describable.is_none())
-            || (self.describable.is_none() && other.describable.is_some())
-        {
+        if self.prune_options != other.prune_options {
+            return false;
+        }
+
+        if self.log_since != other.log_since || self.exec_command != other.exec_command {
             return false;
         }
 
-        if self.describable.is_some() && other.describable.is_some() {
-            match (
-                self.describable.clone().unwrap().describe(),
-                other.describable.clone().unwrap().describe(),
-            ) {
-                (Ok(s), Ok(o)) => return s == o,
-                (Err(s), Err(o)) => return format!("{s}") == format!("{o}"),
-                (_, _) => return false,
+        // Describe doesn't have derived PartialEqual trait
+        // We can assume that if both describe the same resource,
+        // then they are equal
+        match (&self.describable, &other.describable) {
+            (Some(s), Some(o)) => {
+                if s.get_id() != o.get_id() || s.get_name() != o.get_name() {
+                    return false;
+                }
             }
+            (None, None) => {}
+            (_, _) => return false,
         }
 
         true
@@ -62,3 +62,48 @@ impl PartialEq for AppContext {
 }
 
 impl AppContext {}
+
+/// The selection and scroll position of a page, recorded in the navigation history
+/// so that they can be restored when returning to the page
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PageView {
+    pub selected: Option<usize>,
+    pub offset: usize,
+}
+
+impl PageView {
+    pub fn from_table(state: &TableState) -> Self {
+        Self {
+            selected: state.selected(),
+            offset: state.offset(),
+        }
+    }
+
+    pub fn from_list(state: &ListState) -> Self {
+        Self {
+            selected: state.selected(),
+            offset: state.offset(),
+        }
+    }
+
+    /// Applies the view to a table with `len` rows, keeping the selection within it
+    pub fn restore_table(&self, state: &mut TableState, len: usize) {
+        *state = TableState::default()
+            .with_offset(self.offset.min(len.saturating_sub(1)))
+            .with_selected(self.clamp(len));
+    }
+
+    /// Applies the view to a list with `len` items, keeping the selection within it
+    pub fn restore_list(&self, state: &mut ListState, len: usize) {
+        *state = ListState::default()
+            .with_offset(self.offset.min(len.saturating_sub(1)))
+            .with_selected(self.clamp(len));
+    }
+
+    fn clamp(&self, len: usize) -> Option<usize> {
+        match len {
+            0 => None,
+            _ => self.selected.map(|s| s.min(len - 1)),
+        }
+    }
+}
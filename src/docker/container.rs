@@ -1,17 +1,17 @@
-use bollard::container::{ListContainersOptions, RemoveContainerOptions};
-use chrono::prelude::DateTime;
-use chrono::Local;
-use color_eyre::eyre::{bail, Context, Result};
+use async_trait::async_trait;
+use bollard::container::{InspectContainerOptions, ListContainersOptions, RemoveContainerOptions};
+use color_eyre::eyre::{bail, Context, Error, Result};
 use serde::Serialize;
-use std::{
-    collections::HashMap,
-    time::{Duration, UNIX_EPOCH},
-};
+use std::{cmp::Ordering, collections::HashMap, str::FromStr};
 use tokio::process::Command;
+use tracing::instrument;
 
 use bollard::secret::ContainerSummary;
 
-use super::traits::Describe;
+use super::{
+    traits::Describe,
+    util::{find_by_name_or_id, format_size, format_timestamp},
+};
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct DockerContainer {
@@ -19,15 +19,48 @@ pub struct DockerContainer {
     pub image_id: String,
     pub image: String,
     pub command: String,
-    pub created: String,
+    /// Unix timestamp (in seconds) at which the container was created
+    pub created: i64,
     pub status: String,
     pub ports: String,
     pub names: String,
     pub running: bool,
-    read_write_size: String,
-    root_fs_size: String,
-    labels: Option<HashMap<String, String>>,
-    network_mode: Option<String>,
+    pub labels: HashMap<String, String>,
+    /// IP addresses of the container on each network it is attached to
+    pub ip_addresses: String,
+    /// Health of the container, as reported in its status (eg `healthy`)
+    pub health: String,
+    /// Only populated when listed with [`ContainerDetails::size`]
+    pub size_rw: Option<i64>,
+    /// Only populated when listed with [`ContainerDetails::size`]
+    pub size_root_fs: Option<i64>,
+    /// Only populated by [`DockerContainer::fetch_restart_count`], as it needs the
+    /// container to be inspected
+    pub restart_count: Option<i64>,
+}
+
+/// Optional, more expensive, details to retrieve when listing containers
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ContainerDetails {
+    /// Calculate the size of each container's filesystem
+    pub size: bool,
+    /// Inspect the container to find the number of times it has restarted; this is
+    /// only done by [`DockerContainer::get`], never when listing every container
+    pub restart_count: bool,
+}
+
+impl ContainerDetails {
+    /// Whether these include every detail that `other` does
+    pub fn covers(&self, other: &Self) -> bool {
+        (self.size || !other.size) && (self.restart_count || !other.restart_count)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            size: self.size || other.size,
+            restart_count: self.restart_count || other.restart_count,
+        }
+    }
 }
 
 impl DockerContainer {
@@ -54,13 +87,6 @@ impl DockerContainer {
                 .join(", "),
             None => "".into(),
         };
-        let datetime = DateTime::<Local>::from(
-            UNIX_EPOCH
-                + Duration::from_secs(c.created.unwrap_or_default().try_into().unwrap_or_default()),
-        )
-        .format("%Y-%m-%d %H:%M:%S")
-        .to_string();
-
         let running = matches!(c.state.clone().unwrap_or_default().as_str(), "running");
 
         let names = c
@@ -72,31 +98,93 @@ impl DockerContainer {
             .collect::<Vec<String>>()
             .join(", ");
 
+        let ip_addresses = c
+            .network_settings
+            .clone()
+            .and_then(|n| n.networks)
+            .unwrap_or_default()
+            .into_values()
+            .filter_map(|n| n.ip_address)
+            .filter(|ip| !ip.is_empty())
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let status = c.status.clone().unwrap_or_default();
+        let health = if status.contains("(unhealthy)") {
+            "unhealthy"
+        } else if status.contains("(healthy)") {
+            "healthy"
+        } else if status.contains("(health: starting)") {
+            "starting"
+        } else {
+            ""
+        };
+
         Self {
             id: c.id.clone().unwrap_or_default(),
             image: c.image.clone().unwrap_or_default(),
             image_id: c.image.clone().unwrap_or_default(),
             command: c.command.clone().unwrap_or_default(),
-            created: datetime,
+            created: c.created.unwrap_or_default(),
             status: c.status.clone().unwrap_or_default(),
             ports,
             names,
             running,
-            read_write_size: String::new(),
-            root_fs_size: String::new(),
-            labels: None,
-            network_mode: None,
+            labels: c.labels.clone().unwrap_or_default(),
+            ip_addresses,
+            health: health.into(),
+            size_rw: c.size_rw,
+            size_root_fs: c.size_root_fs,
+            restart_count: None,
         }
     }
 
     /// Lists all containers present on a given docker daemon
     ///
     /// **Note:** While this returns all containers present, it will
-    /// return only the minimal set of values (those which aren't marked optional).
-    pub async fn list(docker: &bollard::Docker) -> Result<Vec<Self>> {
+    /// return only the minimal set of values (those which aren't marked optional)
+    /// unless the more expensive details are requested.
+    #[instrument(level = "debug", skip(docker), err)]
+    pub async fn list(docker: &bollard::Docker, details: ContainerDetails) -> Result<Vec<Self>> {
+        Self::list_filtered(docker, details, HashMap::new()).await
+    }
+
+    /// Gets a single container by its id, if it still exists
+    #[instrument(level = "debug", skip(docker), err)]
+    pub async fn get(
+        docker: &bollard::Docker,
+        id: &str,
+        details: ContainerDetails,
+    ) -> Result<Option<Self>> {
+        let filters = HashMap::from([("id".to_string(), vec![id.to_string()])]);
+        let containers = Self::list_filtered(docker, details, filters).await?;
+        let mut container = containers.into_iter().find(|c| c.id == id);
+        if let (Some(container), true) = (container.as_mut(), details.restart_count) {
+            container.fetch_restart_count(docker).await;
+        }
+        Ok(container)
+    }
+
+    /// Inspects the container for the number of times it has restarted, which isn't
+    /// included when listing containers.  It is left unset if the inspect fails
+    pub async fn fetch_restart_count(&mut self, docker: &bollard::Docker) {
+        self.restart_count = docker
+            .inspect_container(&self.id, None)
+            .await
+            .ok()
+            .and_then(|i| i.restart_count);
+    }
+
+    async fn list_filtered(
+        docker: &bollard::Docker,
+        details: ContainerDetails,
+        filters: HashMap<String, Vec<String>>,
+    ) -> Result<Vec<Self>> {
         let containers = docker
             .list_containers(Some(ListContainersOptions::<String> {
                 all: true,
+                size: details.size,
+                filters,
                 ..Default::default()
             }))
             .await
@@ -108,7 +196,51 @@ impl DockerContainer {
         Ok(containers)
     }
 
+    /// Gets the value of the given field formatted for display
+    pub fn field(&self, field: &ContainerField) -> String {
+        match field {
+            ContainerField::Id => self.id.clone(),
+            ContainerField::Image => self.image.clone(),
+            ContainerField::Command => self.command.clone(),
+            ContainerField::Created => format_timestamp(self.created),
+            ContainerField::Status => self.status.clone(),
+            ContainerField::Ports => self.ports.clone(),
+            ContainerField::Names => self.names.clone(),
+            ContainerField::Ip => self.ip_addresses.clone(),
+            ContainerField::Health => self.health.clone(),
+            ContainerField::RestartCount => self
+                .restart_count
+                .map(|r| r.to_string())
+                .unwrap_or_default(),
+            ContainerField::Size => match (self.size_rw, self.size_root_fs) {
+                (Some(rw), Some(root_fs)) => {
+                    format!("{} (virtual {})", format_size(rw), format_size(root_fs))
+                }
+                (Some(rw), None) => format_size(rw),
+                _ => String::new(),
+            },
+            ContainerField::Label(label) => self.labels.get(label).cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Compares the given field of two containers, using the underlying typed value
+    /// where the field has one
+    pub fn compare_field(&self, other: &Self, field: &ContainerField) -> Ordering {
+        match field {
+            ContainerField::Created => self.created.cmp(&other.created),
+            ContainerField::RestartCount => self.restart_count.cmp(&other.restart_count),
+            ContainerField::Size => self.size_rw.cmp(&other.size_rw),
+            // Group running containers together, before falling back to the status message
+            ContainerField::Status => other
+                .running
+                .cmp(&self.running)
+                .then_with(|| self.status.cmp(&other.status)),
+            _ => self.field(field).cmp(&other.field(field)),
+        }
+    }
+
     /// Delete the container from the relevant docker daemon
+    #[instrument(level = "debug", skip_all, fields(id = %self.id, force), err)]
     pub async fn delete(&self, docker: &bollard::Docker, force: bool) -> Result<()> {
         let opt = RemoveContainerOptions {
             force,
@@ -119,6 +251,7 @@ impl DockerContainer {
     }
 
     /// Start the container on the docker daemon
+    #[instrument(level = "debug", skip_all, fields(id = %self.id), err)]
     pub async fn start(&self, docker: &bollard::Docker) -> Result<()> {
         docker
             .start_container::<String>(&self.id, None)
@@ -129,6 +262,7 @@ impl DockerContainer {
     }
 
     /// Stop the container from running
+    #[instrument(level = "debug", skip_all, fields(id = %self.id), err)]
     pub async fn stop(&self, docker: &bollard::Docker) -> Result<()> {
         docker
             .stop_container(&self.id, None)
@@ -137,7 +271,31 @@ impl DockerContainer {
         Ok(())
     }
 
+    /// Finds a container by name, id or unique id prefix
+    #[instrument(level = "debug", skip(docker), err(level = "debug"))]
+    pub async fn find(docker: &bollard::Docker, name_or_id: &str) -> Result<Self> {
+        let containers = Self::list(docker, ContainerDetails::default()).await?;
+        find_by_name_or_id(
+            containers,
+            name_or_id,
+            |c| c.names.split(", ").any(|n| n == name_or_id),
+            |c| &c.id,
+        )
+        .with_context(|| format!("unable to find container \"{name_or_id}\""))
+    }
+
+    /// Restart the container
+    #[instrument(level = "debug", skip_all, fields(id = %self.id), err)]
+    pub async fn restart(&self, docker: &bollard::Docker) -> Result<()> {
+        docker
+            .restart_container(&self.id, None)
+            .await
+            .context("failed to restart container")?;
+        Ok(())
+    }
+
     /// Exec into the container with the given command
+    #[instrument(level = "debug", skip(self), fields(name = %self.names), err)]
     pub async fn attach(&self, cmd: &str) -> Result<()> {
         Command::new("clear").spawn()?.wait().await?;
 
@@ -155,6 +313,88 @@ impl DockerContainer {
     }
 }
 
+/// A field of a container which can be displayed as a column in a table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContainerField {
+    Id,
+    Image,
+    Command,
+    Created,
+    Status,
+    Ports,
+    Names,
+    Ip,
+    Health,
+    RestartCount,
+    Size,
+    /// The value of the given label, eg `label:com.docker.compose.service`
+    Label(String),
+}
+
+impl ContainerField {
+    /// The name used to refer to the field in config and commands
+    pub fn key(&self) -> String {
+        match self {
+            Self::Id => "id".into(),
+            Self::Image => "image".into(),
+            Self::Command => "command".into(),
+            Self::Created => "created".into(),
+            Self::Status => "status".into(),
+            Self::Ports => "ports".into(),
+            Self::Names => "name".into(),
+            Self::Ip => "ip".into(),
+            Self::Health => "health".into(),
+            Self::RestartCount => "restart_count".into(),
+            Self::Size => "size".into(),
+            Self::Label(label) => format!("label:{label}"),
+        }
+    }
+
+    /// The default header used when displaying the field as a column
+    pub fn header(&self) -> String {
+        match self {
+            Self::Id => "ID".into(),
+            Self::Image => "Image".into(),
+            Self::Command => "Command".into(),
+            Self::Created => "Created".into(),
+            Self::Status => "Status".into(),
+            Self::Ports => "Ports".into(),
+            Self::Names => "Names".into(),
+            Self::Ip => "IP".into(),
+            Self::Health => "Health".into(),
+            Self::RestartCount => "Restarts".into(),
+            Self::Size => "Size".into(),
+            Self::Label(label) => label.clone(),
+        }
+    }
+}
+
+impl FromStr for ContainerField {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let field = match s {
+            "id" => Self::Id,
+            "image" => Self::Image,
+            "command" => Self::Command,
+            "created" => Self::Created,
+            "status" => Self::Status,
+            "ports" => Self::Ports,
+            "name" | "names" => Self::Names,
+            "ip" => Self::Ip,
+            "health" => Self::Health,
+            "restart_count" | "restarts" => Self::RestartCount,
+            "size" => Self::Size,
+            _ => match s.strip_prefix("label:") {
+                Some(label) if !label.is_empty() => Self::Label(label.into()),
+                _ => bail!("unknown container field `{s}`"),
+            },
+        };
+        Ok(field)
+    }
+}
+
+#[async_trait]
 impl Describe for DockerContainer {
     fn get_id(&self) -> String {
         self.id.clone()
@@ -162,13 +402,12 @@ impl Describe for DockerContainer {
     fn get_name(&self) -> String {
         format!("container: {}", self.names)
     }
-    fn describe(&self) -> Result<Vec<String>> {
-        let summary = match serde_yml::to_string(&self) {
-            Ok(s) => s,
-            Err(_) => {
-                bail!("failed to parse container summary")
-            }
-        };
-        Ok(summary.lines().map(String::from).collect())
+    #[instrument(level = "debug", skip_all, fields(id = %self.id), err)]
+    async fn describe(&self, docker: &bollard::Docker) -> Result<serde_json::Value> {
+        let details = docker
+            .inspect_container(&self.id, Some(InspectContainerOptions { size: true }))
+            .await
+            .context("unable to inspect container")?;
+        serde_json::to_value(details).context("failed to parse container details")
     }
 }
@@ -1,38 +1,37 @@
-use bollard::image::RemoveImageOptions;
-use byte_unit::{Byte, UnitType};
-use chrono::prelude::DateTime;
-use chrono::Local;
-use color_eyre::eyre::{bail, Context, Result};
+use async_trait::async_trait;
+use bollard::image::{CreateImageOptions, RemoveImageOptions};
+use color_eyre::eyre::{bail, Context, Error, Result};
+use futures::StreamExt;
 use itertools::Itertools;
 use serde::Serialize;
-use std::collections::HashMap;
-use std::time::{Duration, UNIX_EPOCH};
+use std::{cmp::Ordering, collections::HashMap, str::FromStr};
+use tracing::instrument;
 
 use bollard::{image::ListImagesOptions, secret::ImageSummary};
 
-use super::traits::Describe;
+use super::{
+    traits::Describe,
+    util::{find_by_name_or_id, format_size, format_timestamp},
+};
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 pub struct DockerImage {
     pub id: String,
     pub name: String,
     pub tag: String,
-    pub created: String,
-    pub size: String,
+    /// Unix timestamp (in seconds) at which the image was created
+    pub created: i64,
+    /// Size of the image in bytes
+    pub size: i64,
     pub tags: Vec<String>,
     pub digests: Vec<String>,
+    pub labels: HashMap<String, String>,
 }
 
 impl DockerImage {
     pub fn from(bollard_image: ImageSummary) -> Vec<Self> {
         let mut response = vec![];
 
-        let datetime = DateTime::<Local>::from(
-            UNIX_EPOCH + Duration::from_secs(bollard_image.created.try_into().unwrap_or_default()),
-        )
-        .format("%Y-%m-%d %H:%M:%S");
-        let b = Byte::from_u64(bollard_image.size as u64).get_appropriate_unit(UnitType::Binary);
-
         let tags = bollard_image.repo_tags.clone();
         let digests = bollard_image.repo_digests.clone();
 
@@ -44,10 +43,11 @@ impl DockerImage {
                     id: bollard_image.id.clone(),
                     name: split_tag[0].to_string(),
                     tag: split_tag[1].to_string(),
-                    created: datetime.to_string(),
-                    size: format!("{b:.2}"),
+                    created: bollard_image.created,
+                    size: bollard_image.size,
                     tags: tags.clone(),
                     digests: digests.clone(),
+                    labels: bollard_image.labels.clone(),
                 })
             }
         } else {
@@ -55,22 +55,30 @@ impl DockerImage {
                 id: bollard_image.id.clone(),
                 name: "<none>".into(),
                 tag: "<none>".into(),
-                created: datetime.to_string(),
-                size: format!("{b:.2}"),
+                created: bollard_image.created,
+                size: bollard_image.size,
                 tags,
                 digests,
+                labels: bollard_image.labels,
             })
         }
         response
     }
 
+    /// Whether the image is dangling, ie it has no tag.  Intermediate images, which
+    /// are listed along with all images, are also untagged
+    pub fn is_dangling(&self) -> bool {
+        self.name == "<none>"
+    }
+
+    #[instrument(level = "debug", skip(docker), err)]
     pub async fn list(docker: &bollard::Docker, dangling: bool) -> Result<Vec<Self>> {
         let mut filters: HashMap<String, Vec<String>> = HashMap::new();
         if !dangling {
             filters.insert("dangling".into(), vec!["false".into()]);
         }
 
-        let mut images = docker
+        let images = docker
             .list_images(Some(ListImagesOptions::<String> {
                 all: true,
                 digests: false,
@@ -81,10 +89,10 @@ impl DockerImage {
             .into_iter()
             .flat_map(DockerImage::from)
             .collect_vec();
-        images.sort_by_key(|i| i.id.clone());
         Ok(images)
     }
 
+    #[instrument(level = "debug", skip_all, fields(id = %self.id, force), err)]
     pub async fn delete(&self, docker: &bollard::Docker, force: bool) -> Result<()> {
         docker
             .remove_image(
@@ -99,6 +107,67 @@ impl DockerImage {
         Ok(())
     }
 
+    /// Gets the value of the given field formatted for display
+    pub fn field(&self, field: &ImageField) -> String {
+        match field {
+            ImageField::Id => self.id.clone(),
+            ImageField::Name => self.name.clone(),
+            ImageField::Tag => self.tag.clone(),
+            ImageField::Created => format_timestamp(self.created),
+            ImageField::Size => format_size(self.size),
+            ImageField::Label(label) => self.labels.get(label).cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Compares the given field of two images, using the underlying typed value
+    /// where the field has one
+    pub fn compare_field(&self, other: &Self, field: &ImageField) -> Ordering {
+        match field {
+            ImageField::Created => self.created.cmp(&other.created),
+            ImageField::Size => self.size.cmp(&other.size),
+            _ => self.field(field).cmp(&other.field(field)),
+        }
+    }
+
+    /// Pull the latest version of the image's tag from its registry
+    #[instrument(level = "debug", skip_all, fields(image = %self.get_full_name()), err)]
+    pub async fn pull(&self, docker: &bollard::Docker) -> Result<()> {
+        if self.name == "<none>" {
+            bail!("unable to pull an untagged image");
+        }
+
+        let mut stream = docker.create_image(
+            Some(CreateImageOptions {
+                from_image: self.name.clone(),
+                tag: self.tag.clone(),
+                ..Default::default()
+            }),
+            None,
+            None,
+        );
+        while let Some(info) = stream.next().await {
+            info.context("failed to pull image")?;
+        }
+        Ok(())
+    }
+
+    /// Finds an image by name (with an optional tag), id or unique id prefix
+    #[instrument(level = "debug", skip(docker), err(level = "debug"))]
+    pub async fn find(docker: &bollard::Docker, reference: &str) -> Result<Self> {
+        let images = Self::list(docker, true).await?;
+        let (name, tag) = match reference.rsplit_once(':') {
+            Some((name, tag)) if !tag.contains('/') => (name, tag),
+            _ => (reference, "latest"),
+        };
+        find_by_name_or_id(
+            images,
+            reference.trim_start_matches("sha256:"),
+            |i| i.name == name && i.tag == tag,
+            |i| i.id.trim_start_matches("sha256:"),
+        )
+        .with_context(|| format!("unable to find image \"{reference}\""))
+    }
+
     pub fn get_full_name(&self) -> String {
         let image = format!("{}:{}", self.name, self.tag);
 
@@ -110,6 +179,64 @@ impl DockerImage {
     }
 }
 
+/// A field of an image which can be displayed as a column in a table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageField {
+    Id,
+    Name,
+    Tag,
+    Created,
+    Size,
+    /// The value of the given label, eg `label:org.opencontainers.image.version`
+    Label(String),
+}
+
+impl ImageField {
+    /// The name used to refer to the field in config and commands
+    pub fn key(&self) -> String {
+        match self {
+            Self::Id => "id".into(),
+            Self::Name => "name".into(),
+            Self::Tag => "tag".into(),
+            Self::Created => "created".into(),
+            Self::Size => "size".into(),
+            Self::Label(label) => format!("label:{label}"),
+        }
+    }
+
+    /// The default header used when displaying the field as a column
+    pub fn header(&self) -> String {
+        match self {
+            Self::Id => "ID".into(),
+            Self::Name => "Name".into(),
+            Self::Tag => "Tag".into(),
+            Self::Created => "Created".into(),
+            Self::Size => "Size".into(),
+            Self::Label(label) => label.clone(),
+        }
+    }
+}
+
+impl FromStr for ImageField {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let field = match s {
+            "id" => Self::Id,
+            "name" => Self::Name,
+            "tag" => Self::Tag,
+            "created" => Self::Created,
+            "size" => Self::Size,
+            _ => match s.strip_prefix("label:") {
+                Some(label) if !label.is_empty() => Self::Label(label.into()),
+                _ => bail!("unknown image field `{s}`"),
+            },
+        };
+        Ok(field)
+    }
+}
+
+#[async_trait]
 impl Describe for DockerImage {
     fn get_id(&self) -> String {
         self.id.clone()
@@ -117,13 +244,12 @@ impl Describe for DockerImage {
     fn get_name(&self) -> String {
         format!("image: {}", self.name)
     }
-    fn describe(&self) -> Result<Vec<String>> {
-        let summary = match serde_yml::to_string(&self) {
-            Ok(s) => s,
-            Err(_) => {
-                bail!("failed to parse image summary")
-            }
-        };
-        Ok(summary.lines().map(String::from).collect())
+    #[instrument(level = "debug", skip_all, fields(id = %self.id), err)]
+    async fn describe(&self, docker: &bollard::Docker) -> Result<serde_json::Value> {
+        let details = docker
+            .inspect_image(&self.id)
+            .await
+            .context("unable to inspect image")?;
+        serde_json::to_value(details).context("failed to parse image details")
     }
 }
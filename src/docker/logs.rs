@@ -17,7 +17,14 @@ impl DockerLogs {
         Self::new(container)
     }
 
-    pub fn get_log_stream(&self, docker: &bollard::Docker, tail: u8) -> impl Stream<Item = String> {
+    /// Streams the logs of the container; if `since` is given then all logs since that
+    /// unix timestamp are returned, otherwise the last `tail` lines
+    pub fn get_log_stream(
+        &self,
+        docker: &bollard::Docker,
+        tail: u8,
+        since: Option<i64>,
+    ) -> impl Stream<Item = String> {
         let logstream = docker
             .logs(
                 &self.container.id,
@@ -25,7 +32,11 @@ impl DockerLogs {
                     follow: true,
                     stdout: true,
                     stderr: true,
-                    tail: tail.to_string(),
+                    since: since.unwrap_or_default(),
+                    tail: match since {
+                        Some(_) => "all".into(),
+                        None => tail.to_string(),
+                    },
                     ..Default::default()
                 }),
             )
@@ -1,5 +1,10 @@
 pub mod container;
 pub mod image;
 pub mod logs;
+pub mod network;
+pub mod prune;
+pub mod stats;
+pub mod store;
 pub mod traits;
 pub mod util;
+pub mod volume;
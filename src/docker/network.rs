@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+use bollard::network::{InspectNetworkOptions, ListNetworksOptions};
+use color_eyre::eyre::{Context, Result};
+use serde::Serialize;
+use tracing::instrument;
+
+use super::{traits::Describe, util::find_by_name_or_id};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DockerNetwork {
+    pub id: String,
+    pub name: String,
+    pub driver: String,
+}
+
+impl DockerNetwork {
+    /// Lists all networks present on a given docker daemon
+    #[instrument(level = "debug", skip(docker), err)]
+    pub async fn list(docker: &bollard::Docker) -> Result<Vec<Self>> {
+        let networks = docker
+            .list_networks(None::<ListNetworksOptions<String>>)
+            .await
+            .context("unable to retrieve list of networks")?
+            .into_iter()
+            .map(|n| Self {
+                id: n.id.unwrap_or_default(),
+                name: n.name.unwrap_or_default(),
+                driver: n.driver.unwrap_or_default(),
+            })
+            .collect();
+        Ok(networks)
+    }
+
+    /// Finds a network by name, id or unique id prefix
+    #[instrument(level = "debug", skip(docker), err(level = "debug"))]
+    pub async fn find(docker: &bollard::Docker, name_or_id: &str) -> Result<Self> {
+        let networks = Self::list(docker).await?;
+        find_by_name_or_id(networks, name_or_id, |n| n.name == name_or_id, |n| &n.id)
+            .with_context(|| format!("unable to find network \"{name_or_id}\""))
+    }
+}
+
+#[async_trait]
+impl Describe for DockerNetwork {
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+    fn get_name(&self) -> String {
+        format!("network: {}", self.name)
+    }
+    #[instrument(level = "debug", skip_all, fields(id = %self.id), err)]
+    async fn describe(&self, docker: &bollard::Docker) -> Result<serde_json::Value> {
+        let details = docker
+            .inspect_network(&self.id, None::<InspectNetworkOptions<String>>)
+            .await
+            .context("unable to inspect network")?;
+        serde_json::to_value(details).context("failed to parse network details")
+    }
+}
@@ -0,0 +1,533 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    str::FromStr,
+};
+
+use bollard::{
+    container::{ListContainersOptions, PruneContainersOptions},
+    image::PruneImagesOptions,
+    network::{ListNetworksOptions, PruneNetworksOptions},
+    volume::PruneVolumesOptions,
+};
+use chrono::{DateTime, Local};
+use color_eyre::eyre::{bail, Context, Error, Result};
+use tokio::process::Command;
+use tracing::instrument;
+
+use super::util::{docker_host, format_size, parse_time};
+
+/// Networks created by docker itself, which are never pruned
+const PREDEFINED_NETWORKS: [&str; 3] = ["bridge", "host", "none"];
+
+/// Label docker gives to volumes created without a name
+const ANONYMOUS_VOLUME_LABEL: &str = "com.docker.volume.anonymous";
+
+/// The kinds of resources which can be pruned
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PruneKind {
+    #[default]
+    Containers,
+    Images,
+    Volumes,
+    Networks,
+    BuildCache,
+}
+
+impl PruneKind {
+    pub const ALL: [PruneKind; 5] = [
+        Self::Containers,
+        Self::Images,
+        Self::Volumes,
+        Self::Networks,
+        Self::BuildCache,
+    ];
+
+    pub fn next(self) -> Self {
+        let idx = Self::ALL
+            .iter()
+            .position(|k| *k == self)
+            .unwrap_or_default();
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        let idx = Self::ALL
+            .iter()
+            .position(|k| *k == self)
+            .unwrap_or_default();
+        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// The name used to refer to this kind in commands, eg `:prune buildcache`
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Containers => "containers",
+            Self::Images => "images",
+            Self::Volumes => "volumes",
+            Self::Networks => "networks",
+            Self::BuildCache => "buildcache",
+        }
+    }
+
+    /// Describes what `all` means for this kind, if it is supported
+    pub fn scope(self, all: bool) -> Option<&'static str> {
+        match (self, all) {
+            (Self::Images, false) => Some("dangling"),
+            (Self::Images, true) => Some("all unused"),
+            (Self::Volumes, false) => Some("anonymous"),
+            (Self::Volumes, true) => Some("all unused"),
+            (Self::BuildCache, false) => Some("unshared"),
+            (Self::BuildCache, true) => Some("all unused"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PruneKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for PruneKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let kind = match s {
+            "container" | "containers" => Self::Containers,
+            "image" | "images" => Self::Images,
+            "volume" | "volumes" => Self::Volumes,
+            "network" | "networks" => Self::Networks,
+            "buildcache" | "build-cache" | "builder" | "build" => Self::BuildCache,
+            _ => bail!(
+                "unknown kind \"{s}\", expected one of {}",
+                Self::ALL.map(|k| k.key()).join(", ")
+            ),
+        };
+        Ok(kind)
+    }
+}
+
+/// A filter on the resources to prune, matching those supported by the docker CLI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PruneFilter {
+    /// Only prune resources created before the given unix timestamp
+    Until { value: String, timestamp: i64 },
+    /// Only prune resources with (or, when negated, without) the given label
+    Label {
+        key: String,
+        value: Option<String>,
+        negate: bool,
+    },
+}
+
+impl PruneFilter {
+    fn matches(&self, candidate: &PruneCandidate) -> bool {
+        match self {
+            Self::Until { timestamp, .. } => candidate.created.is_none_or(|c| c < *timestamp),
+            Self::Label { key, value, negate } => {
+                let found = match (candidate.labels.get(key), value) {
+                    (Some(v), Some(value)) => v == value,
+                    (Some(_), None) => true,
+                    (None, _) => false,
+                };
+                found != *negate
+            }
+        }
+    }
+
+    /// The name and value of the filter as understood by the docker API
+    fn api_filter(&self) -> (&'static str, String) {
+        match self {
+            Self::Until { timestamp, .. } => ("until", timestamp.to_string()),
+            Self::Label { key, value, negate } => (
+                if *negate { "label!" } else { "label" },
+                match value {
+                    Some(v) => format!("{key}={v}"),
+                    None => key.clone(),
+                },
+            ),
+        }
+    }
+}
+
+impl fmt::Display for PruneFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Until { value, .. } => write!(f, "until={value}"),
+            Self::Label { .. } => {
+                let (name, value) = self.api_filter();
+                write!(f, "{name}={value}")
+            }
+        }
+    }
+}
+
+impl FromStr for PruneFilter {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if let Some(label) = s.strip_prefix("label!=") {
+            return label_filter(label, true);
+        }
+        if let Some(label) = s.strip_prefix("label=") {
+            return label_filter(label, false);
+        }
+        if let Some(until) = s.strip_prefix("until=") {
+            return Ok(Self::Until {
+                value: until.into(),
+                timestamp: parse_time(until)?,
+            });
+        }
+        bail!("invalid filter \"{s}\", expected until=<time>, label=<key>[=<value>] or label!=<key>[=<value>]")
+    }
+}
+
+fn label_filter(label: &str, negate: bool) -> Result<PruneFilter> {
+    let (key, value) = match label.split_once('=') {
+        Some((k, v)) => (k, Some(v.to_string())),
+        None => (label, None),
+    };
+    if key.is_empty() {
+        bail!("label filters require a key");
+    }
+    Ok(PruneFilter::Label {
+        key: key.into(),
+        value,
+        negate,
+    })
+}
+
+/// What to prune and how to narrow it down
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PruneOptions {
+    pub kind: PruneKind,
+    /// Prune all unused resources rather than only dangling ones, where supported
+    pub all: bool,
+    pub filters: Vec<PruneFilter>,
+}
+
+impl PruneOptions {
+    /// Parses the arguments to a prune command, eg `images until=24h --all`
+    pub fn parse(args: &[&str]) -> Result<Self> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+        if let Some(kind) = args.next() {
+            options.kind = kind.parse()?;
+        }
+        for arg in args {
+            match *arg {
+                "-a" | "--all" => options.all = true,
+                filter => options.filters.push(filter.parse()?),
+            }
+        }
+        Ok(options)
+    }
+
+    pub fn parse_filters(input: &str) -> Result<Vec<PruneFilter>> {
+        input.split_whitespace().map(|f| f.parse()).collect()
+    }
+
+    pub fn filters_string(&self) -> String {
+        self.filters
+            .iter()
+            .map(|f| f.to_string())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    fn api_filters(&self) -> HashMap<String, Vec<String>> {
+        let mut filters: HashMap<String, Vec<String>> = HashMap::new();
+        for filter in &self.filters {
+            let (name, value) = filter.api_filter();
+            filters.entry(name.into()).or_default().push(value);
+        }
+        filters
+    }
+}
+
+/// A resource which would be removed by a prune
+#[derive(Debug, Clone, Default)]
+pub struct PruneCandidate {
+    pub id: String,
+    pub name: String,
+    pub size: Option<i64>,
+    pub created: Option<i64>,
+    labels: HashMap<String, String>,
+}
+
+/// The outcome of a prune
+#[derive(Debug, Clone, Default)]
+pub struct PruneReport {
+    pub kind: PruneKind,
+    pub deleted: Vec<String>,
+    pub space_reclaimed: Option<String>,
+}
+
+impl PruneReport {
+    pub fn title(&self) -> String {
+        format!("Pruned {}", self.kind)
+    }
+
+    pub fn message(&self) -> String {
+        let mut message = if self.deleted.is_empty() {
+            format!("No {} were removed", self.kind)
+        } else {
+            format!("Removed {} {}", self.deleted.len(), self.kind)
+        };
+        if let Some(space) = &self.space_reclaimed {
+            message.push_str(&format!(", reclaiming {space}"));
+        }
+        message
+    }
+}
+
+/// Lists the resources which would be removed by pruning with the given options
+#[instrument(level = "debug", skip_all, fields(kind = %options.kind), err)]
+pub async fn preview(
+    docker: &bollard::Docker,
+    options: &PruneOptions,
+) -> Result<Vec<PruneCandidate>> {
+    let candidates = match options.kind {
+        PruneKind::Networks => preview_networks(docker).await?,
+        kind => {
+            let usage = docker.df().await.context("unable to retrieve disk usage")?;
+            match kind {
+                PruneKind::Containers => usage
+                    .containers
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|c| matches!(c.state.as_deref(), Some("exited" | "created" | "dead")))
+                    .map(|c| PruneCandidate {
+                        name: c
+                            .names
+                            .and_then(|n| n.first().cloned())
+                            .map(|n| n.trim_start_matches('/').to_string())
+                            .unwrap_or_default(),
+                        id: c.id.unwrap_or_default(),
+                        size: c.size_rw,
+                        created: c.created,
+                        labels: c.labels.unwrap_or_default(),
+                    })
+                    .collect(),
+                PruneKind::Images => usage
+                    .images
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|i| {
+                        let dangling = i.repo_tags.iter().all(|t| t == "<none>:<none>");
+                        i.containers <= 0 && (options.all || dangling)
+                    })
+                    .map(|i| PruneCandidate {
+                        name: i
+                            .repo_tags
+                            .first()
+                            .filter(|t| *t != "<none>:<none>")
+                            .cloned()
+                            .unwrap_or_else(|| "<none>".into()),
+                        id: i.id,
+                        size: Some(i.size),
+                        created: Some(i.created),
+                        labels: i.labels,
+                    })
+                    .collect(),
+                PruneKind::Volumes => usage
+                    .volumes
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|v| {
+                        let unused = v.usage_data.as_ref().is_none_or(|u| u.ref_count == 0);
+                        unused && (options.all || v.labels.contains_key(ANONYMOUS_VOLUME_LABEL))
+                    })
+                    .map(|v| PruneCandidate {
+                        id: v.name.clone(),
+                        name: v.name,
+                        size: v.usage_data.map(|u| u.size).filter(|s| *s >= 0),
+                        created: v.created_at.as_deref().and_then(parse_date),
+                        labels: v.labels,
+                    })
+                    .collect(),
+                _ => usage
+                    .build_cache
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|b| !b.in_use.unwrap_or_default())
+                    .filter(|b| options.all || !b.shared.unwrap_or_default())
+                    .map(|b| PruneCandidate {
+                        name: b.description.unwrap_or_default(),
+                        id: b.id.unwrap_or_default(),
+                        size: b.size,
+                        created: b
+                            .last_used_at
+                            .or(b.created_at)
+                            .as_deref()
+                            .and_then(parse_date),
+                        labels: HashMap::new(),
+                    })
+                    .collect(),
+            }
+        }
+    };
+
+    Ok(candidates
+        .into_iter()
+        .filter(|c| options.filters.iter().all(|f| f.matches(c)))
+        .collect())
+}
+
+async fn preview_networks(docker: &bollard::Docker) -> Result<Vec<PruneCandidate>> {
+    let containers = docker
+        .list_containers(Some(ListContainersOptions::<String> {
+            all: true,
+            ..Default::default()
+        }))
+        .await
+        .context("unable to retrieve list of containers")?;
+    let in_use = containers
+        .into_iter()
+        .filter_map(|c| c.network_settings.and_then(|n| n.networks))
+        .flat_map(|networks| networks.into_values().filter_map(|n| n.network_id))
+        .collect::<HashSet<String>>();
+
+    let networks = docker
+        .list_networks(None::<ListNetworksOptions<String>>)
+        .await
+        .context("unable to retrieve list of networks")?;
+
+    Ok(networks
+        .into_iter()
+        .filter(|n| {
+            let name = n.name.as_deref().unwrap_or_default();
+            let id = n.id.as_deref().unwrap_or_default();
+            !PREDEFINED_NETWORKS.contains(&name) && !in_use.contains(id)
+        })
+        .map(|n| PruneCandidate {
+            id: n.id.unwrap_or_default(),
+            name: n.name.unwrap_or_default(),
+            size: None,
+            created: n.created.as_deref().and_then(parse_date),
+            labels: n.labels.unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Removes the resources matched by the given options
+#[instrument(level = "debug", skip_all, fields(kind = %options.kind), err)]
+pub async fn prune(
+    docker: &bollard::Docker,
+    docker_path: &str,
+    options: &PruneOptions,
+) -> Result<PruneReport> {
+    let mut filters = options.api_filters();
+    let (deleted, space_reclaimed) = match options.kind {
+        PruneKind::Containers => {
+            let res = docker
+                .prune_containers(Some(PruneContainersOptions { filters }))
+                .await
+                .context("failed to prune containers")?;
+            (
+                res.containers_deleted.unwrap_or_default(),
+                res.space_reclaimed,
+            )
+        }
+        PruneKind::Images => {
+            filters.insert("dangling".into(), vec![(!options.all).to_string()]);
+            let res = docker
+                .prune_images(Some(PruneImagesOptions { filters }))
+                .await
+                .context("failed to prune images")?;
+            let deleted = res
+                .images_deleted
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|i| i.deleted)
+                .collect();
+            (deleted, res.space_reclaimed)
+        }
+        PruneKind::Volumes => {
+            if options.all {
+                filters.insert("all".into(), vec!["true".into()]);
+            }
+            let res = docker
+                .prune_volumes(Some(PruneVolumesOptions { filters }))
+                .await
+                .context("failed to prune volumes")?;
+            (res.volumes_deleted.unwrap_or_default(), res.space_reclaimed)
+        }
+        PruneKind::Networks => {
+            let res = docker
+                .prune_networks(Some(PruneNetworksOptions { filters }))
+                .await
+                .context("failed to prune networks")?;
+            (res.networks_deleted.unwrap_or_default(), None)
+        }
+        PruneKind::BuildCache => return prune_build_cache(docker_path, options).await,
+    };
+
+    Ok(PruneReport {
+        kind: options.kind,
+        deleted,
+        space_reclaimed: space_reclaimed.map(format_size),
+    })
+}
+
+/// The docker API version we are using has no endpoint for pruning the build cache,
+/// so as with exec we defer to the docker CLI.  It is pointed at the daemon the
+/// preview came from, rather than whichever the CLI would otherwise use
+async fn prune_build_cache(docker_path: &str, options: &PruneOptions) -> Result<PruneReport> {
+    let mut cmd = Command::new("docker");
+    cmd.env("DOCKER_HOST", docker_host(docker_path));
+    cmd.arg("builder").arg("prune").arg("--force");
+    if options.all {
+        cmd.arg("--all");
+    }
+    for filter in &options.filters {
+        let filter = match filter {
+            // The builder only understands durations
+            PruneFilter::Until { timestamp, .. } => {
+                let seconds = (Local::now().timestamp() - timestamp).max(0);
+                format!("until={seconds}s")
+            }
+            filter => filter.to_string(),
+        };
+        cmd.arg("--filter").arg(filter);
+    }
+
+    let output = cmd
+        .output()
+        .await
+        .context("failed to run docker builder prune")?;
+    if !output.status.success() {
+        bail!(
+            "failed to prune build cache: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    // The output is a table of the removed cache records followed by a total
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut deleted = vec![];
+    let mut space_reclaimed = None;
+    for line in stdout.lines().filter(|l| !l.trim().is_empty()) {
+        if let Some(total) = line.strip_prefix("Total:") {
+            space_reclaimed = Some(total.trim().to_string());
+        } else if !line.starts_with("ID") && !line.starts_with("Deleted") {
+            if let Some(id) = line.split_whitespace().next() {
+                deleted.push(id.trim_end_matches('*').to_string());
+            }
+        }
+    }
+
+    Ok(PruneReport {
+        kind: PruneKind::BuildCache,
+        deleted,
+        space_reclaimed,
+    })
+}
+
+fn parse_date(date: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(date)
+        .ok()
+        .map(|d| d.timestamp())
+}
@@ -0,0 +1,141 @@
+use bollard::container::{MemoryStatsStats, Stats, StatsOptions};
+use color_eyre::eyre::{Context, Result};
+use futures::{Stream, StreamExt};
+
+use super::{container::DockerContainer, util::format_size};
+
+/// A summary of a container's resource usage, as shown by `docker stats`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContainerStats {
+    /// Percentage of a single CPU, so may be over 100 on a machine with several
+    pub cpu_percent: f64,
+    pub memory_usage: u64,
+    pub memory_limit: u64,
+    pub network_rx: u64,
+    pub network_tx: u64,
+    pub block_read: u64,
+    pub block_write: u64,
+    pub pids: u64,
+}
+
+impl ContainerStats {
+    /// Streams the resource usage of a running container, roughly once a second
+    pub fn stream(
+        docker: &bollard::Docker,
+        container: &DockerContainer,
+    ) -> impl Stream<Item = Result<Self>> {
+        docker
+            .stats(
+                &container.id,
+                Some(StatsOptions {
+                    stream: true,
+                    one_shot: false,
+                }),
+            )
+            .map(|res| {
+                res.map(Self::from)
+                    .context("unable to retrieve container stats")
+            })
+    }
+
+    pub fn memory_percent(&self) -> f64 {
+        match self.memory_limit {
+            0 => 0.0,
+            limit => self.memory_usage as f64 / limit as f64 * 100.0,
+        }
+    }
+
+    /// The stats as lines of `label: value`
+    pub fn lines(&self) -> Vec<String> {
+        let size = |bytes: u64| format_size(bytes.try_into().unwrap_or(i64::MAX));
+        vec![
+            format!("CPU:       {:.2}%", self.cpu_percent),
+            format!(
+                "Memory:    {} / {} ({:.2}%)",
+                size(self.memory_usage),
+                size(self.memory_limit),
+                self.memory_percent()
+            ),
+            format!(
+                "Net I/O:   {} / {}",
+                size(self.network_rx),
+                size(self.network_tx)
+            ),
+            format!(
+                "Block I/O: {} / {}",
+                size(self.block_read),
+                size(self.block_write)
+            ),
+            format!("PIDs:      {}", self.pids),
+        ]
+    }
+}
+
+impl From<Stats> for ContainerStats {
+    /// Calculated in the same way as the docker CLI
+    fn from(stats: Stats) -> Self {
+        let cpu_delta = stats
+            .cpu_stats
+            .cpu_usage
+            .total_usage
+            .saturating_sub(stats.precpu_stats.cpu_usage.total_usage);
+        let system_delta = stats
+            .cpu_stats
+            .system_cpu_usage
+            .unwrap_or_default()
+            .saturating_sub(stats.precpu_stats.system_cpu_usage.unwrap_or_default());
+        let cpus = stats.cpu_stats.online_cpus.unwrap_or_else(|| {
+            stats
+                .cpu_stats
+                .cpu_usage
+                .percpu_usage
+                .as_ref()
+                .map_or(1, |p| p.len() as u64)
+        });
+        let cpu_percent = match system_delta {
+            0 => 0.0,
+            _ => cpu_delta as f64 / system_delta as f64 * cpus as f64 * 100.0,
+        };
+
+        // Page cache is counted in the usage, but can be reclaimed
+        let cache = match stats.memory_stats.stats {
+            Some(MemoryStatsStats::V1(v1)) => v1.total_inactive_file,
+            Some(MemoryStatsStats::V2(v2)) => v2.inactive_file,
+            None => 0,
+        };
+
+        let (network_rx, network_tx) = stats
+            .networks
+            .iter()
+            .flat_map(|n| n.values())
+            .fold((0, 0), |(rx, tx), n| (rx + n.rx_bytes, tx + n.tx_bytes));
+
+        let (block_read, block_write) = stats
+            .blkio_stats
+            .io_service_bytes_recursive
+            .iter()
+            .flatten()
+            .fold((0, 0), |(read, write), e| {
+                match e.op.to_lowercase().as_str() {
+                    "read" => (read + e.value, write),
+                    "write" => (read, write + e.value),
+                    _ => (read, write),
+                }
+            });
+
+        Self {
+            cpu_percent,
+            memory_usage: stats
+                .memory_stats
+                .usage
+                .unwrap_or_default()
+                .saturating_sub(cache),
+            memory_limit: stats.memory_stats.limit.unwrap_or_default(),
+            network_rx,
+            network_tx,
+            block_read,
+            block_write,
+            pids: stats.pids_stats.current.unwrap_or_default(),
+        }
+    }
+}
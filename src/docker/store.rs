@@ -0,0 +1,371 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bollard::{
+    secret::{EventMessage, EventMessageTypeEnum},
+    system::EventsOptions,
+    Docker,
+};
+use color_eyre::eyre::Result;
+use futures::{future::join_all, StreamExt};
+use tokio::{
+    sync::{mpsc::Sender, watch, Notify},
+    time::{interval, sleep, sleep_until, Instant, MissedTickBehavior},
+};
+use tracing::instrument;
+
+use super::{
+    container::{ContainerDetails, DockerContainer},
+    image::DockerImage,
+    network::DockerNetwork,
+    volume::DockerVolume,
+};
+use crate::events::{Key, Message, Transition};
+
+/// How often everything is refetched, in case an event was missed
+const RESYNC_INTERVAL: Duration = Duration::from_secs(30);
+/// How long to wait before subscribing to events again once the stream has ended
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+/// Image, volume and network events come in bursts, eg a pull emits one for each
+/// tag, so they are only refetched once the events have stopped for this long
+const REFETCH_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// The kinds of resource held in the store
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceKind {
+    Containers,
+    Images,
+    Volumes,
+    Networks,
+}
+
+impl ResourceKind {
+    pub const ALL: [Self; 4] = [
+        Self::Containers,
+        Self::Images,
+        Self::Volumes,
+        Self::Networks,
+    ];
+
+    /// The type of the daemon's events about this kind
+    fn event_type(self) -> &'static str {
+        match self {
+            Self::Containers => "container",
+            Self::Images => "image",
+            Self::Volumes => "volume",
+            Self::Networks => "network",
+        }
+    }
+}
+
+/// The number of each kind of resource, for showing at a glance
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceCounts {
+    pub containers: usize,
+    pub running: usize,
+    /// Images other than dangling images
+    pub images: usize,
+    pub volumes: usize,
+    pub networks: usize,
+}
+
+#[derive(Debug, Default)]
+struct StoreState {
+    containers: Vec<DockerContainer>,
+    images: Vec<DockerImage>,
+    volumes: Vec<DockerVolume>,
+    networks: Vec<DockerNetwork>,
+    details: ContainerDetails,
+    /// Whether a full sync has ever completed
+    synced: bool,
+    /// The error from the last full sync, if it failed
+    error: Option<String>,
+}
+
+/// The containers, images, volumes and networks on the daemon, shared across the
+/// app and kept up to date by a background task.  The task subscribes to the
+/// daemon's events, patching a container whenever one of its events arrives and
+/// refetching the other kinds after any of their events, and resyncs everything
+/// periodically in case any events are missed.
+///
+/// Reading from the store never waits on the daemon, so pages are able to read from
+/// it on every key press and tick, and have their resources ready as soon as they
+/// are opened
+#[derive(Debug, Clone)]
+pub struct ResourceStore {
+    state: Arc<Mutex<StoreState>>,
+    /// One channel per kind, indexed by `ResourceKind`, which is sent on whenever
+    /// the resources of that kind change
+    changes: [watch::Sender<()>; 4],
+    resync: Arc<Notify>,
+}
+
+impl ResourceStore {
+    /// Creates the store and starts keeping it up to date; it is empty until the
+    /// first sync completes
+    pub fn start(docker: Docker, tx: Sender<Message<Key, Transition>>) -> Self {
+        let store = Self {
+            state: Arc::new(Mutex::new(StoreState::default())),
+            changes: std::array::from_fn(|_| watch::channel(()).0),
+            resync: Arc::new(Notify::new()),
+        };
+        tokio::spawn(store.clone().run(docker, tx));
+        store
+    }
+
+    /// Subscribes to changes to the given kind of resource.  The receiver starts out
+    /// marked as changed, so that the first check copies whatever is already held
+    pub fn subscribe(&self, kind: ResourceKind) -> watch::Receiver<()> {
+        let mut rx = self.changes[kind as usize].subscribe();
+        rx.mark_changed();
+        rx
+    }
+
+    pub fn containers(&self) -> Vec<DockerContainer> {
+        self.state.lock().unwrap().containers.clone()
+    }
+
+    /// Every image, including dangling images
+    pub fn images(&self) -> Vec<DockerImage> {
+        self.state.lock().unwrap().images.clone()
+    }
+
+    pub fn volumes(&self) -> Vec<DockerVolume> {
+        self.state.lock().unwrap().volumes.clone()
+    }
+
+    pub fn networks(&self) -> Vec<DockerNetwork> {
+        self.state.lock().unwrap().networks.clone()
+    }
+
+    /// Counts each kind of resource, or `None` before the first sync has completed
+    pub fn counts(&self) -> Option<ResourceCounts> {
+        let state = self.state.lock().unwrap();
+        state.synced.then(|| ResourceCounts {
+            containers: state.containers.len(),
+            running: state.containers.iter().filter(|c| c.running).count(),
+            images: state.images.iter().filter(|i| !i.is_dangling()).count(),
+            volumes: state.volumes.len(),
+            networks: state.networks.len(),
+        })
+    }
+
+    /// Asks for the given container details to be included from now on, resyncing
+    /// if they weren't already
+    pub fn request_details(&self, details: ContainerDetails) {
+        let mut state = self.state.lock().unwrap();
+        if !state.details.covers(&details) {
+            state.details = state.details.union(&details);
+            self.resync.notify_one();
+        }
+    }
+
+    /// Refetches everything, without waiting for the next periodic resync
+    pub fn resync(&self) {
+        self.resync.notify_one();
+    }
+
+    async fn run(self, docker: Docker, tx: Sender<Message<Key, Transition>>) {
+        loop {
+            let mut events = docker.events(Some(EventsOptions::<String> {
+                filters: HashMap::from([(
+                    "type".to_string(),
+                    ResourceKind::ALL
+                        .iter()
+                        .map(|k| k.event_type().to_string())
+                        .collect(),
+                )]),
+                ..Default::default()
+            }));
+            self.sync(&docker, &tx).await;
+
+            let mut resync = interval(RESYNC_INTERVAL);
+            resync.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            resync.reset();
+            let mut stale = HashSet::new();
+            let mut stale_at: Option<Instant> = None;
+
+            loop {
+                tokio::select! {
+                    event = events.next() => match event {
+                        Some(Ok(event)) => {
+                            if let Some(kind) = self.apply(&docker, event).await {
+                                stale.insert(kind);
+                                stale_at = Some(Instant::now() + REFETCH_DEBOUNCE);
+                            }
+                        }
+                        Some(Err(e)) => {
+                            tracing::warn!(error = %e, "docker event stream failed");
+                            break;
+                        }
+                        None => {
+                            tracing::warn!("docker event stream ended");
+                            break;
+                        }
+                    },
+                    _ = sleep_until(stale_at.unwrap_or_else(Instant::now)), if stale_at.is_some() => {
+                        stale_at = None;
+                        for kind in stale.drain() {
+                            if let Err(e) = self.sync_kind(&docker, kind).await {
+                                tracing::warn!(error = %e, ?kind, "unable to refetch resources");
+                            }
+                        }
+                    }
+                    _ = resync.tick() => self.sync(&docker, &tx).await,
+                    _ = self.resync.notified() => self.sync(&docker, &tx).await,
+                }
+            }
+
+            sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    /// Refetches everything, notifying the user if it fails after having succeeded
+    async fn sync(&self, docker: &Docker, tx: &Sender<Message<Key, Transition>>) {
+        let result = tokio::try_join!(
+            self.sync_kind(docker, ResourceKind::Containers),
+            self.sync_kind(docker, ResourceKind::Images),
+            self.sync_kind(docker, ResourceKind::Volumes),
+            self.sync_kind(docker, ResourceKind::Networks),
+        );
+
+        let failed = {
+            let mut state = self.state.lock().unwrap();
+            let was_failing = state.error.is_some();
+            state.error = result.as_ref().err().map(|e| format!("{e}"));
+            state.synced |= result.is_ok();
+            !was_failing && state.error.is_some()
+        };
+        if let (true, Err(e)) = (failed, result) {
+            let _ = tx
+                .send(Message::Error(format!("unable to refresh resources: {e}")))
+                .await;
+        }
+    }
+
+    async fn sync_kind(&self, docker: &Docker, kind: ResourceKind) -> Result<()> {
+        match kind {
+            ResourceKind::Containers => {
+                let (details, restart_counts) = {
+                    let state = self.state.lock().unwrap();
+                    let restart_counts: HashMap<String, i64> = state
+                        .containers
+                        .iter()
+                        .filter_map(|c| Some((c.id.clone(), c.restart_count?)))
+                        .collect();
+                    (state.details, restart_counts)
+                };
+                let mut containers = DockerContainer::list(docker, details).await?;
+                if details.restart_count {
+                    // Each restart count needs its own inspect, so they are kept from the
+                    // last sync and only fetched for containers which are new to the
+                    // store; container events refetch them from then on
+                    let mut unknown = vec![];
+                    for container in &mut containers {
+                        match restart_counts.get(&container.id) {
+                            Some(count) => container.restart_count = Some(*count),
+                            None => unknown.push(container),
+                        }
+                    }
+                    join_all(unknown.into_iter().map(|c| c.fetch_restart_count(docker))).await;
+                }
+                self.replace(kind, |s| &mut s.containers, containers);
+            }
+            ResourceKind::Images => {
+                let images = DockerImage::list(docker, true).await?;
+                self.replace(kind, |s| &mut s.images, images);
+            }
+            ResourceKind::Volumes => {
+                let volumes = DockerVolume::list(docker).await?;
+                self.replace(kind, |s| &mut s.volumes, volumes);
+            }
+            ResourceKind::Networks => {
+                let networks = DockerNetwork::list(docker).await?;
+                self.replace(kind, |s| &mut s.networks, networks);
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the resources of one kind, notifying subscribers if they differ
+    fn replace<T: PartialEq>(
+        &self,
+        kind: ResourceKind,
+        field: impl FnOnce(&mut StoreState) -> &mut Vec<T>,
+        resources: Vec<T>,
+    ) {
+        let changed = {
+            let mut state = self.state.lock().unwrap();
+            let current = field(&mut state);
+            let changed = *current != resources;
+            *current = resources;
+            changed
+        };
+        if changed {
+            self.changed(kind);
+        }
+    }
+
+    fn changed(&self, kind: ResourceKind) {
+        self.changes[kind as usize].send_replace(());
+    }
+
+    /// Patches the store with the outcome of an event, returning the kind which needs
+    /// refetching, if any
+    #[instrument(level = "debug", skip_all, fields(typ = ?event.typ, action = ?event.action))]
+    async fn apply(&self, docker: &Docker, event: EventMessage) -> Option<ResourceKind> {
+        let action = event.action.unwrap_or_default();
+        let id = event.actor.and_then(|a| a.id).unwrap_or_default();
+
+        match event.typ {
+            Some(EventMessageTypeEnum::IMAGE) => Some(ResourceKind::Images),
+            // Mounting a volume or connecting a container to a network doesn't change
+            // anything which is held about the volume or network
+            Some(EventMessageTypeEnum::VOLUME) if !action.contains("mount") => {
+                Some(ResourceKind::Volumes)
+            }
+            Some(EventMessageTypeEnum::NETWORK) if !action.contains("connect") => {
+                Some(ResourceKind::Networks)
+            }
+            // Running a command in a container doesn't change the container itself
+            Some(EventMessageTypeEnum::CONTAINER) if !action.starts_with("exec_") => {
+                let container = if action == "destroy" {
+                    Ok(None)
+                } else {
+                    let details = self.state.lock().unwrap().details;
+                    DockerContainer::get(docker, &id, details).await
+                };
+                match container {
+                    Ok(container) => self.patch_container(&id, container),
+                    Err(e) => {
+                        tracing::warn!(error = %e, id, "unable to refetch container");
+                        self.resync();
+                    }
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn patch_container(&self, id: &str, container: Option<DockerContainer>) {
+        {
+            let mut state = self.state.lock().unwrap();
+            let existing = state.containers.iter().position(|c| c.id == id);
+            match (existing, container) {
+                (Some(idx), Some(container)) if state.containers[idx] != container => {
+                    state.containers[idx] = container;
+                }
+                (Some(idx), None) => {
+                    state.containers.remove(idx);
+                }
+                (None, Some(container)) => state.containers.push(container),
+                _ => return,
+            }
+        }
+        self.changed(ResourceKind::Containers);
+    }
+}
@@ -1,5 +1,6 @@
 use std::fmt;
 
+use async_trait::async_trait;
 use color_eyre::eyre::Result;
 use dyn_clone::DynClone;
 
@@ -7,13 +8,15 @@ use dyn_clone::DynClone;
 /// struct in a human readable format.
 /// Provides a generic minimal description interface over a selection of
 /// docker resources
+#[async_trait]
 pub trait Describe: fmt::Debug + Send + Sync + DynClone {
     /// Get the ID of the resource being described
     fn get_id(&self) -> String;
     /// Get a human readable name of the resource being described
     fn get_name(&self) -> String;
-    /// Get a human readable description of the resource being described
-    fn describe(&self) -> Result<Vec<String>>;
+    /// Inspect the resource on the docker daemon, returning the full
+    /// structured description as given by the docker API
+    async fn describe(&self, docker: &bollard::Docker) -> Result<serde_json::Value>;
 }
 
 dyn_clone::clone_trait_object!(Describe);
@@ -1,14 +1,167 @@
+use std::time::{Duration, UNIX_EPOCH};
+
 use bollard::{Docker, API_DEFAULT_VERSION};
-use color_eyre::eyre::{Context, Result};
+use byte_unit::{Byte, UnitType};
+use chrono::prelude::DateTime;
+use chrono::{Local, NaiveDate};
+use color_eyre::eyre::{bail, eyre, Context, Result};
 
-use super::container::DockerContainer;
+use super::container::{ContainerDetails, DockerContainer};
 
 pub async fn new_local_docker_connection(socket_path: &str) -> Result<Docker> {
     let docker = bollard::Docker::connect_with_socket(socket_path, 120, API_DEFAULT_VERSION)
         .with_context(|| "unable to connect to local docker socket")?;
 
-    DockerContainer::list(&docker)
+    DockerContainer::list(&docker, ContainerDetails::default())
         .await
         .context("unable to connect to local docker socket")?;
     Ok(docker)
 }
+
+/// The `DOCKER_HOST` which points the docker CLI at the daemon at `docker_path`,
+/// which may be given as a bare socket path
+pub fn docker_host(docker_path: &str) -> String {
+    if docker_path.contains("://") {
+        docker_path.to_string()
+    } else if cfg!(windows) {
+        format!("npipe://{docker_path}")
+    } else {
+        format!("unix://{docker_path}")
+    }
+}
+
+/// Formats a unix timestamp (in seconds) as a local date time
+pub fn format_timestamp(timestamp: i64) -> String {
+    DateTime::<Local>::from(
+        UNIX_EPOCH + Duration::from_secs(timestamp.try_into().unwrap_or_default()),
+    )
+    .format("%Y-%m-%d %H:%M:%S")
+    .to_string()
+}
+
+/// Formats a size in bytes in the most appropriate binary unit, eg `1.21 GiB`
+pub fn format_size(size: i64) -> String {
+    let b =
+        Byte::from_u64(size.try_into().unwrap_or_default()).get_appropriate_unit(UnitType::Binary);
+    format!("{b:.2}")
+}
+
+/// Parses a point in time into a unix timestamp.  As with the docker CLI, this can be
+/// a duration before now (eg `24h` or `1h30m`), a unix timestamp or a date
+pub fn parse_time(value: &str) -> Result<i64> {
+    if let Ok(timestamp) = value.parse::<i64>() {
+        return Ok(timestamp);
+    }
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date.timestamp());
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        if let Some(date) = date
+            .and_hms_opt(0, 0, 0)
+            .and_then(|d| d.and_local_timezone(Local).single())
+        {
+            return Ok(date.timestamp());
+        }
+    }
+
+    let mut seconds = 0;
+    let mut digits = String::new();
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 60 * 60 * 24,
+            _ => bail!(
+                "invalid time \"{value}\", expected a duration such as 24h, a timestamp or a date"
+            ),
+        };
+        let n = digits
+            .parse::<i64>()
+            .map_err(|_| eyre!("invalid duration \"{value}\""))?;
+        seconds += n * unit;
+        digits.clear();
+    }
+    if !digits.is_empty() || seconds == 0 {
+        bail!("invalid duration \"{value}\", each number requires a unit of s, m, h or d");
+    }
+    Ok(Local::now().timestamp() - seconds)
+}
+
+/// Finds the single resource matching the given name, id or id prefix, in that
+/// order of precedence.  An id prefix matching several resources is an error
+pub fn find_by_name_or_id<T>(
+    resources: Vec<T>,
+    name_or_id: &str,
+    name_matches: impl Fn(&T) -> bool,
+    id: impl Fn(&T) -> &str,
+) -> Result<T> {
+    if name_or_id.is_empty() {
+        bail!("no name or id given");
+    }
+    let mut by_prefix = vec![];
+    for resource in resources {
+        if name_matches(&resource) || id(&resource) == name_or_id {
+            return Ok(resource);
+        }
+        if id(&resource).starts_with(name_or_id) {
+            by_prefix.push(resource);
+        }
+    }
+    // Images are listed once per tag, so several entries may share the same id
+    by_prefix.dedup_by(|a, b| id(a) == id(b));
+    match by_prefix.len() {
+        0 => bail!("no match found"),
+        1 => Ok(by_prefix.remove(0)),
+        n => bail!("id prefix \"{name_or_id}\" is ambiguous, matching {n} resources"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn docker_host_keeps_a_scheme() {
+        assert_eq!(
+            docker_host("unix:///var/run/docker.sock"),
+            "unix:///var/run/docker.sock"
+        );
+        assert_eq!(docker_host("tcp://10.0.0.1:2375"), "tcp://10.0.0.1:2375");
+    }
+
+    #[test]
+    fn parse_time_accepts_timestamps_and_dates() {
+        assert_eq!(parse_time("1700000000").unwrap(), 1_700_000_000);
+        assert_eq!(parse_time("2024-01-01T00:00:00Z").unwrap(), 1_704_067_200);
+        assert!(parse_time("2024-01-01").is_ok());
+    }
+
+    #[test]
+    fn parse_time_counts_durations_back_from_now() {
+        let expected = Local::now().timestamp() - (60 * 60 + 30 * 60);
+        let parsed = parse_time("1h30m").unwrap();
+        assert!((expected - parsed).abs() <= 1);
+    }
+
+    #[test]
+    fn parse_time_rejects_durations_without_units() {
+        assert!(parse_time("24x").is_err());
+        assert!(parse_time("1h30").is_err());
+        assert!(parse_time("h").is_err());
+        assert!(parse_time("0s").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn docker_host_adds_a_scheme_to_a_socket_path() {
+        assert_eq!(
+            docker_host("/run/user/1000/docker.sock"),
+            "unix:///run/user/1000/docker.sock"
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use bollard::volume::ListVolumesOptions;
+use color_eyre::eyre::{Context, Result};
+use serde::Serialize;
+use tracing::instrument;
+
+use super::{traits::Describe, util::find_by_name_or_id};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DockerVolume {
+    pub name: String,
+    pub driver: String,
+    pub mountpoint: String,
+}
+
+impl DockerVolume {
+    /// Lists all volumes present on a given docker daemon
+    #[instrument(level = "debug", skip(docker), err)]
+    pub async fn list(docker: &bollard::Docker) -> Result<Vec<Self>> {
+        let volumes = docker
+            .list_volumes(None::<ListVolumesOptions<String>>)
+            .await
+            .context("unable to retrieve list of volumes")?
+            .volumes
+            .unwrap_or_default()
+            .into_iter()
+            .map(|v| Self {
+                name: v.name,
+                driver: v.driver,
+                mountpoint: v.mountpoint,
+            })
+            .collect();
+        Ok(volumes)
+    }
+
+    /// Finds a volume by name.  Volumes have no separate id, so anonymous volumes
+    /// can also be found by a unique prefix of their name
+    #[instrument(level = "debug", skip(docker), err(level = "debug"))]
+    pub async fn find(docker: &bollard::Docker, name: &str) -> Result<Self> {
+        let volumes = Self::list(docker).await?;
+        find_by_name_or_id(volumes, name, |v| v.name == name, |v| &v.name)
+            .with_context(|| format!("unable to find volume \"{name}\""))
+    }
+}
+
+#[async_trait]
+impl Describe for DockerVolume {
+    fn get_id(&self) -> String {
+        self.name.clone()
+    }
+    fn get_name(&self) -> String {
+        format!("volume: {}", self.name)
+    }
+    #[instrument(level = "debug", skip_all, fields(name = %self.name), err)]
+    async fn describe(&self, docker: &bollard::Docker) -> Result<serde_json::Value> {
+        let details = docker
+            .inspect_volume(&self.name)
+            .await
+            .context("unable to inspect volume")?;
+        serde_json::to_value(details).context("failed to parse volume details")
+    }
+}
@@ -0,0 +1,79 @@
+use std::{
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use color_eyre::eyre::Result;
+use tokio::{sync::mpsc::Sender, task::JoinHandle, time::interval};
+
+use super::{transition::send_transition, Key, Message, Transition};
+use crate::config::{self, Overrides};
+
+const POLL_RATE: Duration = Duration::from_secs(1);
+
+/// When each watched file was last modified; a file which is missing has no time
+type Snapshot = Vec<(PathBuf, Option<SystemTime>)>;
+
+/// Watches the config files and the theme files for changes, sending a
+/// `Transition::ReloadConfig` whenever any of them is modified, created or removed.
+///
+/// The files are polled rather than relying on platform notifications, as editors
+/// save files in many different ways (eg writing a temporary file and renaming it)
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    config_files: Vec<PathBuf>,
+    themes: PathBuf,
+}
+
+impl ConfigWatcher {
+    pub fn new(overrides: &Overrides) -> Result<Self> {
+        Ok(Self {
+            config_files: overrides.config_files()?,
+            themes: config::themes_path()?,
+        })
+    }
+
+    pub fn start(self, tx: Sender<Message<Key, Transition>>) -> JoinHandle<()> {
+        let mut interval = interval(POLL_RATE);
+        tokio::spawn(async move {
+            let mut last = self.snapshot();
+            loop {
+                tokio::select! {
+                    _ = tx.closed() => {
+                        break;
+                    }
+                    _ = interval.tick() => {
+                        let snapshot = self.snapshot();
+                        if snapshot != last {
+                            last = snapshot;
+                            if send_transition(tx.clone(), Transition::ReloadConfig).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        let mut files = self.config_files.clone();
+        if let Ok(entries) = fs::read_dir(&self.themes) {
+            files.extend(
+                entries
+                    .filter_map(|e| e.ok().map(|e| e.path()))
+                    .filter(|p| p.extension().is_some_and(|ext| ext == "yaml")),
+            );
+        }
+        files.sort();
+
+        files
+            .into_iter()
+            .map(|path| {
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                (path, modified)
+            })
+            .collect()
+    }
+}
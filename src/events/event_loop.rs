@@ -14,6 +14,7 @@ use tokio::{
 
 use super::key::Key;
 use super::Message;
+use super::MouseTracker;
 use super::Transition;
 
 const TICK_RATE: Duration = Duration::from_millis(250);
@@ -93,6 +94,7 @@ impl EventLoop {
         tokio::spawn(async move {
             let mut reader = crossterm::event::EventStream::new();
             let mut tick = interval(TICK_RATE);
+            let mut mouse = MouseTracker::default();
             loop {
                 let delay = tick.tick();
                 let crossterm_event = reader.next().fuse();
@@ -102,10 +104,20 @@ impl EventLoop {
                     }
                     _ = delay => {}
                     Some(Ok(event)) = crossterm_event => {
-                        if let CrossTermEvent::Key(key) = event {
-                            let key = Key::from(key);
-                            tx.send(Message::Input(key)).await.unwrap();
-                        }
+                        let message = match event {
+                            CrossTermEvent::Key(key) => {
+                                let key = Key::from(key);
+                                tracing::trace!(?key, "key pressed");
+                                Message::Input(key)
+                            }
+                            CrossTermEvent::Mouse(event) => match mouse.track(event) {
+                                Some(mouse) => Message::Mouse(mouse),
+                                None => continue,
+                            },
+                            CrossTermEvent::Resize(..) => Message::Resize,
+                            _ => continue,
+                        };
+                        tx.send(message).await.unwrap();
                     }
                 }
             }
@@ -1,18 +1,16 @@
 use crossterm::event;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[cfg(test)]
-use serde::Serialize;
-
 /// Represents a key.
-#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Deserialize)]
-#[cfg_attr(test, derive(Serialize))]
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Serialize, Deserialize)]
 pub enum Key {
     /// Both Enter (or Return) and numpad Enter
     Enter,
     /// Tabulation key
     Tab,
+    /// Shift + Tab
+    BackTab,
     /// Backspace key
     Backspace,
     /// Escape key
@@ -114,6 +112,7 @@ impl fmt::Display for Key {
             Key::Right => write!(f, "\u{2192}"), //→
             Key::Up => write!(f, "\u{2191}"),    //↑
             Key::Down => write!(f, "\u{2193}"),  //↓
+            Key::BackTab => write!(f, "<Shift+Tab>"),
             Key::Enter
             | Key::Tab
             | Key::Backspace
@@ -192,6 +191,10 @@ impl From<event::KeyEvent> for Key {
                 code: event::KeyCode::Tab,
                 ..
             } => Key::Tab,
+            event::KeyEvent {
+                code: event::KeyCode::BackTab,
+                ..
+            } => Key::BackTab,
 
             // First check for char + modifier
             event::KeyEvent {
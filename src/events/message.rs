@@ -1,9 +1,17 @@
-#[derive(Clone)]
+use super::{Mouse, Notification};
+
+#[derive(Debug, Clone)]
 pub enum Message<I, T> {
     Tick,
     Input(I),
+    /// A click or scroll, only sent when mouse support is turned on
+    Mouse(Mouse),
+    /// The terminal has been resized, so should be drawn again straight away
+    Resize,
     Transition(T),
+    /// Something went wrong in the background; it is shown as an error notification
     Error(String),
+    Notify(Notification),
 }
 
 #[derive(PartialEq, Debug, Clone)]
@@ -1,9 +1,15 @@
+pub mod config_watcher;
 pub mod event_loop;
 pub mod key;
 pub mod message;
+pub mod mouse;
+pub mod notification;
 pub mod transition;
 
+pub use config_watcher::ConfigWatcher;
 pub use event_loop::EventLoop;
 pub use key::Key;
 pub use message::Message;
+pub use mouse::{Mouse, MouseKind, MouseTracker};
+pub use notification::{send_notification, Notification, Severity};
 pub use transition::Transition;
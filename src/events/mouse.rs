@@ -0,0 +1,77 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+
+/// The longest gap between two clicks on the same cell for them to count as a
+/// double-click
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseKind {
+    Click,
+    DoubleClick,
+    ScrollUp,
+    ScrollDown,
+}
+
+/// Something done with the mouse, and the cell of the terminal it was done on.
+/// Only the events which ducker acts on are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mouse {
+    pub kind: MouseKind,
+    pub column: u16,
+    pub row: u16,
+}
+
+impl Mouse {
+    pub fn is_in(&self, area: Rect) -> bool {
+        area.contains(Position::new(self.column, self.row))
+    }
+
+    /// The index of the row under the mouse in a table drawn in `area`, below a
+    /// header `header` lines high and scrolled down by `offset` rows
+    pub fn row_in(&self, area: Rect, header: u16, offset: usize) -> Option<usize> {
+        if !self.is_in(area) || self.row < area.y + header {
+            return None;
+        }
+        Some((self.row - area.y - header) as usize + offset)
+    }
+}
+
+/// Turns crossterm's mouse events into `Mouse` events, recognising a second click
+/// on the same cell soon after the first as a double-click
+#[derive(Debug, Default)]
+pub struct MouseTracker {
+    last_click: Option<(Instant, u16, u16)>,
+}
+
+impl MouseTracker {
+    pub fn track(&mut self, event: MouseEvent) -> Option<Mouse> {
+        let kind = match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let now = Instant::now();
+                let double = self.last_click.is_some_and(|(at, column, row)| {
+                    column == event.column
+                        && row == event.row
+                        && now.duration_since(at) <= DOUBLE_CLICK_INTERVAL
+                });
+                // A third click starts again, rather than being another double-click
+                self.last_click = (!double).then_some((now, event.column, event.row));
+                if double {
+                    MouseKind::DoubleClick
+                } else {
+                    MouseKind::Click
+                }
+            }
+            MouseEventKind::ScrollUp => MouseKind::ScrollUp,
+            MouseEventKind::ScrollDown => MouseKind::ScrollDown,
+            _ => return None,
+        };
+        Some(Mouse {
+            kind,
+            column: event.column,
+            row: event.row,
+        })
+    }
+}
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use color_eyre::eyre::{Context, Result};
+use tokio::sync::mpsc::Sender;
+
+use super::{Key, Message, Transition};
+
+/// How important a notification is, which decides how it is styled and how long
+/// it is shown for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Info => "Info",
+            Self::Success => "Success",
+            Self::Warning => "Warning",
+            Self::Error => "Error",
+        }
+    }
+
+    /// How long a notification is shown before it is dismissed; the more severe
+    /// it is the longer it stays, so that there is time to read what went wrong
+    pub fn timeout(self) -> Duration {
+        match self {
+            Self::Info | Self::Success => Duration::from_secs(3),
+            Self::Warning => Duration::from_secs(5),
+            Self::Error => Duration::from_secs(8),
+        }
+    }
+}
+
+/// A short message shown to the user without interrupting what they are doing,
+/// such as the outcome of an action which ran in the background
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Notification {
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+        }
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(Severity::Info, message)
+    }
+
+    pub fn success(message: impl Into<String>) -> Self {
+        Self::new(Severity::Success, message)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+}
+
+pub async fn send_notification(
+    tx: Sender<Message<Key, Transition>>,
+    notification: Notification,
+) -> Result<()> {
+    tx.send(Message::Notify(notification))
+        .await
+        .context("unable to send notification")?;
+    Ok(())
+}
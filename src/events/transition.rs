@@ -1,6 +1,7 @@
 use color_eyre::eyre::{Context, Result};
 use tokio::sync::mpsc::Sender;
 
+use crate::commands::Command;
 use crate::context::AppContext;
 use crate::events::{Key, Message};
 
@@ -18,6 +19,25 @@ pub enum Transition {
     ToLogPage(AppContext),
     ToDescribeContainerPage(AppContext),
     ToAttach(AppContext),
+    ToPrunePage(AppContext),
+    ToActivityPage(AppContext),
+    /// Returns to the previous page in the navigation history
+    Back,
+    /// Goes forward again after going back in the navigation history
+    Forward,
+    /// Shows the tab after the current one
+    NextTab,
+    /// Shows the tab before the current one
+    PreviousTab,
+    /// Closes the current tab, unless it is the only one
+    CloseTab,
+    /// Runs a command entered at the prompt which acts on a specific resource or
+    /// on the current page
+    RunCommand(Command),
+    /// The config file or a theme file has changed on disk
+    ReloadConfig,
+    /// Shows or hides the debug overlay
+    ToggleDebug,
 }
 
 pub async fn send_transition(
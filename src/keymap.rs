@@ -0,0 +1,793 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+
+use color_eyre::eyre::{bail, eyre, Error, Result};
+use itertools::Itertools;
+use serde::{
+    de::{self, value::EnumAccessDeserializer, EnumAccess, IntoDeserializer, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::events::Key;
+
+/// The set of keys which an action belongs to.  A key may only be bound to one
+/// action amongst all of the scopes which are active on a page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// Handled by the application when the page does not consume the key
+    Global,
+    /// Moving around a list, shared by most pages
+    Navigation,
+    /// Filtering, sorting and marking rows of a resource table
+    Table,
+    /// The preview of the selected row of a resource table
+    Preview,
+    Containers,
+    Images,
+    Logs,
+    Describe,
+    Prune,
+    Attach,
+    Activity,
+}
+
+impl Scope {
+    const PAGES: [Scope; 7] = [
+        Self::Containers,
+        Self::Images,
+        Self::Logs,
+        Self::Describe,
+        Self::Prune,
+        Self::Attach,
+        Self::Activity,
+    ];
+
+    /// The scopes searched, in order, when looking up a key in this scope
+    fn chain(self) -> &'static [Scope] {
+        match self {
+            Self::Containers => &[
+                Self::Containers,
+                Self::Table,
+                Self::Preview,
+                Self::Navigation,
+            ],
+            Self::Images => &[Self::Images, Self::Table, Self::Preview, Self::Navigation],
+            Self::Logs => &[Self::Logs, Self::Navigation],
+            Self::Describe => &[Self::Describe, Self::Navigation],
+            Self::Prune => &[Self::Prune, Self::Navigation],
+            Self::Attach => &[Self::Attach],
+            Self::Activity => &[Self::Activity, Self::Navigation],
+            Self::Global => &[Self::Global],
+            Self::Navigation => &[Self::Navigation],
+            Self::Table => &[Self::Table],
+            Self::Preview => &[Self::Preview],
+        }
+    }
+}
+
+/// Something which can be triggered by a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Action {
+    Quit,
+    Command,
+    Back,
+    Forward,
+    TabNew,
+    TabNext,
+    TabPrevious,
+    TabClose,
+
+    Up,
+    Down,
+    Top,
+    Bottom,
+
+    Filter,
+    Sort,
+    ReverseSort,
+    Mark,
+    MarkDown,
+    MarkUp,
+    MarkAll,
+    Clear,
+
+    PreviewToggle,
+    PreviewCycle,
+
+    ContainerExec,
+    ContainerDelete,
+    ContainerStart,
+    ContainerStop,
+    ContainerRestart,
+    ContainerLogs,
+    ContainerDescribe,
+
+    ImageDelete,
+    ImagePull,
+    ImageToggleDangling,
+    ImagePrune,
+    ImageDescribe,
+
+    LogsToggleAutoscroll,
+    LogsBack,
+
+    DescribeFold,
+    DescribeExpand,
+    DescribeCollapse,
+    DescribeExpandAll,
+    DescribeCollapseAll,
+    DescribeCycleView,
+    DescribeCopyPath,
+    DescribeSearch,
+    DescribeNextMatch,
+    DescribePreviousMatch,
+    DescribeBack,
+
+    PruneNextKind,
+    PrunePreviousKind,
+    PruneToggleAll,
+    PruneFilters,
+    PruneConfirm,
+    PruneBack,
+
+    AttachBack,
+
+    ActivityDetails,
+    ActivityBack,
+}
+
+impl Action {
+    pub const ALL: [Action; 56] = [
+        Self::Quit,
+        Self::Command,
+        Self::Back,
+        Self::Forward,
+        Self::TabNew,
+        Self::TabNext,
+        Self::TabPrevious,
+        Self::TabClose,
+        Self::Up,
+        Self::Down,
+        Self::Top,
+        Self::Bottom,
+        Self::Filter,
+        Self::Sort,
+        Self::ReverseSort,
+        Self::Mark,
+        Self::MarkDown,
+        Self::MarkUp,
+        Self::MarkAll,
+        Self::Clear,
+        Self::PreviewToggle,
+        Self::PreviewCycle,
+        Self::ContainerExec,
+        Self::ContainerDelete,
+        Self::ContainerStart,
+        Self::ContainerStop,
+        Self::ContainerRestart,
+        Self::ContainerLogs,
+        Self::ContainerDescribe,
+        Self::ImageDelete,
+        Self::ImagePull,
+        Self::ImageToggleDangling,
+        Self::ImagePrune,
+        Self::ImageDescribe,
+        Self::LogsToggleAutoscroll,
+        Self::LogsBack,
+        Self::DescribeFold,
+        Self::DescribeExpand,
+        Self::DescribeCollapse,
+        Self::DescribeExpandAll,
+        Self::DescribeCollapseAll,
+        Self::DescribeCycleView,
+        Self::DescribeCopyPath,
+        Self::DescribeSearch,
+        Self::DescribeNextMatch,
+        Self::DescribePreviousMatch,
+        Self::DescribeBack,
+        Self::PruneNextKind,
+        Self::PrunePreviousKind,
+        Self::PruneToggleAll,
+        Self::PruneFilters,
+        Self::PruneConfirm,
+        Self::PruneBack,
+        Self::AttachBack,
+        Self::ActivityDetails,
+        Self::ActivityBack,
+    ];
+
+    /// The name used to refer to the action in config, eg `containers.exec`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Quit => "global.quit",
+            Self::Command => "global.command",
+            Self::Back => "global.back",
+            Self::Forward => "global.forward",
+            Self::TabNew => "global.tab_new",
+            Self::TabNext => "global.tab_next",
+            Self::TabPrevious => "global.tab_previous",
+            Self::TabClose => "global.tab_close",
+            Self::Up => "navigation.up",
+            Self::Down => "navigation.down",
+            Self::Top => "navigation.top",
+            Self::Bottom => "navigation.bottom",
+            Self::Filter => "table.filter",
+            Self::Sort => "table.sort",
+            Self::ReverseSort => "table.reverse_sort",
+            Self::Mark => "table.mark",
+            Self::MarkDown => "table.mark_down",
+            Self::MarkUp => "table.mark_up",
+            Self::MarkAll => "table.mark_all",
+            Self::Clear => "table.clear",
+            Self::PreviewToggle => "preview.toggle",
+            Self::PreviewCycle => "preview.cycle",
+            Self::ContainerExec => "containers.exec",
+            Self::ContainerDelete => "containers.delete",
+            Self::ContainerStart => "containers.start",
+            Self::ContainerStop => "containers.stop",
+            Self::ContainerRestart => "containers.restart",
+            Self::ContainerLogs => "containers.logs",
+            Self::ContainerDescribe => "containers.describe",
+            Self::ImageDelete => "images.delete",
+            Self::ImagePull => "images.pull",
+            Self::ImageToggleDangling => "images.toggle_dangling",
+            Self::ImagePrune => "images.prune",
+            Self::ImageDescribe => "images.describe",
+            Self::LogsToggleAutoscroll => "logs.toggle_autoscroll",
+            Self::LogsBack => "logs.back",
+            Self::DescribeFold => "describe.fold",
+            Self::DescribeExpand => "describe.expand",
+            Self::DescribeCollapse => "describe.collapse",
+            Self::DescribeExpandAll => "describe.expand_all",
+            Self::DescribeCollapseAll => "describe.collapse_all",
+            Self::DescribeCycleView => "describe.cycle_view",
+            Self::DescribeCopyPath => "describe.copy_path",
+            Self::DescribeSearch => "describe.search",
+            Self::DescribeNextMatch => "describe.next_match",
+            Self::DescribePreviousMatch => "describe.previous_match",
+            Self::DescribeBack => "describe.back",
+            Self::PruneNextKind => "prune.next_kind",
+            Self::PrunePreviousKind => "prune.previous_kind",
+            Self::PruneToggleAll => "prune.toggle_all",
+            Self::PruneFilters => "prune.filters",
+            Self::PruneConfirm => "prune.confirm",
+            Self::PruneBack => "prune.back",
+            Self::AttachBack => "attach.back",
+            Self::ActivityDetails => "activity.details",
+            Self::ActivityBack => "activity.back",
+        }
+    }
+
+    pub fn scope(self) -> Scope {
+        match self {
+            Self::Quit
+            | Self::Command
+            | Self::Back
+            | Self::Forward
+            | Self::TabNew
+            | Self::TabNext
+            | Self::TabPrevious
+            | Self::TabClose => Scope::Global,
+            Self::Up | Self::Down | Self::Top | Self::Bottom => Scope::Navigation,
+            Self::Filter
+            | Self::Sort
+            | Self::ReverseSort
+            | Self::Mark
+            | Self::MarkDown
+            | Self::MarkUp
+            | Self::MarkAll
+            | Self::Clear => Scope::Table,
+            Self::PreviewToggle | Self::PreviewCycle => Scope::Preview,
+            Self::ContainerExec
+            | Self::ContainerDelete
+            | Self::ContainerStart
+            | Self::ContainerStop
+            | Self::ContainerRestart
+            | Self::ContainerLogs
+            | Self::ContainerDescribe => Scope::Containers,
+            Self::ImageDelete
+            | Self::ImagePull
+            | Self::ImageToggleDangling
+            | Self::ImagePrune
+            | Self::ImageDescribe => Scope::Images,
+            Self::LogsToggleAutoscroll | Self::LogsBack => Scope::Logs,
+            Self::DescribeFold
+            | Self::DescribeExpand
+            | Self::DescribeCollapse
+            | Self::DescribeExpandAll
+            | Self::DescribeCollapseAll
+            | Self::DescribeCycleView
+            | Self::DescribeCopyPath
+            | Self::DescribeSearch
+            | Self::DescribeNextMatch
+            | Self::DescribePreviousMatch
+            | Self::DescribeBack => Scope::Describe,
+            Self::PruneNextKind
+            | Self::PrunePreviousKind
+            | Self::PruneToggleAll
+            | Self::PruneFilters
+            | Self::PruneConfirm
+            | Self::PruneBack => Scope::Prune,
+            Self::AttachBack => Scope::Attach,
+            Self::ActivityDetails | Self::ActivityBack => Scope::Activity,
+        }
+    }
+
+    fn default_keys(self) -> Vec<Key> {
+        match self {
+            Self::Quit => vec![Key::Char('q'), Key::Char('Q')],
+            Self::Command => vec![Key::Char(':')],
+            Self::Back => vec![Key::Char('[')],
+            Self::Forward => vec![Key::Char(']')],
+            Self::TabNew => vec![Key::Ctrl('t')],
+            Self::TabNext => vec![Key::Char('>')],
+            Self::TabPrevious => vec![Key::Char('<')],
+            Self::TabClose => vec![Key::Ctrl('w')],
+            Self::Up => vec![Key::Char('k'), Key::Up],
+            Self::Down => vec![Key::Char('j'), Key::Down],
+            Self::Top => vec![Key::Char('g')],
+            Self::Bottom => vec![Key::Char('G')],
+            Self::Filter => vec![Key::Char('/')],
+            Self::Sort => vec![Key::Char('o')],
+            Self::ReverseSort => vec![Key::Char('O')],
+            Self::Mark => vec![Key::Char(' ')],
+            Self::MarkDown => vec![Key::Char('J')],
+            Self::MarkUp => vec![Key::Char('K')],
+            Self::MarkAll => vec![Key::Ctrl('a')],
+            Self::Clear => vec![Key::Esc],
+            Self::PreviewToggle => vec![Key::Char('v')],
+            Self::PreviewCycle => vec![Key::Tab],
+            Self::ContainerExec => vec![Key::Char('a')],
+            Self::ContainerDelete => vec![Key::Ctrl('d')],
+            Self::ContainerStart => vec![Key::Char('r')],
+            Self::ContainerStop => vec![Key::Char('s')],
+            Self::ContainerRestart => vec![Key::Char('R')],
+            Self::ContainerLogs => vec![Key::Char('l')],
+            Self::ContainerDescribe => vec![Key::Char('d')],
+            Self::ImageDelete => vec![Key::Ctrl('d')],
+            Self::ImagePull => vec![Key::Char('p')],
+            Self::ImageToggleDangling => vec![Key::Char('D')],
+            Self::ImagePrune => vec![Key::Char('P')],
+            Self::ImageDescribe => vec![Key::Char('d')],
+            Self::LogsToggleAutoscroll => vec![Key::Char(' ')],
+            Self::LogsBack => vec![Key::Esc],
+            Self::DescribeFold => vec![Key::Enter, Key::Char(' ')],
+            Self::DescribeExpand => vec![Key::Char('l'), Key::Right],
+            Self::DescribeCollapse => vec![Key::Char('h'), Key::Left],
+            Self::DescribeExpandAll => vec![Key::Char('E')],
+            Self::DescribeCollapseAll => vec![Key::Char('C')],
+            Self::DescribeCycleView => vec![Key::Char('v')],
+            Self::DescribeCopyPath => vec![Key::Char('y')],
+            Self::DescribeSearch => vec![Key::Char('/')],
+            Self::DescribeNextMatch => vec![Key::Char('n')],
+            Self::DescribePreviousMatch => vec![Key::Char('N')],
+            Self::DescribeBack => vec![Key::Esc],
+            Self::PruneNextKind => vec![Key::Char('l'), Key::Tab, Key::Right],
+            Self::PrunePreviousKind => vec![Key::Char('h'), Key::Left],
+            Self::PruneToggleAll => vec![Key::Char('a')],
+            Self::PruneFilters => vec![Key::Char('f')],
+            Self::PruneConfirm => vec![Key::Ctrl('d'), Key::Enter],
+            Self::PruneBack => vec![Key::Esc],
+            Self::AttachBack => vec![Key::Esc],
+            Self::ActivityDetails => vec![Key::Enter],
+            Self::ActivityBack => vec![Key::Esc],
+        }
+    }
+}
+
+impl std::str::FromStr for Action {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|a| a.name() == s)
+            .ok_or_else(|| eyre!("unknown action \"{s}\""))
+    }
+}
+
+/// A key, or a sequence of keys pressed one after another such as `gg`, bound to
+/// an action.  In config a sequence is written as a list of keys
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Binding(Vec<Key>);
+
+impl Binding {
+    pub fn keys(&self) -> &[Key] {
+        &self.0
+    }
+}
+
+impl From<Key> for Binding {
+    fn from(value: Key) -> Self {
+        Self(vec![value])
+    }
+}
+
+impl From<Vec<Key>> for Binding {
+    fn from(value: Vec<Key>) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for key in &self.0 {
+            write!(f, "{key}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Binding {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self.0.as_slice() {
+            [key] => key.serialize(serializer),
+            keys => keys.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Binding {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct BindingVisitor;
+
+        impl<'de> Visitor<'de> for BindingVisitor {
+            type Value = Binding;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a key or a list of keys")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<Binding, E> {
+                Key::deserialize(v.into_deserializer()).map(Binding::from)
+            }
+
+            fn visit_enum<A: EnumAccess<'de>>(
+                self,
+                data: A,
+            ) -> std::result::Result<Binding, A::Error> {
+                Key::deserialize(EnumAccessDeserializer::new(data)).map(Binding::from)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> std::result::Result<Binding, A::Error> {
+                let mut keys = vec![];
+                while let Some(key) = seq.next_element::<Key>()? {
+                    keys.push(key);
+                }
+                Ok(Binding(keys))
+            }
+        }
+
+        deserializer.deserialize_any(BindingVisitor)
+    }
+}
+
+/// What a page is given to handle: either a key as it was pressed, or an action
+/// which has already been resolved, eg from a sequence of keys such as `gg` or
+/// from clicking on a key in the help
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Key(Key),
+    Action(Action),
+}
+
+impl Input {
+    /// The key pressed, for handling text input or a modal; resolved actions have
+    /// no key
+    pub fn key(self) -> Option<Key> {
+        match self {
+            Self::Key(key) => Some(key),
+            Self::Action(_) => None,
+        }
+    }
+}
+
+impl From<Key> for Input {
+    fn from(value: Key) -> Self {
+        Self::Key(value)
+    }
+}
+
+impl From<Action> for Input {
+    fn from(value: Action) -> Self {
+        Self::Action(value)
+    }
+}
+
+/// The keys bound to each action; any action not given in config keeps its
+/// default keys
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    try_from = "BTreeMap<String, Vec<Binding>>",
+    into = "BTreeMap<String, Vec<Binding>>"
+)]
+pub struct Keymap {
+    bindings: HashMap<Action, Vec<Binding>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: Action::ALL
+                .into_iter()
+                .map(|a| (a, a.default_keys().into_iter().map(Binding::from).collect()))
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<BTreeMap<String, Vec<Binding>>> for Keymap {
+    type Error = Error;
+
+    fn try_from(value: BTreeMap<String, Vec<Binding>>) -> Result<Self> {
+        let mut keymap = Self::default();
+        for (name, bindings) in value {
+            keymap.bindings.insert(name.parse::<Action>()?, bindings);
+        }
+        Ok(keymap)
+    }
+}
+
+impl From<Keymap> for BTreeMap<String, Vec<Binding>> {
+    fn from(value: Keymap) -> Self {
+        value
+            .bindings
+            .into_iter()
+            .map(|(action, bindings)| (action.name().to_string(), bindings))
+            .collect()
+    }
+}
+
+impl Keymap {
+    /// Ensures that no key, or sequence of keys, is bound to more than one action
+    /// on any page, and that no binding is the start of a longer one bound to a
+    /// different action, as the longer one could never be pressed
+    pub fn validate(&self) -> Result<()> {
+        for (action, bindings) in &self.bindings {
+            for binding in bindings {
+                if binding.keys().is_empty() {
+                    bail!(
+                        "an empty sequence of keys cannot be bound to {}",
+                        action.name()
+                    );
+                }
+                if let Some(key) = binding
+                    .keys()
+                    .iter()
+                    .find(|k| matches!(k, Key::Null | Key::Unknown))
+                {
+                    bail!("{key} cannot be bound to {}", action.name());
+                }
+            }
+        }
+
+        for page in Scope::PAGES {
+            let mut seen: HashMap<&Binding, Action> = HashMap::new();
+            for (action, binding) in self.bindings_for(page) {
+                match seen.get(binding) {
+                    Some(other) if *other != action => bail!(
+                        "{binding} is bound to both {} and {}",
+                        other.name(),
+                        action.name()
+                    ),
+                    _ => seen.insert(binding, action),
+                };
+            }
+
+            for (action, binding) in self.bindings_for(page) {
+                if let Some((other, longer)) = self.bindings_for(page).find(|(other, b)| {
+                    *other != action
+                        && b.keys().len() > binding.keys().len()
+                        && b.keys().starts_with(binding.keys())
+                }) {
+                    bail!(
+                        "{binding} is bound to {}, so {longer} bound to {} can never be pressed",
+                        action.name(),
+                        other.name()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Finds the action bound to the key amongst the scopes active in the given
+    /// scope.  An action which has already been resolved is kept if it belongs to
+    /// one of those scopes
+    pub fn action(&self, scope: Scope, input: Input) -> Option<Action> {
+        let key = match input {
+            Input::Key(key) => key,
+            Input::Action(action) => {
+                return scope.chain().contains(&action.scope()).then_some(action)
+            }
+        };
+        scope.chain().iter().find_map(|scope| {
+            Action::ALL
+                .into_iter()
+                .filter(|a| a.scope() == *scope)
+                .find(|a| self.bindings(*a).iter().any(|b| b.keys() == [key]))
+        })
+    }
+
+    /// Every binding which is active on a page, including the global bindings
+    fn bindings_for(&self, page: Scope) -> impl Iterator<Item = (Action, &Binding)> {
+        let scopes = page.chain().iter().chain(Scope::Global.chain());
+        scopes.flat_map(move |scope| {
+            Action::ALL
+                .into_iter()
+                .filter(move |a| a.scope() == *scope)
+                .flat_map(move |a| self.bindings(a).iter().map(move |b| (a, b)))
+        })
+    }
+
+    /// Finds the action bound to exactly the given sequence of keys on a page
+    pub fn sequence(&self, page: Scope, keys: &[Key]) -> Option<Action> {
+        self.bindings_for(page)
+            .find(|(_, b)| b.keys() == keys)
+            .map(|(action, _)| action)
+    }
+
+    /// The bindings on a page which start with, but are longer than, the given keys;
+    /// returning the remaining keys of each along with its action
+    pub fn continuations(&self, page: Scope, prefix: &[Key]) -> Vec<(Binding, Action)> {
+        self.bindings_for(page)
+            .filter(|(_, b)| b.keys().len() > prefix.len() && b.keys().starts_with(prefix))
+            .map(|(action, b)| (Binding(b.keys()[prefix.len()..].to_vec()), action))
+            .collect()
+    }
+
+    pub fn bindings(&self, action: Action) -> &[Binding] {
+        self.bindings
+            .get(&action)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The keys bound to an action, formatted for display in help text
+    pub fn display(&self, action: Action) -> String {
+        self.bindings(action).iter().join("/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keymap(yaml: &str) -> Keymap {
+        serde_yml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn default_keymap_is_valid() {
+        Keymap::default().validate().unwrap();
+    }
+
+    #[test]
+    fn deserialises_keys() {
+        let binding = |yaml: &str| serde_yml::from_str::<Binding>(yaml).unwrap();
+        assert_eq!(binding("Home"), Binding::from(Key::Home));
+        assert_eq!(binding("!Ctrl w"), Binding::from(Key::Ctrl('w')));
+    }
+
+    #[test]
+    fn deserialises_sequences() {
+        let keymap = keymap("navigation.top: [[!Char g, !Char g], Home]");
+        assert_eq!(
+            keymap.bindings(Action::Top),
+            [
+                Binding::from(vec![Key::Char('g'), Key::Char('g')]),
+                Binding::from(Key::Home)
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_sequences_and_their_continuations() {
+        let keymap =
+            keymap("navigation.top: [[!Char g, !Char g]]\ncontainers.logs: [[!Char g, !Char l]]");
+        keymap.validate().unwrap();
+        let g = Key::Char('g');
+        assert_eq!(
+            keymap.sequence(Scope::Containers, &[g, g]),
+            Some(Action::Top)
+        );
+        assert_eq!(keymap.sequence(Scope::Containers, &[g]), None);
+
+        let mut continuations = keymap
+            .continuations(Scope::Containers, &[g])
+            .into_iter()
+            .map(|(binding, action)| (binding.to_string(), action))
+            .collect::<Vec<_>>();
+        continuations.sort();
+        assert_eq!(
+            continuations,
+            [
+                ("g".to_string(), Action::Top),
+                ("l".to_string(), Action::ContainerLogs)
+            ]
+        );
+        // The logs are only a continuation on the containers page
+        assert_eq!(keymap.continuations(Scope::Images, &[g]).len(), 1);
+    }
+
+    #[test]
+    fn resolved_actions_only_apply_in_their_scope() {
+        let keymap = Keymap::default();
+        let describe = Input::Action(Action::ContainerDescribe);
+        assert_eq!(
+            keymap.action(Scope::Containers, describe),
+            Some(Action::ContainerDescribe)
+        );
+        assert_eq!(keymap.action(Scope::Images, describe), None);
+        assert_eq!(
+            keymap.action(Scope::Images, Input::Key(Key::Char('d'))),
+            Some(Action::ImageDescribe)
+        );
+    }
+
+    #[test]
+    fn rebinding_an_action_keeps_the_other_defaults() {
+        let keymap = keymap("containers.exec: [!Char e, F2]");
+        assert_eq!(
+            keymap.bindings(Action::ContainerExec),
+            [Binding::from(Key::Char('e')), Binding::from(Key::F2)]
+        );
+        assert_eq!(
+            keymap.bindings(Action::Quit),
+            Keymap::default().bindings(Action::Quit)
+        );
+        keymap.validate().unwrap();
+    }
+
+    #[test]
+    fn rejects_unknown_actions() {
+        assert!(serde_yml::from_str::<Keymap>("containers.explode: [!Char x]").is_err());
+    }
+
+    #[test]
+    fn rejects_keys_which_cannot_be_pressed() {
+        assert!(keymap("containers.exec: [Unknown]").validate().is_err());
+        assert!(keymap("containers.exec: [[]]").validate().is_err());
+    }
+
+    #[test]
+    fn rejects_a_key_bound_twice_on_a_page() {
+        let err = keymap("containers.exec: [!Char d]").validate().unwrap_err();
+        assert!(err.to_string().contains("containers.exec"));
+        assert!(err.to_string().contains("containers.describe"));
+
+        // Global actions apply on every page
+        let err = keymap("global.quit: [!Char s]").validate().unwrap_err();
+        assert!(err.to_string().contains("containers.stop"));
+    }
+
+    #[test]
+    fn rejects_a_key_which_starts_a_sequence_on_a_page() {
+        let err = keymap("containers.logs: [[!Char z, !Char l]]\ncontainers.exec: [!Char z]")
+            .validate()
+            .unwrap_err();
+        assert!(err.to_string().contains("containers.exec"));
+        assert!(err.to_string().contains("containers.logs"));
+
+        // A global key conflicts with the sequences of every page
+        let err = keymap("images.pull: [[!Char x, !Char p]]\nglobal.quit: [!Char x]")
+            .validate()
+            .unwrap_err();
+        assert!(err.to_string().contains("images.pull"));
+
+        // Sequences on other pages are unaffected
+        keymap("images.pull: [[!Char z, !Char p]]\ncontainers.exec: [!Char z]")
+            .validate()
+            .unwrap();
+    }
+
+    #[test]
+    fn allows_a_key_bound_on_different_pages() {
+        keymap("images.pull: [!Char a]").validate().unwrap();
+    }
+}
@@ -1,13 +1,20 @@
+pub mod activity;
 pub mod autocomplete;
 pub mod callbacks;
+pub mod cli;
+pub mod clipboard;
+pub mod commands;
 pub mod components;
 pub mod config;
 pub mod context;
 pub mod docker;
 pub mod events;
+pub mod keymap;
+pub mod logging;
 pub mod pages;
 pub mod state;
 pub mod terminal;
+pub mod theme;
 pub mod traits;
 pub mod ui;
 pub mod widgets;
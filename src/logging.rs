@@ -0,0 +1,299 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::{Debug, Write},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Local};
+use color_eyre::eyre::{eyre, Context, Result};
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Level, Subscriber,
+};
+use tracing_appender::{
+    non_blocking::WorkerGuard,
+    rolling::{RollingFileAppender, Rotation},
+};
+use tracing_subscriber::{
+    filter::Targets, fmt::format::FmtSpan, layer::Context as LayerContext, prelude::*,
+    registry::LookupSpan, EnvFilter, Layer,
+};
+
+use crate::config::logs_path;
+
+/// The environment variable from which the log level is read, if it isn't given
+/// with `--log-level`.  Either takes a level, eg `debug`, or a list of directives in
+/// the format understood by `tracing_subscriber`, eg `info,ducker::docker=trace`
+pub const LOG_ENV: &str = "DUCKER_LOG";
+const DEFAULT_LEVEL: &str = "info";
+
+/// How many of the daily log files are kept before the oldest are removed
+const MAX_LOG_FILES: usize = 7;
+const MAX_EVENTS: usize = 200;
+const MAX_CALLS: usize = 200;
+
+/// Spans with a target under this prefix are calls to the Docker API, which are
+/// timed for the debug overlay
+const API_TARGET: &str = "ducker::docker::";
+
+/// Starts writing traces to a file in the data directory, which is rotated daily.
+/// The returned guard flushes any buffered traces when dropped, so must be held for
+/// as long as the application runs.
+///
+/// Logging never stops ducker from starting; if the log file can't be written, eg
+/// because the level is invalid or the data directory is read-only, a warning is
+/// printed and traces are only kept for the debug overlay
+pub fn init(level: Option<&str>) -> (Option<WorkerGuard>, TraceLog) {
+    let (file_layer, guard) = match file_layer(level) {
+        Ok((layer, guard)) => (Some(layer), Some(guard)),
+        Err(e) => {
+            eprintln!("warning: {e:#}; continuing without a log file");
+            (None, None)
+        }
+    };
+
+    // The overlay always shows ducker's own debug events and API calls, whatever
+    // is written to the file
+    let trace_log = TraceLog::default();
+    let installed = tracing_subscriber::registry()
+        .with(file_layer)
+        .with(
+            trace_log
+                .clone()
+                .with_filter(Targets::new().with_target("ducker", Level::DEBUG)),
+        )
+        .try_init();
+    if let Err(e) = installed {
+        eprintln!("warning: unable to install trace subscriber: {e}");
+    }
+
+    (guard, trace_log)
+}
+
+/// The layer which writes traces at the given level, or `DUCKER_LOG`, to the log file
+fn file_layer<S>(level: Option<&str>) -> Result<(impl Layer<S>, WorkerGuard)>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let level = match level {
+        Some(level) => level.to_string(),
+        None => std::env::var(LOG_ENV).unwrap_or_else(|_| DEFAULT_LEVEL.into()),
+    };
+    // The parse error already includes its cause, so isn't kept as a source
+    let filter =
+        EnvFilter::try_new(&level).map_err(|e| eyre!("invalid log level \"{level}\": {e}"))?;
+
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("ducker")
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(logs_path()?)
+        .context("unable to create log file")?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .with_ansi(false)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_filter(filter);
+    Ok((layer, guard))
+}
+
+/// An event recorded by the application, eg a transition between pages
+#[derive(Debug, Clone)]
+pub struct TraceEvent {
+    pub time: DateTime<Local>,
+    pub level: Level,
+    pub target: String,
+    /// The message of the event, followed by any of its other fields as `key=value`
+    pub message: String,
+}
+
+/// A completed call to the Docker API
+#[derive(Debug, Clone)]
+pub struct ApiCall {
+    /// The resource and the operation, eg `container.start`
+    pub name: String,
+    pub duration: Duration,
+    pub failed: bool,
+}
+
+/// Timings of every call of one kind to the Docker API
+#[derive(Debug, Clone, Default)]
+pub struct ApiCallStats {
+    pub name: String,
+    pub count: usize,
+    pub failures: usize,
+    pub last: Duration,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+impl ApiCallStats {
+    pub fn mean(&self) -> Duration {
+        self.total / self.count.max(1) as u32
+    }
+}
+
+#[derive(Debug, Default)]
+struct Traces {
+    events: VecDeque<TraceEvent>,
+    calls: VecDeque<ApiCall>,
+}
+
+/// Keeps the most recent trace events and Docker API call timings in memory, so
+/// that they can be shown in the debug overlay while the application runs
+#[derive(Debug, Clone, Default)]
+pub struct TraceLog {
+    traces: Arc<Mutex<Traces>>,
+}
+
+impl TraceLog {
+    /// The most recent events, oldest first
+    pub fn events(&self) -> Vec<TraceEvent> {
+        self.traces.lock().unwrap().events.iter().cloned().collect()
+    }
+
+    /// Timings of the most recent Docker API calls, grouped by kind of call and
+    /// ordered by the slowest on average
+    pub fn call_stats(&self) -> Vec<ApiCallStats> {
+        let mut stats: HashMap<String, ApiCallStats> = HashMap::new();
+        for call in &self.traces.lock().unwrap().calls {
+            let entry = stats
+                .entry(call.name.clone())
+                .or_insert_with(|| ApiCallStats {
+                    name: call.name.clone(),
+                    ..Default::default()
+                });
+            entry.count += 1;
+            entry.failures += usize::from(call.failed);
+            entry.last = call.duration;
+            entry.total += call.duration;
+            entry.max = entry.max.max(call.duration);
+        }
+        let mut stats = stats.into_values().collect::<Vec<_>>();
+        stats.sort_by(|a, b| b.mean().cmp(&a.mean()).then_with(|| a.name.cmp(&b.name)));
+        stats
+    }
+
+    fn push_event(&self, event: TraceEvent) {
+        let mut traces = self.traces.lock().unwrap();
+        if traces.events.len() == MAX_EVENTS {
+            traces.events.pop_front();
+        }
+        traces.events.push_back(event);
+    }
+
+    fn push_call(&self, call: ApiCall) {
+        let mut traces = self.traces.lock().unwrap();
+        if traces.calls.len() == MAX_CALLS {
+            traces.calls.pop_front();
+        }
+        traces.calls.push_back(call);
+    }
+}
+
+/// Stored against each API call span while it is open
+struct CallTiming {
+    started: Instant,
+    failed: bool,
+}
+
+impl<S> Layer<S> for TraceLog
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: LayerContext<'_, S>) {
+        if !attrs.metadata().target().starts_with(API_TARGET) {
+            return;
+        }
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(CallTiming {
+                started: Instant::now(),
+                failed: false,
+            });
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: LayerContext<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        // `#[instrument(err)]` reports a failed call with an `error` event in its span
+        if visitor.error {
+            if let Some(span) = ctx.event_span(event) {
+                if let Some(timing) = span.extensions_mut().get_mut::<CallTiming>() {
+                    timing.failed = true;
+                }
+            }
+        }
+        self.push_event(TraceEvent {
+            time: Local::now(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.message(),
+        });
+    }
+
+    fn on_close(&self, id: span::Id, ctx: LayerContext<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(timing) = span.extensions_mut().remove::<CallTiming>() else {
+            return;
+        };
+        let resource = span
+            .metadata()
+            .target()
+            .trim_start_matches(API_TARGET)
+            .replace("::", ".");
+        self.push_call(ApiCall {
+            name: format!("{resource}.{}", span.name()),
+            duration: timing.started.elapsed(),
+            failed: timing.failed,
+        });
+    }
+}
+
+/// Formats the fields of an event as its message followed by `key=value` pairs
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+    error: bool,
+}
+
+impl MessageVisitor {
+    fn message(self) -> String {
+        match (self.message.is_empty(), self.fields.is_empty()) {
+            (_, true) => self.message,
+            (true, false) => self.fields,
+            (false, false) => format!("{} {}", self.message, self.fields),
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+            return;
+        }
+        self.error |= field.name() == "error";
+        if !self.fields.is_empty() {
+            self.fields.push(' ');
+        }
+        let _ = write!(self.fields, "{}={value:?}", field.name());
+    }
+}
@@ -1,12 +1,15 @@
-use clap::Parser;
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
 use color_eyre::eyre::Context;
 
 use ducker::{
-    config::Config,
+    cli::{self, ConfigCommand},
+    config::{Config, Overrides},
     docker::util::new_local_docker_connection,
     events,
-    events::{EventLoop, Key, Message},
-    state, terminal,
+    events::{ConfigWatcher, EventLoop, Key, Message},
+    logging, state, terminal,
     ui::App,
 };
 
@@ -22,14 +25,62 @@ struct Args {
 
     /// Path at which to find the socket to communicate with
     /// docker
-    #[clap(long, short)]
+    #[clap(long, short, global = true)]
     docker_path: Option<String>,
+
+    /// Config file to read in place of the default config file
+    #[clap(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// Theme to use, overriding the config
+    #[clap(long, global = true)]
+    theme: Option<String>,
+
+    /// Command to run when exec'ing into a container, overriding the config
+    #[clap(long, global = true)]
+    exec: Option<String>,
+
+    /// Level of the traces written to the log file, eg `debug`; overrides the
+    /// DUCKER_LOG environment variable
+    #[clap(long)]
+    log_level: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Inspect and edit the config
+    #[command(subcommand)]
+    Config(ConfigCommand),
 }
 
 #[tokio::main]
 async fn main() -> color_eyre::Result<()> {
     let args = Args::parse();
-    let config = Config::new(&args.export_default_config, args.docker_path)?;
+    let overrides = Overrides {
+        config_file: args.config,
+        theme: args.theme,
+        exec: args.exec,
+        docker_path: args.docker_path,
+    };
+    if let Some(Command::Config(command)) = args.command {
+        if !command.run(overrides)? {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if args.export_default_config {
+        cli::export_default_config(&overrides)?;
+    }
+
+    // Held until ducker exits, so that any buffered traces are written out
+    let (_log_guard, trace_log) = logging::init(args.log_level.as_deref());
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), "starting ducker");
+
+    let config = Config::new(overrides.clone())?;
 
     let docker = new_local_docker_connection(&config.docker_path)
         .await
@@ -38,14 +89,18 @@ async fn main() -> color_eyre::Result<()> {
     terminal::init_panic_hook();
 
     let mut terminal = terminal::init().context("failed to initialise terminal")?;
+    terminal::set_mouse_capture(config.mouse).context("failed to set mouse capture")?;
 
     let mut events = EventLoop::new();
     let events_tx = events.get_tx();
-    let mut app = App::new(events_tx, docker, config)
+    let mut app = App::new(events_tx, docker, config, trace_log)
         .await
         .context("failed to create app")?;
 
     events.start().context("failed to start event loop")?;
+    ConfigWatcher::new(&overrides)
+        .context("failed to watch config")?
+        .start(events.get_tx());
 
     while app.running != state::Running::Done {
         terminal
@@ -54,11 +109,12 @@ async fn main() -> color_eyre::Result<()> {
             })
             .context("failed to update view")?;
 
-        match events
+        let message = events
             .next()
             .await
-            .context("unable to receive next event")?
-        {
+            .context("unable to receive next event")?;
+        tracing::trace!(?message, "handling message");
+        match message {
             Message::Input(k) => {
                 let res = app.update(k).await;
                 if !res.is_consumed() {
@@ -71,22 +127,36 @@ async fn main() -> color_eyre::Result<()> {
             Message::Transition(t) => {
                 if t == events::Transition::ToNewTerminal {
                     terminal = terminal::init().context("failed to initialise terminal")?;
+                    terminal::set_mouse_capture(app.mouse_capture())
+                        .context("failed to set mouse capture")?;
                 } else {
                     let _ = &app.transition(t).await;
                 }
             }
 
+            Message::Mouse(mouse) => {
+                app.mouse(mouse).await;
+            }
+
+            // Nothing to do other than drawing again, which happens for every message
+            Message::Resize => {}
+
             Message::Tick => {
                 app.update(Key::Null).await;
             }
 
-            Message::Error(_) => {
-                // This needs implementing
+            Message::Error(msg) => {
+                app.notify_error(msg);
+            }
+
+            Message::Notify(notification) => {
+                app.notify(notification);
             }
         }
     }
 
     terminal::restore().context("failed to restore terminal")?;
+    tracing::info!("exiting ducker");
 
     Ok(())
 }
@@ -0,0 +1,291 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use color_eyre::eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Cell, Row, Table, TableState},
+    Frame,
+};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    activity::{Activity as ActivityEntry, ActivityKind},
+    components::{
+        alert_modal::{AlertModal, ModalState},
+        help::{PageHelp, PageHelpBuilder},
+    },
+    config::Config,
+    context::{AppContext, PageView},
+    events::{message::MessageResponse, Key, Message, Mouse, MouseKind, Transition},
+    keymap::{Action, Input, Scope},
+    state::SharedSession,
+    traits::{Close, Component, ModalComponent, Page},
+};
+
+const NAME: &str = "Activity";
+
+#[derive(Debug)]
+enum ModalTypes {
+    Details,
+}
+
+/// Lists every action performed during the session, newest first, along with
+/// its outcome
+#[derive(Debug)]
+pub struct Activity {
+    config: Box<Config>,
+    tx: Sender<Message<Key, Transition>>,
+    session: SharedSession,
+    page_help: Arc<Mutex<PageHelp>>,
+    table_state: TableState,
+    /// Where the table was last drawn, to find the row which is clicked
+    table_area: Rect,
+    modal: Option<AlertModal<ModalTypes>>,
+}
+
+#[async_trait::async_trait]
+impl Page for Activity {
+    async fn update(&mut self, input: Input) -> Result<MessageResponse> {
+        if let Some(m) = self.modal.as_mut() {
+            if let ModalState::Open(_) = m.state {
+                let Some(key) = input.key() else {
+                    return Ok(MessageResponse::NotConsumed);
+                };
+                let res = m.update(key).await;
+                if let ModalState::Closed = m.state {
+                    self.modal = None;
+                }
+                return res;
+            }
+        }
+
+        let len = self.activities().len();
+        let result = match self.config.keybindings.action(Scope::Activity, input) {
+            Some(Action::Up) => {
+                let idx = self.table_state.selected().unwrap_or_default();
+                self.table_state.select(Some(idx.saturating_sub(1)));
+                MessageResponse::Consumed
+            }
+            Some(Action::Down) => {
+                let idx = self.table_state.selected().map_or(0, |i| i + 1);
+                self.table_state
+                    .select(Some(idx.min(len.saturating_sub(1))));
+                MessageResponse::Consumed
+            }
+            Some(Action::Top) => {
+                self.table_state.select(Some(0));
+                MessageResponse::Consumed
+            }
+            Some(Action::Bottom) => {
+                self.table_state.select(Some(len.saturating_sub(1)));
+                MessageResponse::Consumed
+            }
+            Some(Action::ActivityDetails) => {
+                self.show_details();
+                MessageResponse::Consumed
+            }
+            Some(Action::ActivityBack) => {
+                self.tx.send(Message::Transition(Transition::Back)).await?;
+                MessageResponse::Consumed
+            }
+            _ => MessageResponse::NotConsumed,
+        };
+        Ok(result)
+    }
+
+    async fn initialise(&mut self, _cx: AppContext) -> Result<()> {
+        self.table_state.select(Some(0));
+        Ok(())
+    }
+
+    fn get_help(&self) -> Arc<Mutex<PageHelp>> {
+        self.page_help.clone()
+    }
+
+    fn set_config(&mut self, config: Box<Config>) {
+        self.page_help = Arc::new(Mutex::new(Self::build_page_help(config.clone())));
+        self.config = config;
+    }
+
+    fn is_capturing_input(&self) -> bool {
+        self.modal.is_some()
+    }
+
+    fn view(&self) -> Option<PageView> {
+        Some(PageView::from_table(&self.table_state))
+    }
+
+    fn restore_view(&mut self, view: PageView) {
+        let len = self.activities().len();
+        view.restore_table(&mut self.table_state, len);
+    }
+
+    /// Clicking an entry selects it, and double-clicking it shows its details
+    async fn mouse(&mut self, mouse: Mouse) -> Result<MessageResponse> {
+        let len = self.activities().len();
+        let Some(idx) = mouse
+            .row_in(self.table_area, 1, self.table_state.offset())
+            .filter(|idx| *idx < len)
+        else {
+            return Ok(MessageResponse::NotConsumed);
+        };
+        self.table_state.select(Some(idx));
+        if mouse.kind == MouseKind::DoubleClick {
+            self.show_details();
+        }
+        Ok(MessageResponse::Consumed)
+    }
+}
+
+#[async_trait::async_trait]
+impl Close for Activity {}
+
+impl Activity {
+    pub fn new(
+        tx: Sender<Message<Key, Transition>>,
+        config: Box<Config>,
+        session: SharedSession,
+    ) -> Self {
+        let page_help = Self::build_page_help(config.clone());
+
+        Self {
+            config,
+            tx,
+            session,
+            page_help: Arc::new(Mutex::new(page_help)),
+            table_state: TableState::default(),
+            table_area: Rect::default(),
+            modal: None,
+        }
+    }
+
+    fn build_page_help(config: Box<Config>) -> PageHelp {
+        PageHelpBuilder::new(NAME.into(), config)
+            .add_action(Action::ActivityBack, "back")
+            .add_action(Action::ActivityDetails, "details")
+            .build()
+    }
+
+    /// The session's activities, newest first.  These are read afresh each time, as
+    /// actions started elsewhere may still be finishing in the background
+    fn activities(&self) -> Vec<ActivityEntry> {
+        let session = self.session.lock().unwrap();
+        session.activities().iter().rev().cloned().collect()
+    }
+
+    fn show_details(&mut self) {
+        let Some(activity) = self
+            .table_state
+            .selected()
+            .and_then(|idx| self.activities().into_iter().nth(idx))
+        else {
+            return;
+        };
+
+        let (title, message) = match &activity.outcome {
+            Ok(_) => (
+                "Succeeded".to_string(),
+                format!(
+                    "{} {} succeeded in {}",
+                    activity.kind.verb(),
+                    activity.target,
+                    format_duration(activity.duration)
+                ),
+            ),
+            Err(chain) => {
+                let mut message = chain.first().cloned().unwrap_or_default();
+                if chain.len() > 1 {
+                    message.push_str("\n\nCaused by:");
+                    for (idx, cause) in chain.iter().skip(1).enumerate() {
+                        message.push_str(&format!("\n  {idx}: {cause}"));
+                    }
+                }
+                ("Failed".to_string(), message)
+            }
+        };
+
+        let mut modal = AlertModal::new(title, ModalTypes::Details, self.config.theme);
+        modal.initialise(message);
+        self.modal = Some(modal);
+    }
+}
+
+impl Component for Activity {
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        let [table_area, summary] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+
+        let activities = self.activities();
+        let failures = activities.iter().filter(|a| !a.succeeded()).count();
+
+        let rows = activities.iter().map(|a| {
+            let result = match &a.outcome {
+                Ok(_) => Cell::from("✔").style(self.config.theme.success()),
+                Err(chain) => {
+                    Cell::from(format!("✘ {}", chain.first().cloned().unwrap_or_default()))
+                        .style(self.config.theme.error())
+                }
+            };
+            let target = if a.target.is_empty() {
+                "-".to_string()
+            } else {
+                a.target.clone()
+            };
+            Row::new(vec![
+                Cell::from(a.started.format("%H:%M:%S").to_string()),
+                Cell::from(a.kind.verb()),
+                Cell::from(target),
+                Cell::from(match a.kind {
+                    ActivityKind::Error => "-".to_string(),
+                    _ => format_duration(a.duration),
+                }),
+                result,
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(10),
+                Constraint::Length(9),
+                Constraint::Fill(1),
+                Constraint::Length(10),
+                Constraint::Fill(2),
+            ],
+        )
+        .header(
+            Row::new(vec!["Time", "Action", "Target", "Duration", "Result"])
+                .style(self.config.theme.table_header()),
+        )
+        .highlight_style(self.config.theme.selected());
+        self.table_area = table_area;
+        f.render_stateful_widget(table, table_area, &mut self.table_state);
+
+        let summary_line = if activities.is_empty() {
+            "nothing has been done yet this session".to_string()
+        } else {
+            format!("{} actions, {failures} failed", activities.len())
+        };
+        f.render_widget(
+            Line::from(Span::from(summary_line).style(self.config.theme.footer())),
+            summary,
+        );
+
+        if let Some(m) = self.modal.as_mut() {
+            if let ModalState::Open(_) = m.state {
+                m.draw(f, area)
+            }
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{:.1}s", duration.as_secs_f64())
+    }
+}
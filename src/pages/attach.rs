@@ -8,18 +8,18 @@ use tokio::sync::mpsc::Sender;
 
 use crate::config::Config;
 use crate::context::AppContext;
+use crate::terminal;
 use crate::traits::Close;
 use crate::{
     components::help::{PageHelp, PageHelpBuilder},
     docker::container::DockerContainer,
     events::{message::MessageResponse, Key, Message, Transition},
+    keymap::{Action, Input},
     traits::{Component, Page},
 };
 
 const NAME: &str = "Attach";
 
-const ESC_KEY: Key = Key::Esc;
-
 #[derive(Debug)]
 pub struct Attach {
     config: Box<Config>,
@@ -30,9 +30,7 @@ pub struct Attach {
 
 impl Attach {
     pub fn new(tx: Sender<Message<Key, Transition>>, config: Box<Config>) -> Self {
-        let page_help = PageHelpBuilder::new(NAME.into(), config.clone())
-            .add_input(format!("{ESC_KEY}"), "back".into())
-            .build();
+        let page_help = Self::build_page_help(config.clone());
 
         Self {
             config,
@@ -41,11 +39,17 @@ impl Attach {
             page_help: Arc::new(Mutex::new(page_help)),
         }
     }
+
+    fn build_page_help(config: Box<Config>) -> PageHelp {
+        PageHelpBuilder::new(NAME.into(), config)
+            .add_action(Action::AttachBack, "back")
+            .build()
+    }
 }
 
 #[async_trait::async_trait]
 impl Page for Attach {
-    async fn update(&mut self, _message: Key) -> Result<MessageResponse> {
+    async fn update(&mut self, _input: Input) -> Result<MessageResponse> {
         let res = MessageResponse::Consumed;
 
         Ok(res)
@@ -59,18 +63,14 @@ impl Page for Attach {
         }
         if let Some(container) = self.container.clone() {
             disable_raw_mode()?;
-            container.attach(&self.config.default_exec).await?;
-
-            let transition = if let Some(t) = cx.next() {
-                t
-            } else {
-                Transition::ToContainerPage(AppContext {
-                    docker_container: Some(container),
-                    ..Default::default()
-                })
-            };
-
-            self.tx.send(Message::Transition(transition)).await?;
+            terminal::set_mouse_capture(false)?;
+            let cmd = cx
+                .exec_command
+                .clone()
+                .unwrap_or_else(|| self.config.default_exec.clone());
+            container.attach(&cmd).await?;
+
+            self.tx.send(Message::Transition(Transition::Back)).await?;
             self.tx
                 .send(Message::Transition(Transition::ToNewTerminal))
                 .await?;
@@ -81,6 +81,11 @@ impl Page for Attach {
     fn get_help(&self) -> Arc<Mutex<PageHelp>> {
         self.page_help.clone()
     }
+
+    fn set_config(&mut self, config: Box<Config>) {
+        self.page_help = Arc::new(Mutex::new(Self::build_page_help(config.clone())));
+        self.config = config;
+    }
 }
 
 #[async_trait::async_trait]
@@ -1,51 +1,72 @@
 use bollard::Docker;
-use color_eyre::eyre::{bail, Context, Result};
-use futures::lock::Mutex as FutureMutex;
+use color_eyre::eyre::{bail, Result};
+use futures::{lock::Mutex as FutureMutex, Future};
 use ratatui::{
     layout::Rect,
     prelude::*,
     style::Style,
-    widgets::{Row, Table, TableState},
+    widgets::{Cell, Row, Table, TableState},
     Frame,
 };
 use std::{
     collections::HashSet,
     sync::{Arc, Mutex},
 };
-use tokio::sync::mpsc::Sender;
+use tokio::sync::{mpsc::Sender, watch};
 
 use crate::{
-    callbacks::DeleteContainer,
+    activity::{self, Activity, ActivityKind},
+    callbacks::{
+        bulk_action::{BulkReport, BulkTask},
+        BulkAction, DeleteContainer,
+    },
+    commands::Command,
     components::{
+        alert_modal::{AlertModal, ModalState as AlertState},
         boolean_modal::{BooleanModal, ModalState},
+        filter::Filter,
         help::{PageHelp, PageHelpBuilder},
     },
-    config::Config,
-    context::AppContext,
-    docker::container::DockerContainer,
-    events::{message::MessageResponse, Key, Message, Transition},
+    config::{ColumnConfig, Config},
+    context::{AppContext, PageView},
+    docker::{
+        container::{ContainerDetails, ContainerField, DockerContainer},
+        store::{ResourceKind, ResourceStore},
+    },
+    events::{
+        message::MessageResponse, send_notification, Key, Message, Mouse, MouseKind, Notification,
+        Transition,
+    },
+    keymap::{Action, Input, Scope},
+    state::{SharedSession, SortDirection, SortOrder},
     traits::{Close, Component, ModalComponent, Page},
 };
 
 const NAME: &str = "Containers";
 
-const UP_KEY: Key = Key::Up;
-const DOWN_KEY: Key = Key::Down;
+#[derive(Debug, Clone)]
+struct Column {
+    field: ContainerField,
+    header: String,
+    constraint: Constraint,
+}
 
-const A_KEY: Key = Key::Char('a');
-const J_KEY: Key = Key::Char('j');
-const K_KEY: Key = Key::Char('k');
-const CTRL_D_KEY: Key = Key::Ctrl('d');
-const D_KEY: Key = Key::Char('d');
-const R_KEY: Key = Key::Char('r');
-const S_KEY: Key = Key::Char('s');
-const G_KEY: Key = Key::Char('g');
-const L_KEY: Key = Key::Char('l');
-const SHIFT_G_KEY: Key = Key::Char('G');
+impl Column {
+    fn from_config(config: &ColumnConfig) -> Option<Self> {
+        let field = config.field.parse::<ContainerField>().ok()?;
+        Some(Self {
+            header: config.header.clone().unwrap_or_else(|| field.header()),
+            constraint: config.constraint().ok()?,
+            field,
+        })
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum ModalTypes {
     DeleteContainer,
+    BulkAction,
+    BulkReport,
 }
 
 #[derive(Debug)]
@@ -55,20 +76,71 @@ pub struct Containers {
     tx: Sender<Message<Key, Transition>>,
     page_help: Arc<Mutex<PageHelp>>,
     docker: Docker,
+    store: ResourceStore,
+    /// Notified whenever the containers in the store change
+    changes: watch::Receiver<()>,
     containers: Vec<DockerContainer>,
+    /// Indices into `containers` of the rows which pass the filter
+    visible: Vec<usize>,
+    filter: Filter,
+    columns: Vec<Column>,
+    session: SharedSession,
     list_state: TableState,
+    /// Where the table was last drawn, to find the row which is clicked
+    table_area: Rect,
     modal: Option<BooleanModal<ModalTypes>>,
+    alert: Option<AlertModal<ModalTypes>>,
     stopping_containers: Arc<Mutex<HashSet<String>>>,
+    /// Ids of the containers marked for a bulk action
+    marked: HashSet<String>,
+    bulk_report: Arc<Mutex<Option<BulkReport>>>,
 }
 
 #[async_trait::async_trait]
 impl Page for Containers {
-    async fn update(&mut self, message: Key) -> Result<MessageResponse> {
+    async fn update(&mut self, input: Input) -> Result<MessageResponse> {
+        self.refresh();
+
+        if let Some(m) = self.alert.as_mut() {
+            if let AlertState::Open(_) = m.state {
+                let Some(key) = input.key() else {
+                    return Ok(MessageResponse::NotConsumed);
+                };
+                let res = m.update(key).await;
+                if let AlertState::Closed = m.state {
+                    self.alert = None;
+                }
+                return res;
+            }
+        }
+
+        // Once a bulk action has completed the marks are spent, and we report the outcome
+        let report = self.bulk_report.lock().unwrap().take();
+        if let Some(report) = report {
+            self.marked.clear();
+            // Failures are listed in a modal so that they aren't missed
+            if report.has_failures() {
+                let mut alert =
+                    AlertModal::new(report.title(), ModalTypes::BulkReport, self.config.theme);
+                alert.initialise(report.message());
+                self.alert = Some(alert);
+            } else {
+                send_notification(
+                    self.tx.clone(),
+                    Notification::success(report.summary("containers")),
+                )
+                .await?;
+            }
+        }
+
         // If a modal is open, we process it; if it is open or complete, and the
         // result is Consumed, we exit early with the Consumed result
         if let Some(m) = self.modal.as_mut() {
             if let ModalState::Open(_) = m.state {
-                let res = m.update(message).await;
+                let Some(key) = input.key() else {
+                    return Ok(MessageResponse::NotConsumed);
+                };
+                let res = m.update(key).await;
                 if let ModalState::Closed = m.state {
                     self.modal = None;
                 }
@@ -76,40 +148,112 @@ impl Page for Containers {
             }
         }
 
-        let result = match message {
-            UP_KEY | K_KEY => {
+        if self.filter.update(input).is_consumed() {
+            let selected = self.get_container().ok().map(|c| c.id.clone());
+            self.apply_filter_and_sort(selected);
+            return Ok(MessageResponse::Consumed);
+        }
+
+        let result = match self.config.keybindings.action(Scope::Containers, input) {
+            Some(Action::Up) => {
                 self.decrement_list();
                 MessageResponse::Consumed
             }
-            DOWN_KEY | J_KEY => {
+            Some(Action::Down) => {
                 self.increment_list();
                 MessageResponse::Consumed
             }
-            CTRL_D_KEY => match self.delete_container() {
+            Some(Action::Mark) => {
+                self.toggle_mark();
+                MessageResponse::Consumed
+            }
+            Some(Action::MarkDown) => {
+                self.mark();
+                self.increment_list();
+                MessageResponse::Consumed
+            }
+            Some(Action::MarkUp) => {
+                self.mark();
+                self.decrement_list();
+                MessageResponse::Consumed
+            }
+            Some(Action::MarkAll) => {
+                self.toggle_mark_all();
+                MessageResponse::Consumed
+            }
+            Some(Action::Clear) if !self.marked.is_empty() => {
+                self.marked.clear();
+                MessageResponse::Consumed
+            }
+            Some(Action::ContainerDelete) if !self.marked.is_empty() => {
+                self.confirm_bulk_action(ActivityKind::Delete, |c, docker| async move {
+                    c.delete(&docker, c.running).await
+                });
+                MessageResponse::Consumed
+            }
+            Some(Action::ContainerStart) if !self.marked.is_empty() => {
+                self.confirm_bulk_action(ActivityKind::Start, |c, docker| async move {
+                    c.start(&docker).await
+                });
+                MessageResponse::Consumed
+            }
+            Some(Action::ContainerStop) if !self.marked.is_empty() => {
+                let stopping_containers = self.stopping_containers.clone();
+                self.confirm_bulk_action(ActivityKind::Stop, move |c, docker| {
+                    let stopping_containers = stopping_containers.clone();
+                    async move {
+                        stopping_containers.lock().unwrap().insert(c.id.clone());
+                        let result = c.stop(&docker).await;
+                        stopping_containers.lock().unwrap().remove(&c.id);
+                        result
+                    }
+                });
+                MessageResponse::Consumed
+            }
+            Some(Action::ContainerRestart) if !self.marked.is_empty() => {
+                self.confirm_bulk_action(ActivityKind::Restart, |c, docker| async move {
+                    c.restart(&docker).await
+                });
+                MessageResponse::Consumed
+            }
+            Some(Action::ContainerDelete) => match self.delete_container() {
                 Ok(_) => MessageResponse::Consumed,
                 Err(_) => MessageResponse::NotConsumed,
             },
-            R_KEY => {
-                self.start_container()
-                    .await
-                    .context("could not start container")?;
+            Some(Action::ContainerStart) => {
+                self.spawn_action(ActivityKind::Start, |c, docker| async move {
+                    c.start(&docker).await
+                });
+                MessageResponse::Consumed
+            }
+            Some(Action::ContainerStop) => {
+                self.stop_container();
                 MessageResponse::Consumed
             }
-            S_KEY => {
-                self.stop_container()
-                    .await
-                    .context("could not stop container")?;
+            Some(Action::ContainerRestart) => {
+                self.spawn_action(ActivityKind::Restart, |c, docker| async move {
+                    c.restart(&docker).await
+                });
                 MessageResponse::Consumed
             }
-            G_KEY => {
+            Some(Action::Top) => {
                 self.list_state.select(Some(0));
                 MessageResponse::Consumed
             }
-            SHIFT_G_KEY => {
-                self.list_state.select(Some(self.containers.len() - 1));
+            Some(Action::Sort) => {
+                self.cycle_sort_column();
+                MessageResponse::Consumed
+            }
+            Some(Action::ReverseSort) => {
+                self.reverse_sort_direction();
                 MessageResponse::Consumed
             }
-            A_KEY => {
+            Some(Action::Bottom) => {
+                self.list_state
+                    .select(Some(self.visible.len().saturating_sub(1)));
+                MessageResponse::Consumed
+            }
+            Some(Action::ContainerExec) => {
                 self.tx
                     .send(Message::Transition(Transition::ToAttach(
                         self.get_context()?,
@@ -117,7 +261,7 @@ impl Page for Containers {
                     .await?;
                 MessageResponse::Consumed
             }
-            L_KEY => {
+            Some(Action::ContainerLogs) => {
                 self.tx
                     .send(Message::Transition(Transition::ToLogPage(
                         self.get_context()?,
@@ -125,7 +269,7 @@ impl Page for Containers {
                     .await?;
                 MessageResponse::Consumed
             }
-            D_KEY => {
+            Some(Action::ContainerDescribe) => {
                 self.tx
                     .send(Message::Transition(Transition::ToDescribeContainerPage(
                         self.get_context()?,
@@ -135,7 +279,6 @@ impl Page for Containers {
             }
             _ => MessageResponse::NotConsumed,
         };
-        self.refresh().await?;
         Ok(result)
     }
 
@@ -143,9 +286,8 @@ impl Page for Containers {
         self.list_state = TableState::default();
         self.list_state.select(Some(0));
 
-        self.refresh()
-            .await
-            .context("unable to set refresh containers")?;
+        self.store.request_details(self.details());
+        self.refresh();
 
         // If a context has been passed in, choose that item in list
         // this ist to allo logs, attach etc to appear to revert to previous
@@ -160,12 +302,7 @@ impl Page for Containers {
             return Ok(());
         }
 
-        for (idx, c) in self.containers.iter().enumerate() {
-            if c.id == container_id {
-                self.list_state.select(Some(idx));
-                break;
-            }
-        }
+        self.select_container(&container_id);
 
         Ok(())
     }
@@ -173,39 +310,255 @@ impl Page for Containers {
     fn get_help(&self) -> Arc<Mutex<PageHelp>> {
         self.page_help.clone()
     }
+
+    fn set_config(&mut self, config: Box<Config>) {
+        self.page_help = Arc::new(Mutex::new(Self::build_page_help(config.clone())));
+        self.columns = Self::build_columns(&config);
+        self.filter.set_config(
+            config.clone(),
+            self.columns.iter().map(|c| c.field.key()).collect(),
+        );
+        self.config = config;
+        self.store.request_details(self.details());
+
+        let selected = self.get_container().ok().map(|c| c.id.clone());
+        self.apply_filter_and_sort(selected);
+    }
+
+    fn is_capturing_input(&self) -> bool {
+        self.filter.is_editing() || self.modal.is_some() || self.alert.is_some()
+    }
+
+    fn view(&self) -> Option<PageView> {
+        Some(PageView::from_table(&self.list_state))
+    }
+
+    fn restore_view(&mut self, view: PageView) {
+        view.restore_table(&mut self.list_state, self.visible.len());
+    }
+
+    fn selection(&self) -> Option<AppContext> {
+        self.get_context().ok()
+    }
+
+    /// Clicking a row selects it, and double-clicking it describes it
+    async fn mouse(&mut self, mouse: Mouse) -> Result<MessageResponse> {
+        let Some(idx) = mouse
+            .row_in(self.table_area, 1, self.list_state.offset())
+            .filter(|idx| *idx < self.visible.len())
+        else {
+            return Ok(MessageResponse::NotConsumed);
+        };
+        self.list_state.select(Some(idx));
+        match mouse.kind {
+            MouseKind::DoubleClick => self.update(Action::ContainerDescribe.into()).await,
+            _ => Ok(MessageResponse::Consumed),
+        }
+    }
+
+    async fn command(&mut self, command: Command) -> Result<()> {
+        match command {
+            Command::Filter { query } => {
+                self.filter.set(&query);
+                let selected = self.get_container().ok().map(|c| c.id.clone());
+                self.apply_filter_and_sort(selected);
+            }
+            Command::Sort { column, direction } => {
+                let field = column.parse::<ContainerField>()?;
+                let direction = direction.unwrap_or_else(|| match self.sort_order() {
+                    Some((current, direction)) if current == field => direction,
+                    _ => SortDirection::default(),
+                });
+                self.set_sort_order(field, direction);
+            }
+            command => bail!("\"{}\" is not supported on this page", command.name()),
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
 impl Close for Containers {}
 
 impl Containers {
-    pub fn new(docker: Docker, tx: Sender<Message<Key, Transition>>, config: Box<Config>) -> Self {
-        let page_help = PageHelpBuilder::new(NAME.into(), config.clone())
-            .add_input(format!("{}", A_KEY), "exec".into())
-            .add_input(format!("{CTRL_D_KEY}"), "delete".into())
-            .add_input(format!("{R_KEY}"), "run".into())
-            .add_input(format!("{S_KEY}"), "stop".into())
-            .add_input(format!("{G_KEY}"), "top".into())
-            .add_input(format!("{SHIFT_G_KEY}"), "bottom".into())
-            .add_input(format!("{L_KEY}"), "logs".into())
-            .build();
+    pub fn new(
+        docker: Docker,
+        tx: Sender<Message<Key, Transition>>,
+        config: Box<Config>,
+        session: SharedSession,
+        store: ResourceStore,
+    ) -> Self {
+        let page_help = Self::build_page_help(config.clone());
+        let columns = Self::build_columns(&config);
 
         Self {
+            filter: Filter::new(config.clone())
+                .with_columns(columns.iter().map(|c| c.field.key()).collect()),
+            columns,
             config,
             name: String::from(NAME),
             page_help: Arc::new(Mutex::new(page_help)),
             tx,
             docker,
+            changes: store.subscribe(ResourceKind::Containers),
+            store,
             containers: vec![],
+            visible: vec![],
+            session,
             list_state: TableState::default(),
+            table_area: Rect::default(),
             modal: None,
+            alert: None,
             stopping_containers: Arc::new(Mutex::new(HashSet::new())),
+            marked: HashSet::new(),
+            bulk_report: Arc::new(Mutex::new(None)),
         }
     }
 
-    async fn refresh(&mut self) -> Result<(), color_eyre::eyre::Error> {
-        self.containers = DockerContainer::list(&self.docker).await?;
-        Ok(())
+    fn build_page_help(config: Box<Config>) -> PageHelp {
+        PageHelpBuilder::new(NAME.into(), config)
+            .add_action(Action::ContainerExec, "exec")
+            .add_action(Action::ContainerDelete, "delete")
+            .add_action(Action::ContainerStart, "run")
+            .add_action(Action::ContainerStop, "stop")
+            .add_action(Action::ContainerRestart, "restart")
+            .add_action(Action::Mark, "mark")
+            .add_actions(&[Action::MarkDown, Action::MarkUp], "mark range")
+            .add_action(Action::MarkAll, "mark all")
+            .add_action(Action::Top, "top")
+            .add_action(Action::Bottom, "bottom")
+            .add_action(Action::ContainerLogs, "logs")
+            .add_action(Action::Filter, "filter")
+            .add_actions(&[Action::Sort, Action::ReverseSort], "sort/reverse")
+            .add_action(Action::PreviewToggle, "preview")
+            .add_action(Action::PreviewCycle, "preview kind")
+            .build()
+    }
+
+    fn build_columns(config: &Config) -> Vec<Column> {
+        config
+            .columns
+            .containers
+            .iter()
+            .filter_map(Column::from_config)
+            .collect()
+    }
+
+    /// Copies the containers from the store, if they have changed since they were last
+    /// copied; this never waits on the daemon, so is cheap enough to do on every key
+    fn refresh(&mut self) {
+        if !self.changes.has_changed().unwrap_or(false) {
+            return;
+        }
+        self.changes.mark_unchanged();
+
+        let selected = self.get_container().ok().map(|c| c.id.clone());
+        self.containers = self.store.containers();
+        let containers = &self.containers;
+        self.marked
+            .retain(|id| containers.iter().any(|c| c.id == *id));
+        self.apply_filter_and_sort(selected);
+    }
+
+    /// Recomputes the rows which pass the filter and puts them in the selected sort
+    /// order, keeping the selection on the given container if it is still visible
+    fn apply_filter_and_sort(&mut self, selected: Option<String>) {
+        self.visible = self
+            .containers
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| self.filter.matches_row(&self.cells(c)))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        if let Some((field, direction)) = self.sort_order() {
+            let containers = &self.containers;
+            self.visible.sort_by(|a, b| {
+                let ordering = containers[*a].compare_field(&containers[*b], &field);
+                match direction {
+                    SortDirection::Ascending => ordering,
+                    SortDirection::Descending => ordering.reverse(),
+                }
+            });
+        }
+
+        match selected {
+            Some(id) if self.select_container(&id) => {}
+            _ => {
+                let idx = self.list_state.selected().unwrap_or_default();
+                self.list_state
+                    .select(Some(idx.min(self.visible.len().saturating_sub(1))));
+            }
+        }
+    }
+
+    /// Only request the more expensive container details if they are displayed
+    fn details(&self) -> ContainerDetails {
+        ContainerDetails {
+            size: self.columns.iter().any(|c| c.field == ContainerField::Size),
+            restart_count: self
+                .columns
+                .iter()
+                .any(|c| c.field == ContainerField::RestartCount),
+        }
+    }
+
+    fn cells(&self, c: &DockerContainer) -> Vec<String> {
+        self.columns.iter().map(|col| c.field(&col.field)).collect()
+    }
+
+    fn sort_order(&self) -> Option<(ContainerField, SortDirection)> {
+        let order = self.session.lock().unwrap().sort_order(NAME)?;
+        let field = order.column.parse::<ContainerField>().ok()?;
+        Some((field, order.direction))
+    }
+
+    fn set_sort_order(&mut self, field: ContainerField, direction: SortDirection) {
+        self.session.lock().unwrap().set_sort_order(
+            NAME,
+            SortOrder {
+                column: field.key(),
+                direction,
+            },
+        );
+        let selected = self.get_container().ok().map(|c| c.id.clone());
+        self.apply_filter_and_sort(selected);
+    }
+
+    fn cycle_sort_column(&mut self) {
+        if self.columns.is_empty() {
+            return;
+        }
+        let (idx, direction) = match self.sort_order() {
+            Some((field, direction)) => match self.columns.iter().position(|c| c.field == field) {
+                Some(idx) => ((idx + 1) % self.columns.len(), direction),
+                None => (0, direction),
+            },
+            None => (0, SortDirection::default()),
+        };
+        self.set_sort_order(self.columns[idx].field.clone(), direction);
+    }
+
+    fn reverse_sort_direction(&mut self) {
+        let Some(first) = self.columns.first() else {
+            return;
+        };
+        let (field, direction) = self
+            .sort_order()
+            .unwrap_or((first.field.clone(), SortDirection::default()));
+        self.set_sort_order(field, direction.reverse());
+    }
+
+    /// Selects the row for the given container id, returning false if it isn't visible
+    fn select_container(&mut self, id: &str) -> bool {
+        let position = self
+            .visible
+            .iter()
+            .position(|idx| self.containers[*idx].id == id);
+        if position.is_some() {
+            self.list_state.select(position);
+        }
+        position.is_some()
     }
 
     fn increment_list(&mut self) {
@@ -213,7 +566,7 @@ impl Containers {
         match current_idx {
             None => self.list_state.select(Some(0)),
             Some(current_idx) => {
-                if !self.containers.is_empty() && current_idx < self.containers.len() - 1 {
+                if !self.visible.is_empty() && current_idx < self.visible.len() - 1 {
                     self.list_state.select(Some(current_idx + 1))
                 }
             }
@@ -233,51 +586,51 @@ impl Containers {
     }
 
     fn get_container(&self) -> Result<&DockerContainer> {
-        if let Some(container_idx) = self.list_state.selected() {
-            if let Some(container) = self.containers.get(container_idx) {
+        if let Some(row_idx) = self.list_state.selected() {
+            if let Some(container) = self.visible.get(row_idx).map(|idx| &self.containers[*idx]) {
                 return Ok(container);
             }
         }
         bail!("no container id found");
     }
 
-    async fn start_container(&mut self) -> Result<Option<()>> {
-        if let Ok(container) = self.get_container() {
-            container.start(&self.docker).await?;
-            self.refresh().await?;
-            return Ok(Some(()));
-        }
-        Ok(None)
+    /// Runs the action on the selected container in the background, so that a slow
+    /// daemon doesn't freeze the UI, and reports its outcome once it completes
+    fn spawn_action<F, Fut>(&self, kind: ActivityKind, action: F)
+    where
+        F: FnOnce(DockerContainer, Docker) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let Ok(container) = self.get_container() else {
+            return;
+        };
+        let c = container.clone();
+        let docker = self.docker.clone();
+        let tx = self.tx.clone();
+        let session = self.session.clone();
+        tokio::spawn(async move {
+            let activity = Activity::begin(kind, format!("container {}", c.names));
+            let result = action(c, docker).await;
+            let _ = activity::report(&session, &tx, activity.finish(&result)).await;
+            let _ = tx.send(Message::Tick).await;
+        });
     }
 
-    async fn stop_container(&mut self) -> Result<Option<()>> {
-        if let Ok(container) = self.get_container() {
-            self.stopping_containers
-                .lock()
-                .unwrap()
-                .insert(container.id.clone());
-
-            let c = container.clone();
-            let docker = self.docker.clone();
-            let tx = self.tx.clone();
-            let stopping_containers = self.stopping_containers.clone();
-            tokio::spawn(async move {
-                let message = if c.stop(&docker).await.is_ok() {
-                    Message::Tick
-                } else {
-                    let msg = format!("Failed to delete container {}", c.id);
-                    Message::Error(msg)
-                };
-                stopping_containers.lock().unwrap().remove(&c.id);
-                let _ = tx.send(message).await;
-            });
-
-            // Second spawned taskt is used to update the state
-
-            self.refresh().await?;
-            return Ok(Some(()));
-        }
-        Ok(None)
+    fn stop_container(&mut self) {
+        let Ok(container) = self.get_container() else {
+            return;
+        };
+        self.stopping_containers
+            .lock()
+            .unwrap()
+            .insert(container.id.clone());
+
+        let stopping_containers = self.stopping_containers.clone();
+        self.spawn_action(ActivityKind::Stop, |c, docker| async move {
+            let result = c.stop(&docker).await;
+            stopping_containers.lock().unwrap().remove(&c.id);
+            result
+        });
     }
 
     fn delete_container(&mut self) -> Result<()> {
@@ -295,11 +648,15 @@ impl Containers {
                 self.docker.clone(),
                 container.clone(),
                 container.running,
+                self.session.clone(),
                 self.tx.clone(),
             )));
 
-            let mut modal =
-                BooleanModal::<ModalTypes>::new("Delete".into(), ModalTypes::DeleteContainer);
+            let mut modal = BooleanModal::<ModalTypes>::new(
+                "Delete".into(),
+                ModalTypes::DeleteContainer,
+                self.config.theme,
+            );
             modal.initialise(message, Some(cb));
             self.modal = Some(modal);
         } else {
@@ -308,17 +665,96 @@ impl Containers {
         Ok(())
     }
 
+    fn mark(&mut self) {
+        if let Ok(container) = self.get_container() {
+            let id = container.id.clone();
+            self.marked.insert(id);
+        }
+    }
+
+    fn toggle_mark(&mut self) {
+        if let Ok(container) = self.get_container() {
+            let id = container.id.clone();
+            if !self.marked.remove(&id) {
+                self.marked.insert(id);
+            }
+        }
+    }
+
+    /// Marks every row which passes the filter, or unmarks them if they are all marked already
+    fn toggle_mark_all(&mut self) {
+        let ids = self
+            .visible
+            .iter()
+            .map(|idx| self.containers[*idx].id.clone())
+            .collect::<Vec<String>>();
+        if ids.iter().all(|id| self.marked.contains(id)) {
+            ids.iter().for_each(|id| {
+                self.marked.remove(id);
+            });
+        } else {
+            self.marked.extend(ids);
+        }
+    }
+
+    /// Opens a single confirmation modal for applying the given action to all marked containers
+    fn confirm_bulk_action<F, Fut>(&mut self, kind: ActivityKind, action: F)
+    where
+        F: Fn(DockerContainer, Docker) -> Fut + Clone + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let targets = self
+            .containers
+            .iter()
+            .filter(|c| self.marked.contains(&c.id))
+            .map(|c| {
+                let container = c.clone();
+                let docker = self.docker.clone();
+                let action = action.clone();
+                let task: BulkTask =
+                    Box::new(move || Box::pin(action(container.clone(), docker.clone())));
+                (c.names.clone(), task)
+            })
+            .collect();
+
+        let mut cb = BulkAction::new(
+            kind,
+            "container",
+            targets,
+            self.bulk_report.clone(),
+            self.session.clone(),
+            self.tx.clone(),
+        );
+        // Running containers are deleted by force, as when deleting a single one
+        if kind == ActivityKind::Delete {
+            let running = self
+                .containers
+                .iter()
+                .filter(|c| c.running && self.marked.contains(&c.id))
+                .map(|c| c.names.clone())
+                .collect::<Vec<String>>();
+            cb = cb.with_warning(
+                &format!(
+                    "{} of these containers are currently running; this will result in a force deletion of:",
+                    running.len()
+                ),
+                &running,
+            );
+        }
+        let message = cb.confirmation();
+
+        let mut modal =
+            BooleanModal::<ModalTypes>::new(cb.title(), ModalTypes::BulkAction, self.config.theme);
+        modal.initialise(message, Some(Arc::new(FutureMutex::new(cb))));
+        self.modal = Some(modal);
+    }
+
     fn get_context(&self) -> Result<AppContext> {
         let container = self.get_container()?;
 
-        let then = Some(Box::new(Transition::ToContainerPage(AppContext {
-            docker_container: Some(container.clone()),
-            ..Default::default()
-        })));
-
         let cx = AppContext {
+            docker_container: Some(container.clone()),
             describable: Some(Box::new(container.clone())),
-            then,
             ..Default::default()
         };
 
@@ -328,44 +764,85 @@ impl Containers {
 
 impl Component for Containers {
     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
-        let rows = self.containers.clone().into_iter().map(|c| {
+        let (area, filter_area) = if self.filter.is_open() {
+            let [area, filter_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+            (area, Some(filter_area))
+        } else {
+            (area, None)
+        };
+
+        let show_marks = !self.marked.is_empty();
+        let rows = self.visible.iter().map(|idx| {
+            let c = &self.containers[*idx];
             let style = if self.stopping_containers.lock().unwrap().contains(&c.id) {
-                Style::default().fg(self.config.theme.negative_highlight())
+                self.config.theme.negative_highlight()
             } else if c.running {
-                Style::default().fg(self.config.theme.positive_highlight())
+                self.config.theme.positive_highlight()
             } else {
                 Style::default()
             };
 
-            Row::new(vec![
-                c.id, c.image, c.command, c.created, c.status, c.ports, c.names,
-            ])
-            .style(style)
+            let mut cells = self
+                .cells(c)
+                .iter()
+                .enumerate()
+                .map(|(idx, v)| Cell::from(self.filter.highlight(idx, v)))
+                .collect::<Vec<Cell>>();
+            if show_marks {
+                let marker = if self.marked.contains(&c.id) {
+                    "●"
+                } else {
+                    ""
+                };
+                cells.insert(0, Cell::from(marker));
+            }
+
+            Row::new(cells).style(style)
         });
-        let columns = Row::new(vec![
-            "ID", "Image", "Command", "Created", "Status", "Ports", "Names",
-        ]);
-
-        let widths = [
-            Constraint::Percentage(12),
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-            Constraint::Percentage(10),
-            Constraint::Percentage(13),
-            Constraint::Percentage(10),
-            Constraint::Percentage(10),
-        ];
-
-        let table = Table::new(rows.clone(), widths)
-            .header(columns.clone().style(Style::new().bold()))
-            .highlight_style(Style::new().reversed());
+        let sort_order = self.sort_order();
+        let mut headers = self
+            .columns
+            .iter()
+            .map(|c| match &sort_order {
+                Some((field, direction)) if *field == c.field => {
+                    format!("{} {}", c.header, direction.indicator())
+                }
+                _ => c.header.clone(),
+            })
+            .collect::<Vec<String>>();
+        let mut widths = self
+            .columns
+            .iter()
+            .map(|c| c.constraint)
+            .collect::<Vec<Constraint>>();
+        if show_marks {
+            headers.insert(0, String::new());
+            widths.insert(0, Constraint::Length(2));
+        }
+        let columns = Row::new(headers);
+
+        let table = Table::new(rows, widths)
+            .header(columns.clone().style(self.config.theme.table_header()))
+            .highlight_style(self.config.theme.selected());
 
+        self.table_area = area;
         f.render_stateful_widget(table, area, &mut self.list_state);
 
+        if let Some(filter_area) = filter_area {
+            self.filter.draw(f, filter_area);
+        }
+
         if let Some(m) = self.modal.as_mut() {
             if let ModalState::Open(_) = m.state {
                 m.draw(f, area)
             }
         }
+
+        if let Some(m) = self.alert.as_mut() {
+            if let AlertState::Open(_) = m.state {
+                m.draw(f, area)
+            }
+        }
     }
 }
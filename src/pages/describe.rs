@@ -3,41 +3,71 @@ use std::sync::{Arc, Mutex};
 
 use bollard::Docker;
 
-use color_eyre::eyre::{bail, Result};
+use color_eyre::eyre::{bail, Context, Result};
+use ratatui::layout::{Constraint, Layout};
 use ratatui::style::Style;
 use ratatui::text::{Line, Span};
-use ratatui::widgets::Paragraph;
+use ratatui::widgets::{List, ListState};
 use ratatui::{layout::Rect, Frame};
 use tokio::sync::mpsc::Sender;
 
+use crate::clipboard;
+use crate::components::tree_view::{highlight, TreeView};
 use crate::config::Config;
-use crate::context::AppContext;
+use crate::context::{AppContext, PageView};
 use crate::docker::traits::Describe;
 use crate::traits::Close;
 use crate::{
     components::help::{PageHelp, PageHelpBuilder},
     events::{message::MessageResponse, Key, Message, Transition},
+    keymap::{Action, Input, Scope},
     traits::{Component, Page},
 };
 
 const NAME: &str = "Describe";
 
-const UP_KEY: Key = Key::Up;
-const DOWN_KEY: Key = Key::Down;
+/// The different ways in which the description can be rendered
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum View {
+    #[default]
+    Tree,
+    Yaml,
+    Json,
+}
+
+impl View {
+    fn next(self) -> Self {
+        match self {
+            Self::Tree => Self::Yaml,
+            Self::Yaml => Self::Json,
+            Self::Json => Self::Tree,
+        }
+    }
 
-const J_KEY: Key = Key::Char('j');
-const K_KEY: Key = Key::Char('k');
+    fn name(self) -> &'static str {
+        match self {
+            Self::Tree => "tree",
+            Self::Yaml => "yaml",
+            Self::Json => "json",
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct DescribeContainer {
-    _docker: Docker,
+    docker: Docker,
     config: Box<Config>,
     thing: Option<Box<dyn Describe>>,
-    thing_summary: Option<Vec<String>>,
     tx: Sender<Message<Key, Transition>>,
-    cx: Option<AppContext>,
     page_help: Arc<Mutex<PageHelp>>,
-    scroll: u16,
+    view: View,
+    tree: TreeView,
+    yaml: Vec<String>,
+    json: Vec<String>,
+    raw_state: ListState,
+    search_input: Option<String>,
+    search: Option<String>,
+    status: Option<String>,
 }
 
 impl DescribeContainer {
@@ -45,14 +75,19 @@ impl DescribeContainer {
         let page_help = Self::build_page_help(config.clone(), None);
 
         Self {
-            _docker: docker,
+            docker,
+            tree: TreeView::new(config.clone()),
             config,
             thing: None,
-            thing_summary: None,
             tx,
-            cx: None,
             page_help: Arc::new(Mutex::new(page_help)),
-            scroll: 0,
+            view: View::default(),
+            yaml: vec![],
+            json: vec![],
+            raw_state: ListState::default(),
+            search_input: None,
+            search: None,
+            status: None,
         }
     }
 
@@ -62,60 +97,233 @@ impl DescribeContainer {
         } else {
             NAME.into()
         };
-        PageHelpBuilder::new(page_name, config).build()
+        PageHelpBuilder::new(page_name, config)
+            .add_action(Action::DescribeBack, "back")
+            .add_action(Action::DescribeFold, "fold")
+            .add_actions(
+                &[Action::DescribeExpandAll, Action::DescribeCollapseAll],
+                "expand/collapse all",
+            )
+            .add_action(Action::DescribeCycleView, "tree/yaml/json")
+            .add_action(Action::DescribeSearch, "search")
+            .add_actions(
+                &[Action::DescribeNextMatch, Action::DescribePreviousMatch],
+                "next/prev match",
+            )
+            .add_action(Action::DescribeCopyPath, "copy path")
+            .build()
+    }
+
+    fn raw_lines(&self) -> &[String] {
+        match self.view {
+            View::Json => &self.json,
+            _ => &self.yaml,
+        }
     }
 
     fn down(&mut self) {
-        self.scroll += 1;
+        if self.view == View::Tree {
+            return self.tree.down();
+        }
+        let n_lines = self.raw_lines().len();
+        match self.raw_state.selected() {
+            None => self.raw_state.select(Some(0)),
+            Some(idx) => {
+                if idx + 1 < n_lines {
+                    self.raw_state.select(Some(idx + 1))
+                }
+            }
+        }
     }
 
     fn up(&mut self) {
-        if self.scroll > 0 {
-            self.scroll -= 1;
+        if self.view == View::Tree {
+            return self.tree.up();
         }
+        self.raw_state.select_previous();
     }
 
-    fn resolve_scroll(&mut self, height: &u16, n_lines: &u16) -> u16 {
-        let max_scroll = if *n_lines < (height / 2) {
-            0
+    fn top(&mut self) {
+        if self.view == View::Tree {
+            return self.tree.top();
+        }
+        self.raw_state.select_first();
+    }
+
+    fn bottom(&mut self) {
+        if self.view == View::Tree {
+            return self.tree.bottom();
+        }
+        let n_lines = self.raw_lines().len();
+        self.raw_state.select(Some(n_lines.saturating_sub(1)));
+    }
+
+    fn set_search(&mut self, search: Option<String>) {
+        self.search = search.filter(|s| !s.is_empty());
+        self.tree.set_search(self.search.clone());
+    }
+
+    /// Moves the selection to the next line or node matching the current search
+    fn next_match(&mut self, reverse: bool, include_current: bool) {
+        let found = if self.view == View::Tree {
+            self.tree.next_match(reverse, include_current)
         } else {
-            n_lines - (height / 2)
+            self.next_raw_match(reverse, include_current)
         };
-        if self.scroll > max_scroll {
-            self.scroll = max_scroll;
+        self.status = match (&self.search, found) {
+            (Some(s), false) => Some(format!("pattern not found: {s}")),
+            _ => None,
         };
-        self.scroll
+    }
+
+    fn next_raw_match(&mut self, reverse: bool, include_current: bool) -> bool {
+        let Some(search) = self.search.as_ref().map(|s| s.to_lowercase()) else {
+            return false;
+        };
+        let lines = self.raw_lines();
+        let n_lines = lines.len();
+        if n_lines == 0 {
+            return false;
+        }
+        let current = self.raw_state.selected().unwrap_or_default();
+
+        let start = if include_current { 0 } else { 1 };
+        let found = (start..=n_lines)
+            .map(|offset| {
+                if reverse {
+                    (current + n_lines * 2 - offset) % n_lines
+                } else {
+                    (current + offset) % n_lines
+                }
+            })
+            .find(|idx| lines[*idx].to_lowercase().contains(&search));
+
+        if let Some(idx) = found {
+            self.raw_state.select(Some(idx));
+        }
+        found.is_some()
+    }
+
+    fn copy_path(&mut self) -> Result<()> {
+        if self.view != View::Tree {
+            self.status = Some("paths can only be copied from the tree view".into());
+            return Ok(());
+        }
+        if let Some(path) = self.tree.selected_path() {
+            clipboard::copy(&path)?;
+            self.status = Some(format!("copied {path}"));
+        }
+        Ok(())
+    }
+
+    async fn update_search_input(&mut self, message: Key) -> Result<MessageResponse> {
+        let Some(input) = self.search_input.as_mut() else {
+            return Ok(MessageResponse::NotConsumed);
+        };
+        match message {
+            Key::Char(c) => input.push(c),
+            Key::Backspace => {
+                input.pop();
+            }
+            Key::Esc => {
+                self.search_input = None;
+                self.set_search(None);
+                return Ok(MessageResponse::Consumed);
+            }
+            Key::Enter => {
+                self.search_input = None;
+                self.next_match(false, true);
+                return Ok(MessageResponse::Consumed);
+            }
+            _ => return Ok(MessageResponse::NotConsumed),
+        }
+
+        // Incrementally search as the user types
+        let search = self.search_input.clone();
+        self.set_search(search);
+        self.next_match(false, true);
+        Ok(MessageResponse::Consumed)
     }
 }
 
 #[async_trait::async_trait]
 impl Page for DescribeContainer {
-    async fn update(&mut self, message: Key) -> Result<MessageResponse> {
-        let res = match message {
-            UP_KEY | K_KEY => {
+    async fn update(&mut self, input: Input) -> Result<MessageResponse> {
+        if self.search_input.is_some() {
+            let Some(key) = input.key() else {
+                return Ok(MessageResponse::NotConsumed);
+            };
+            return self.update_search_input(key).await;
+        }
+
+        if input != Input::Key(Key::Null) {
+            self.status = None;
+        }
+
+        let res = match self.config.keybindings.action(Scope::Describe, input) {
+            Some(Action::Up) => {
                 self.up();
                 MessageResponse::Consumed
             }
-            DOWN_KEY | J_KEY => {
+            Some(Action::Down) => {
                 self.down();
                 MessageResponse::Consumed
             }
-            Key::Esc => {
-                let transition = match self.cx.clone() {
-                    Some(cx) => match cx.then {
-                        Some(tr) => *tr.clone(),
-                        None => Transition::ToContainerPage(AppContext {
-                            describable: self.thing.clone(),
-                            ..Default::default()
-                        }),
-                    },
-                    None => Transition::ToContainerPage(AppContext {
-                        describable: self.thing.clone(),
-                        ..Default::default()
-                    }),
-                };
-
-                self.tx.send(Message::Transition(transition)).await?;
+            Some(Action::Top) => {
+                self.top();
+                MessageResponse::Consumed
+            }
+            Some(Action::Bottom) => {
+                self.bottom();
+                MessageResponse::Consumed
+            }
+            Some(Action::DescribeFold) if self.view == View::Tree => {
+                self.tree.toggle();
+                MessageResponse::Consumed
+            }
+            Some(Action::DescribeExpand) if self.view == View::Tree => {
+                self.tree.expand();
+                MessageResponse::Consumed
+            }
+            Some(Action::DescribeCollapse) if self.view == View::Tree => {
+                self.tree.collapse();
+                MessageResponse::Consumed
+            }
+            Some(Action::DescribeExpandAll) if self.view == View::Tree => {
+                self.tree.expand_all();
+                MessageResponse::Consumed
+            }
+            Some(Action::DescribeCollapseAll) if self.view == View::Tree => {
+                self.tree.collapse_all();
+                MessageResponse::Consumed
+            }
+            Some(Action::DescribeCycleView) => {
+                self.view = self.view.next();
+                self.raw_state.select(Some(0));
+                MessageResponse::Consumed
+            }
+            Some(Action::DescribeCopyPath) => {
+                self.copy_path()?;
+                MessageResponse::Consumed
+            }
+            Some(Action::DescribeSearch) => {
+                self.search_input = Some(String::new());
+                MessageResponse::Consumed
+            }
+            Some(Action::DescribeNextMatch) => {
+                self.next_match(false, false);
+                MessageResponse::Consumed
+            }
+            Some(Action::DescribePreviousMatch) => {
+                self.next_match(true, false);
+                MessageResponse::Consumed
+            }
+            Some(Action::DescribeBack) if self.search.is_some() => {
+                self.set_search(None);
+                MessageResponse::Consumed
+            }
+            Some(Action::DescribeBack) => {
+                self.tx.send(Message::Transition(Transition::Back)).await?;
                 MessageResponse::Consumed
             }
             _ => MessageResponse::NotConsumed,
@@ -131,14 +339,31 @@ impl Page for DescribeContainer {
                 bail!("no docker container")
             }
         };
-        self.thing_summary = Some(thing.describe()?);
+        let details = thing
+            .describe(&self.docker)
+            .await
+            .context("unable to describe resource")?;
+
+        self.tree.set_value(&details);
+        self.yaml = serde_yml::to_string(&details)
+            .context("failed to format description")?
+            .lines()
+            .map(String::from)
+            .collect();
+        self.json = serde_json::to_string_pretty(&details)
+            .context("failed to format description")?
+            .lines()
+            .map(String::from)
+            .collect();
+        self.raw_state = ListState::default();
+        self.raw_state.select(Some(0));
+
         let page_name = format!("Describe ({})", thing.get_name());
         self.page_help = Arc::new(Mutex::new(Self::build_page_help(
             self.config.clone(),
             Some(page_name),
         )));
         self.thing = Some(thing);
-        self.cx = Some(cx);
 
         Ok(())
     }
@@ -146,6 +371,28 @@ impl Page for DescribeContainer {
     fn get_help(&self) -> Arc<Mutex<PageHelp>> {
         self.page_help.clone()
     }
+
+    fn set_config(&mut self, config: Box<Config>) {
+        let name = self.page_help.lock().unwrap().get_name();
+        self.page_help = Arc::new(Mutex::new(Self::build_page_help(
+            config.clone(),
+            Some(name),
+        )));
+        self.tree.set_config(config.clone());
+        self.config = config;
+    }
+
+    fn is_capturing_input(&self) -> bool {
+        self.search_input.is_some()
+    }
+
+    fn view(&self) -> Option<PageView> {
+        Some(self.tree.view())
+    }
+
+    fn restore_view(&mut self, view: PageView) {
+        self.tree.restore_view(view);
+    }
 }
 
 #[async_trait::async_trait]
@@ -153,36 +400,42 @@ impl Close for DescribeContainer {}
 
 impl Component for DescribeContainer {
     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
-        if self.thing_summary.is_none() {
+        if self.thing.is_none() {
             return;
         }
-        let container_summary = self.thing_summary.as_ref().unwrap();
-        let lines: Vec<Line> = container_summary
-            .iter()
-            .map(|l| {
-                let l = l.clone();
-
-                let mut row = l.splitn(2, ':');
-                let key = String::from(row.next().unwrap_or(""));
-                let val = String::from(row.next().unwrap_or(""));
-
-                let key_style = Style::default().fg(self.config.theme.footer());
-
-                Line::from(vec![
-                    Span::from(key.clone()).style(key_style),
-                    Span::from(":"),
-                    Span::from(val.clone()),
-                ])
-            })
-            .collect();
-
-        let paragraph = Paragraph::new(lines);
 
-        let n_lines = paragraph.line_count(area.width) as u16;
+        let [body, status_bar] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
 
-        let scroll = self.resolve_scroll(&area.height, &n_lines);
+        if self.view == View::Tree {
+            self.tree.draw(f, body);
+        } else {
+            let match_style = self.config.theme.search_match();
+            let search = self.search.as_deref();
+            let lines: Vec<Line> = self
+                .raw_lines()
+                .iter()
+                .map(|l| Line::from(highlight(l, search, Style::default(), match_style)))
+                .collect();
+            let list = List::new(lines).highlight_style(self.config.theme.selected());
+            f.render_stateful_widget(list, body, &mut self.raw_state);
+        }
 
-        let paragraph = paragraph.scroll((scroll, 0));
-        f.render_widget(paragraph, area)
+        let status_style = self.config.theme.footer();
+        let status = if let Some(input) = &self.search_input {
+            Line::from(vec![Span::from("/"), Span::from(input.clone())])
+        } else if let Some(status) = &self.status {
+            Line::from(Span::from(status.clone()).style(status_style))
+        } else {
+            let path = match self.view {
+                View::Tree => self.tree.selected_path().unwrap_or_default(),
+                _ => String::new(),
+            };
+            Line::from(vec![
+                Span::from(format!("[{}] ", self.view.name())).style(status_style),
+                Span::from(path),
+            ])
+        };
+        f.render_widget(status, status_bar);
     }
 }
@@ -1,99 +1,235 @@
 use bollard::Docker;
-use color_eyre::eyre::{bail, Context, ContextCompat, Result};
-use futures::lock::Mutex as FutureMutex;
+use color_eyre::eyre::{bail, ContextCompat, Result};
+use futures::{lock::Mutex as FutureMutex, Future};
 use ratatui::{
     layout::Rect,
     prelude::*,
-    style::Style,
-    widgets::{Row, Table, TableState},
+    widgets::{Cell, Row, Table, TableState},
     Frame,
 };
 use std::{
-    collections::HashMap,
+    collections::HashSet,
     sync::{Arc, Mutex},
 };
-use tokio::sync::mpsc::Sender;
+use tokio::sync::{mpsc::Sender, watch};
 
 use crate::{
-    callbacks::delete_image::DeleteImage,
+    activity::ActivityKind,
+    callbacks::{
+        bulk_action::{BulkReport, BulkTask},
+        delete_image::DeleteImage,
+        BulkAction,
+    },
+    commands::Command,
     components::{
+        alert_modal::{AlertModal, ModalState as AlertState},
         boolean_modal::{BooleanModal, ModalState},
+        filter::Filter,
         help::{PageHelp, PageHelpBuilder},
     },
-    config::Config,
-    context::AppContext,
-    docker::image::DockerImage,
-    events::{message::MessageResponse, Key, Message, Transition},
+    config::{ColumnConfig, Config},
+    context::{AppContext, PageView},
+    docker::{
+        image::{DockerImage, ImageField},
+        prune::{PruneKind, PruneOptions},
+        store::{ResourceKind, ResourceStore},
+    },
+    events::{
+        message::MessageResponse, send_notification, Key, Message, Mouse, MouseKind, Notification,
+        Transition,
+    },
+    keymap::{Action, Input, Scope},
+    state::{SharedSession, SortDirection, SortOrder},
     traits::{Close, Component, ModalComponent, Page},
 };
 
 const NAME: &str = "Images";
 
-const UP_KEY: Key = Key::Up;
-const DOWN_KEY: Key = Key::Down;
+#[derive(Debug, Clone)]
+struct Column {
+    field: ImageField,
+    header: String,
+    constraint: Constraint,
+}
 
-const J_KEY: Key = Key::Char('j');
-const K_KEY: Key = Key::Char('k');
-const CTRL_D_KEY: Key = Key::Ctrl('d');
-const SHIFT_D_KEY: Key = Key::Char('D');
-const D_KEY: Key = Key::Char('d');
-const G_KEY: Key = Key::Char('g');
-const SHIFT_G_KEY: Key = Key::Char('G');
+impl Column {
+    fn from_config(config: &ColumnConfig) -> Option<Self> {
+        let field = config.field.parse::<ImageField>().ok()?;
+        Some(Self {
+            header: config.header.clone().unwrap_or_else(|| field.header()),
+            constraint: config.constraint().ok()?,
+            field,
+        })
+    }
+}
 
 #[derive(Debug)]
 enum ModalTypes {
     DeleteImage,
     ForceDeleteImage,
+    BulkAction,
+    BulkReport,
 }
 
 #[derive(Debug)]
 pub struct Images {
     pub name: String,
     tx: Sender<Message<Key, Transition>>,
+    config: Box<Config>,
     page_help: Arc<Mutex<PageHelp>>,
     docker: Docker,
+    store: ResourceStore,
+    /// Notified whenever the images in the store change
+    changes: watch::Receiver<()>,
     images: Vec<DockerImage>,
+    /// Indices into `images` of the rows which pass the filter
+    visible: Vec<usize>,
+    filter: Filter,
+    columns: Vec<Column>,
+    session: SharedSession,
     list_state: TableState,
+    /// Where the table was last drawn, to find the row which is clicked
+    table_area: Rect,
     modal: Option<BooleanModal<ModalTypes>>,
+    alert: Option<AlertModal<ModalTypes>>,
     show_dangling: bool,
+    /// Keys of the images marked for a bulk action
+    marked: HashSet<(String, String, String)>,
+    bulk_report: Arc<Mutex<Option<BulkReport>>>,
 }
 
 #[async_trait::async_trait]
 impl Page for Images {
-    async fn update(&mut self, message: Key) -> Result<MessageResponse> {
-        self.refresh().await?;
+    async fn update(&mut self, input: Input) -> Result<MessageResponse> {
+        self.refresh();
+
+        if let Some(m) = self.alert.as_mut() {
+            if let AlertState::Open(_) = m.state {
+                let Some(key) = input.key() else {
+                    return Ok(MessageResponse::NotConsumed);
+                };
+                let res = m.update(key).await;
+                if let AlertState::Closed = m.state {
+                    self.alert = None;
+                }
+                return res;
+            }
+        }
 
-        let res = self.update_modal(message).await?;
+        // Once a bulk action has completed the marks are spent, and we report the outcome
+        let report = self.bulk_report.lock().unwrap().take();
+        if let Some(report) = report {
+            self.marked.clear();
+            // Failures are listed in a modal so that they aren't missed
+            if report.has_failures() {
+                let mut alert =
+                    AlertModal::new(report.title(), ModalTypes::BulkReport, self.config.theme);
+                alert.initialise(report.message());
+                self.alert = Some(alert);
+            } else {
+                send_notification(
+                    self.tx.clone(),
+                    Notification::success(report.summary("images")),
+                )
+                .await?;
+            }
+        }
+
+        let res = self.update_modal(input).await?;
         if res == MessageResponse::Consumed {
             return Ok(res);
         }
 
-        let result = match message {
-            UP_KEY | K_KEY => {
+        if self.filter.update(input).is_consumed() {
+            let selected = self.get_image().ok().map(image_key);
+            self.apply_filter_and_sort(selected);
+            return Ok(MessageResponse::Consumed);
+        }
+
+        let result = match self.config.keybindings.action(Scope::Images, input) {
+            Some(Action::Up) => {
                 self.decrement_list();
                 MessageResponse::Consumed
             }
-            DOWN_KEY | J_KEY => {
+            Some(Action::Down) => {
                 self.increment_list();
                 MessageResponse::Consumed
             }
-            SHIFT_D_KEY => {
+            Some(Action::ImageToggleDangling) => {
                 self.show_dangling = !self.show_dangling;
+                self.changes.mark_changed();
+                self.refresh();
                 MessageResponse::Consumed
             }
-            G_KEY => {
+            Some(Action::Top) => {
                 self.list_state.select(Some(0));
                 MessageResponse::Consumed
             }
-            SHIFT_G_KEY => {
-                self.list_state.select(Some(self.images.len() - 1));
+            Some(Action::Sort) => {
+                self.cycle_sort_column();
                 MessageResponse::Consumed
             }
-            CTRL_D_KEY => match self.delete_image(false, None, None) {
+            Some(Action::ReverseSort) => {
+                self.reverse_sort_direction();
+                MessageResponse::Consumed
+            }
+            Some(Action::Bottom) => {
+                self.list_state
+                    .select(Some(self.visible.len().saturating_sub(1)));
+                MessageResponse::Consumed
+            }
+            Some(Action::Mark) => {
+                self.toggle_mark();
+                MessageResponse::Consumed
+            }
+            Some(Action::MarkDown) => {
+                self.mark();
+                self.increment_list();
+                MessageResponse::Consumed
+            }
+            Some(Action::MarkUp) => {
+                self.mark();
+                self.decrement_list();
+                MessageResponse::Consumed
+            }
+            Some(Action::MarkAll) => {
+                self.toggle_mark_all();
+                MessageResponse::Consumed
+            }
+            Some(Action::Clear) if !self.marked.is_empty() => {
+                self.marked.clear();
+                MessageResponse::Consumed
+            }
+            Some(Action::ImageDelete) if !self.marked.is_empty() => {
+                self.confirm_bulk_action(ActivityKind::Delete, |i, docker| async move {
+                    i.delete(&docker, false).await
+                });
+                MessageResponse::Consumed
+            }
+            Some(Action::ImagePull) => {
+                self.confirm_bulk_action(ActivityKind::Pull, |i, docker| async move {
+                    i.pull(&docker).await
+                });
+                MessageResponse::Consumed
+            }
+            Some(Action::ImagePrune) => {
+                let cx = AppContext {
+                    prune_options: Some(PruneOptions {
+                        kind: PruneKind::Images,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                };
+                self.tx
+                    .send(Message::Transition(Transition::ToPrunePage(cx)))
+                    .await?;
+                MessageResponse::Consumed
+            }
+            Some(Action::ImageDelete) => match self.delete_image(false, None, None) {
                 Ok(_) => MessageResponse::Consumed,
                 Err(_) => MessageResponse::NotConsumed,
             },
-            D_KEY => {
+            Some(Action::ImageDescribe) => {
                 self.tx
                     .send(Message::Transition(Transition::ToDescribeContainerPage(
                         self.get_context()?,
@@ -110,7 +246,7 @@ impl Page for Images {
         self.list_state = TableState::default();
         self.list_state.select(Some(0));
 
-        self.refresh().await.context("unable to refresh images")?;
+        self.refresh();
 
         // If a context has been passed in, choose that item in list
         // this ist to allo logs, attach etc to appear to revert to previous
@@ -125,11 +261,12 @@ impl Page for Images {
             return Ok(());
         }
 
-        for (idx, c) in self.images.iter().enumerate() {
-            if c.id == image_id {
-                self.list_state.select(Some(idx));
-                break;
-            }
+        if let Some(position) = self
+            .visible
+            .iter()
+            .position(|idx| self.images[*idx].id == image_id)
+        {
+            self.list_state.select(Some(position));
         }
 
         Ok(())
@@ -138,44 +275,242 @@ impl Page for Images {
     fn get_help(&self) -> Arc<Mutex<PageHelp>> {
         self.page_help.clone()
     }
+
+    fn set_config(&mut self, config: Box<Config>) {
+        self.page_help = Arc::new(Mutex::new(Self::build_page_help(config.clone())));
+        self.columns = Self::build_columns(&config);
+        self.filter.set_config(
+            config.clone(),
+            self.columns.iter().map(|c| c.field.key()).collect(),
+        );
+        self.config = config;
+
+        let selected = self.get_image().ok().map(image_key);
+        self.apply_filter_and_sort(selected);
+    }
+
+    fn view(&self) -> Option<PageView> {
+        Some(PageView::from_table(&self.list_state))
+    }
+
+    fn restore_view(&mut self, view: PageView) {
+        view.restore_table(&mut self.list_state, self.visible.len());
+    }
+
+    fn selection(&self) -> Option<AppContext> {
+        self.get_context().ok()
+    }
+
+    /// Clicking a row selects it, and double-clicking it describes it
+    async fn mouse(&mut self, mouse: Mouse) -> Result<MessageResponse> {
+        let Some(idx) = mouse
+            .row_in(self.table_area, 1, self.list_state.offset())
+            .filter(|idx| *idx < self.visible.len())
+        else {
+            return Ok(MessageResponse::NotConsumed);
+        };
+        self.list_state.select(Some(idx));
+        match mouse.kind {
+            MouseKind::DoubleClick => self.update(Action::ImageDescribe.into()).await,
+            _ => Ok(MessageResponse::Consumed),
+        }
+    }
+
+    fn is_capturing_input(&self) -> bool {
+        self.filter.is_editing() || self.modal.is_some() || self.alert.is_some()
+    }
+
+    async fn command(&mut self, command: Command) -> Result<()> {
+        match command {
+            Command::Filter { query } => {
+                self.filter.set(&query);
+                let selected = self.get_image().ok().map(image_key);
+                self.apply_filter_and_sort(selected);
+            }
+            Command::Sort { column, direction } => {
+                let field = column.parse::<ImageField>()?;
+                let direction = direction.unwrap_or_else(|| match self.sort_order() {
+                    (current, direction) if current == field => direction,
+                    _ => SortDirection::default(),
+                });
+                self.set_sort_order(field, direction);
+            }
+            command => bail!("\"{}\" is not supported on this page", command.name()),
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
 impl Close for Images {}
 
 impl Images {
-    pub fn new(docker: Docker, tx: Sender<Message<Key, Transition>>, config: Box<Config>) -> Self {
-        let page_help = PageHelpBuilder::new(NAME.into(), config.clone())
-            .add_input(format!("{CTRL_D_KEY}"), "delete".into())
-            .add_input(format!("{G_KEY}"), "top".into())
-            .add_input(format!("{SHIFT_G_KEY}"), "bottom".into())
-            .add_input(format!("{SHIFT_D_KEY}"), "dangling".into())
-            .add_input(format!("{D_KEY}"), "describe".into())
-            .build();
+    pub fn new(
+        docker: Docker,
+        tx: Sender<Message<Key, Transition>>,
+        config: Box<Config>,
+        session: SharedSession,
+        store: ResourceStore,
+    ) -> Self {
+        let page_help = Self::build_page_help(config.clone());
+        let columns = Self::build_columns(&config);
 
         Self {
             name: String::from(NAME),
             tx,
+            config: config.clone(),
             page_help: Arc::new(Mutex::new(page_help)),
             docker,
+            changes: store.subscribe(ResourceKind::Images),
+            store,
             images: vec![],
+            visible: vec![],
+            filter: Filter::new(config)
+                .with_columns(columns.iter().map(|c| c.field.key()).collect()),
+            columns,
+            session,
             list_state: TableState::default(),
+            table_area: Rect::default(),
             modal: None,
+            alert: None,
             show_dangling: false,
+            marked: HashSet::new(),
+            bulk_report: Arc::new(Mutex::new(None)),
         }
     }
 
-    async fn refresh(&mut self) -> Result<(), color_eyre::eyre::Error> {
-        let mut filters: HashMap<String, Vec<String>> = HashMap::new();
-        filters.insert("dangling".into(), vec!["false".into()]);
+    fn build_page_help(config: Box<Config>) -> PageHelp {
+        PageHelpBuilder::new(NAME.into(), config)
+            .add_action(Action::ImageDelete, "delete")
+            .add_action(Action::ImagePull, "pull")
+            .add_action(Action::Mark, "mark")
+            .add_actions(&[Action::MarkDown, Action::MarkUp], "mark range")
+            .add_action(Action::MarkAll, "mark all")
+            .add_action(Action::Top, "top")
+            .add_action(Action::Bottom, "bottom")
+            .add_action(Action::ImageToggleDangling, "dangling")
+            .add_action(Action::ImagePrune, "prune")
+            .add_action(Action::ImageDescribe, "describe")
+            .add_action(Action::Filter, "filter")
+            .add_actions(&[Action::Sort, Action::ReverseSort], "sort/reverse")
+            .add_action(Action::PreviewToggle, "preview")
+            .build()
+    }
 
-        self.images = DockerImage::list(&self.docker, self.show_dangling)
-            .await
-            .context("unable to retrieve list of images")?;
-        Ok(())
+    fn build_columns(config: &Config) -> Vec<Column> {
+        config
+            .columns
+            .images
+            .iter()
+            .filter_map(Column::from_config)
+            .collect()
     }
 
-    async fn update_modal(&mut self, message: Key) -> Result<MessageResponse> {
+    /// Copies the images from the store, if they have changed since they were last
+    /// copied; this never waits on the daemon, so is cheap enough to do on every key
+    fn refresh(&mut self) {
+        if !self.changes.has_changed().unwrap_or(false) {
+            return;
+        }
+        self.changes.mark_unchanged();
+
+        let selected = self.get_image().ok().map(image_key);
+        self.images = self
+            .store
+            .images()
+            .into_iter()
+            .filter(|i| self.show_dangling || !i.is_dangling())
+            .collect();
+        let images = &self.images;
+        self.marked
+            .retain(|key| images.iter().any(|i| image_key(i) == *key));
+        self.apply_filter_and_sort(selected);
+    }
+
+    /// Recomputes the rows which pass the filter and puts them in the selected sort
+    /// order, keeping the selection on the given image if it is still visible
+    fn apply_filter_and_sort(&mut self, selected: Option<(String, String, String)>) {
+        self.visible = self
+            .images
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| self.filter.matches_row(&self.cells(i)))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        let (field, direction) = self.sort_order();
+        let images = &self.images;
+        self.visible.sort_by(|a, b| {
+            let ordering = images[*a].compare_field(&images[*b], &field);
+            match direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            }
+        });
+
+        let position = selected.and_then(|key| {
+            self.visible
+                .iter()
+                .position(|idx| image_key(&self.images[*idx]) == key)
+        });
+
+        match position {
+            Some(position) => self.list_state.select(Some(position)),
+            None => {
+                let idx = self.list_state.selected().unwrap_or_default();
+                self.list_state
+                    .select(Some(idx.min(self.visible.len().saturating_sub(1))));
+            }
+        }
+    }
+
+    fn cells(&self, i: &DockerImage) -> Vec<String> {
+        self.columns.iter().map(|col| i.field(&col.field)).collect()
+    }
+
+    /// Images are sorted by ID until the user picks a column
+    fn sort_order(&self) -> (ImageField, SortDirection) {
+        self.session
+            .lock()
+            .unwrap()
+            .sort_order(NAME)
+            .and_then(|order| {
+                let field = order.column.parse::<ImageField>().ok()?;
+                Some((field, order.direction))
+            })
+            .unwrap_or((ImageField::Id, SortDirection::Ascending))
+    }
+
+    fn set_sort_order(&mut self, field: ImageField, direction: SortDirection) {
+        self.session.lock().unwrap().set_sort_order(
+            NAME,
+            SortOrder {
+                column: field.key(),
+                direction,
+            },
+        );
+        let selected = self.get_image().ok().map(image_key);
+        self.apply_filter_and_sort(selected);
+    }
+
+    fn cycle_sort_column(&mut self) {
+        if self.columns.is_empty() {
+            return;
+        }
+        let (field, direction) = self.sort_order();
+        let idx = match self.columns.iter().position(|c| c.field == field) {
+            Some(idx) => (idx + 1) % self.columns.len(),
+            None => 0,
+        };
+        self.set_sort_order(self.columns[idx].field.clone(), direction);
+    }
+
+    fn reverse_sort_direction(&mut self) {
+        let (field, direction) = self.sort_order();
+        self.set_sort_order(field, direction.reverse());
+    }
+
+    async fn update_modal(&mut self, input: Input) -> Result<MessageResponse> {
         // Due to the fact only 1 thing should be operating at a time, we can do this to reduce unnecessary nesting
         if self.modal.is_none() {
             return Ok(MessageResponse::NotConsumed);
@@ -185,7 +520,10 @@ impl Images {
         )?;
 
         if let ModalState::Open(_) = m.state {
-            match m.update(message).await {
+            let Some(key) = input.key() else {
+                return Ok(MessageResponse::NotConsumed);
+            };
+            match m.update(key).await {
                 Ok(_) => {
                     if let ModalState::Closed = m.state {
                         self.modal = None
@@ -215,7 +553,7 @@ impl Images {
         match current_idx {
             None => self.list_state.select(Some(0)),
             Some(current_idx) => {
-                if !self.images.is_empty() && current_idx < self.images.len() - 1 {
+                if !self.visible.is_empty() && current_idx < self.visible.len() - 1 {
                     self.list_state.select(Some(current_idx + 1))
                 }
             }
@@ -235,8 +573,8 @@ impl Images {
     }
 
     fn get_image(&self) -> Result<&DockerImage> {
-        if let Some(image_idx) = self.list_state.selected() {
-            if let Some(image) = self.images.get(image_idx) {
+        if let Some(row_idx) = self.list_state.selected() {
+            if let Some(image) = self.visible.get(row_idx).map(|idx| &self.images[*idx]) {
                 return Ok(image);
             }
         }
@@ -257,6 +595,8 @@ impl Images {
                 self.docker.clone(),
                 image.clone(),
                 force,
+                self.session.clone(),
+                self.tx.clone(),
             )));
 
             let mut modal = BooleanModal::<ModalTypes>::new(
@@ -265,6 +605,7 @@ impl Images {
                     Some(t) => t,
                     None => ModalTypes::DeleteImage,
                 },
+                self.config.theme,
             );
 
             modal.initialise(
@@ -281,17 +622,91 @@ impl Images {
         Ok(())
     }
 
+    fn mark(&mut self) {
+        if let Ok(image) = self.get_image() {
+            let key = image_key(image);
+            self.marked.insert(key);
+        }
+    }
+
+    fn toggle_mark(&mut self) {
+        if let Ok(image) = self.get_image() {
+            let key = image_key(image);
+            if !self.marked.remove(&key) {
+                self.marked.insert(key);
+            }
+        }
+    }
+
+    /// Marks every row which passes the filter, or unmarks them if they are all marked already
+    fn toggle_mark_all(&mut self) {
+        let keys = self
+            .visible
+            .iter()
+            .map(|idx| image_key(&self.images[*idx]))
+            .collect::<Vec<_>>();
+        if keys.iter().all(|key| self.marked.contains(key)) {
+            keys.iter().for_each(|key| {
+                self.marked.remove(key);
+            });
+        } else {
+            self.marked.extend(keys);
+        }
+    }
+
+    /// Opens a single confirmation modal for applying the given action to all marked
+    /// images, or to the selected image if none are marked
+    fn confirm_bulk_action<F, Fut>(&mut self, kind: ActivityKind, action: F)
+    where
+        F: Fn(DockerImage, Docker) -> Fut + Clone + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let images: Vec<DockerImage> = if self.marked.is_empty() {
+            self.get_image().into_iter().cloned().collect()
+        } else {
+            self.images
+                .iter()
+                .filter(|i| self.marked.contains(&image_key(i)))
+                .cloned()
+                .collect()
+        };
+        if images.is_empty() {
+            return;
+        }
+
+        let targets = images
+            .into_iter()
+            .map(|image| {
+                let name = image.get_full_name();
+                let docker = self.docker.clone();
+                let action = action.clone();
+                let task: BulkTask =
+                    Box::new(move || Box::pin(action(image.clone(), docker.clone())));
+                (name, task)
+            })
+            .collect();
+
+        let cb = BulkAction::new(
+            kind,
+            "image",
+            targets,
+            self.bulk_report.clone(),
+            self.session.clone(),
+            self.tx.clone(),
+        );
+        let message = cb.confirmation();
+
+        let mut modal =
+            BooleanModal::<ModalTypes>::new(cb.title(), ModalTypes::BulkAction, self.config.theme);
+        modal.initialise(message, Some(Arc::new(FutureMutex::new(cb))));
+        self.modal = Some(modal);
+    }
+
     fn get_context(&self) -> Result<AppContext> {
         let image = self.get_image()?;
 
-        let then = Some(Box::new(Transition::ToImagePage(AppContext {
-            docker_image: Some(image.clone()),
-            ..Default::default()
-        })));
-
         let cx = AppContext {
             describable: Some(Box::new(image.clone())),
-            then,
             ..Default::default()
         };
 
@@ -301,43 +716,83 @@ impl Images {
 
 impl Component for Images {
     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
-        let rows = get_image_rows(&self.images);
-        let columns = Row::new(vec!["ID", "Name", "Tag", "Created", "Size"]);
+        let (area, filter_area) = if self.filter.is_open() {
+            let [area, filter_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+            (area, Some(filter_area))
+        } else {
+            (area, None)
+        };
 
-        let widths = [
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-        ];
+        let show_marks = !self.marked.is_empty();
+        let rows = self.visible.iter().map(|idx| {
+            let image = &self.images[*idx];
+            let mut cells = self
+                .cells(image)
+                .iter()
+                .enumerate()
+                .map(|(idx, v)| Cell::from(self.filter.highlight(idx, v)))
+                .collect::<Vec<Cell>>();
+            if show_marks {
+                let marker = if self.marked.contains(&image_key(image)) {
+                    "●"
+                } else {
+                    ""
+                };
+                cells.insert(0, Cell::from(marker));
+            }
+            Row::new(cells)
+        });
+
+        let (sort_field, sort_direction) = self.sort_order();
+        let mut headers = self
+            .columns
+            .iter()
+            .map(|c| {
+                if c.field == sort_field {
+                    format!("{} {}", c.header, sort_direction.indicator())
+                } else {
+                    c.header.clone()
+                }
+            })
+            .collect::<Vec<String>>();
+        let mut widths = self
+            .columns
+            .iter()
+            .map(|c| c.constraint)
+            .collect::<Vec<Constraint>>();
+        if show_marks {
+            headers.insert(0, String::new());
+            widths.insert(0, Constraint::Length(2));
+        }
+        let columns = Row::new(headers);
 
-        let table = Table::new(rows.clone(), widths)
-            .header(columns.clone().style(Style::new().bold()))
-            .highlight_style(Style::new().reversed());
+        let table = Table::new(rows, widths)
+            .header(columns.clone().style(self.config.theme.table_header()))
+            .highlight_style(self.config.theme.selected());
 
+        self.table_area = area;
         f.render_stateful_widget(table, area, &mut self.list_state);
 
+        if let Some(filter_area) = filter_area {
+            self.filter.draw(f, filter_area);
+        }
+
         if let Some(m) = self.modal.as_mut() {
             if let ModalState::Open(_) = m.state {
                 m.draw(f, area)
             }
         }
+
+        if let Some(m) = self.alert.as_mut() {
+            if let AlertState::Open(_) = m.state {
+                m.draw(f, area)
+            }
+        }
     }
 }
 
-fn get_image_rows(containers: &[DockerImage]) -> Vec<Row> {
-    let rows = containers
-        .iter()
-        .map(|c| {
-            Row::new(vec![
-                c.id.clone(),
-                c.name.clone(),
-                c.tag.clone(),
-                c.created.clone(),
-                c.size.clone(),
-            ])
-        })
-        .collect::<Vec<Row>>();
-    rows
+/// Images are listed once per tag, so the id alone doesn't identify a row
+fn image_key(i: &DockerImage) -> (String, String, String) {
+    (i.id.clone(), i.name.clone(), i.tag.clone())
 }
@@ -10,25 +10,17 @@ use ratatui::{layout::Rect, Frame};
 use tokio::sync::mpsc::Sender;
 
 use crate::config::Config;
-use crate::context::AppContext;
+use crate::context::{AppContext, PageView};
 use crate::{
     components::help::{PageHelp, PageHelpBuilder},
     docker::{container::DockerContainer, logs::DockerLogs},
     events::{message::MessageResponse, Key, Message, Transition},
+    keymap::{Action, Input, Scope},
     traits::{Close, Component, Page},
 };
 
 const NAME: &str = "Logs";
 
-const ESC_KEY: Key = Key::Esc;
-const J_KEY: Key = Key::Char('j');
-const UP_KEY: Key = Key::Up;
-const K_KEY: Key = Key::Char('k');
-const DOWN_KEY: Key = Key::Down;
-const SPACE_BAR: Key = Key::Char(' ');
-const G_KEY: Key = Key::Char('g');
-const SHIFT_G_KEY: Key = Key::Char('G');
-
 #[derive(Debug)]
 pub struct Logs {
     config: Box<Config>,
@@ -41,7 +33,6 @@ pub struct Logs {
     log_streamer_handle: Option<JoinHandle<()>>,
     list_state: ListState,
     auto_scroll: bool,
-    next: Option<Transition>,
 }
 
 impl Logs {
@@ -63,15 +54,14 @@ impl Logs {
             log_streamer_handle: None,
             list_state: ListState::default(),
             auto_scroll: true,
-            next: None,
         }
     }
 
     fn build_page_help(config: Box<Config>) -> PageHelpBuilder {
         PageHelpBuilder::new(NAME.into(), config)
-            .add_input(format!("{ESC_KEY}"), "back".into())
-            .add_input(format!("{G_KEY}"), "top".into())
-            .add_input(format!("{SHIFT_G_KEY}"), "bottom".into())
+            .add_action(Action::LogsBack, "back")
+            .add_action(Action::Top, "top")
+            .add_action(Action::Bottom, "bottom")
     }
 
     fn activate_auto_scroll(&mut self) {
@@ -79,9 +69,7 @@ impl Logs {
             return;
         }
         self.auto_scroll = true;
-        self.page_help = Arc::new(Mutex::new(
-            Self::build_page_help(self.config.clone()).build(),
-        ));
+        self.page_help = Arc::new(Mutex::new(self.current_page_help()));
     }
 
     fn deactivate_auto_scroll(&mut self) {
@@ -89,50 +77,49 @@ impl Logs {
             return;
         }
         self.auto_scroll = false;
-        self.page_help = Arc::new(Mutex::new(
-            Self::build_page_help(self.config.clone())
-                .add_input(format!("{SPACE_BAR}"), "auto-scroll".into())
-                .build(),
-        ));
+        self.page_help = Arc::new(Mutex::new(self.current_page_help()));
+    }
+
+    /// The help for the page, which offers to turn auto-scroll back on when it is off
+    fn current_page_help(&self) -> PageHelp {
+        let page_help = Self::build_page_help(self.config.clone());
+        if self.auto_scroll {
+            page_help.build()
+        } else {
+            page_help
+                .add_action(Action::LogsToggleAutoscroll, "auto-scroll")
+                .build()
+        }
     }
 }
 
 #[async_trait::async_trait]
 impl Page for Logs {
-    async fn update(&mut self, message: Key) -> Result<MessageResponse> {
-        let res = match message {
-            Key::Esc => {
-                let transition = if let Some(t) = self.next.clone() {
-                    t
-                } else {
-                    Transition::ToContainerPage(AppContext {
-                        docker_container: self.container.clone(),
-                        ..Default::default()
-                    })
-                };
-
-                self.tx.send(Message::Transition(transition)).await?;
+    async fn update(&mut self, input: Input) -> Result<MessageResponse> {
+        let res = match self.config.keybindings.action(Scope::Logs, input) {
+            Some(Action::LogsBack) => {
+                self.tx.send(Message::Transition(Transition::Back)).await?;
                 MessageResponse::Consumed
             }
-            G_KEY => {
+            Some(Action::Top) => {
                 self.list_state.select_first();
                 MessageResponse::Consumed
             }
-            SHIFT_G_KEY => {
+            Some(Action::Bottom) => {
                 self.list_state.select_last();
                 MessageResponse::Consumed
             }
-            J_KEY | DOWN_KEY => {
+            Some(Action::Down) => {
                 self.list_state.select_next();
                 self.deactivate_auto_scroll();
                 MessageResponse::Consumed
             }
-            K_KEY | UP_KEY => {
+            Some(Action::Up) => {
                 self.list_state.select_previous();
                 self.deactivate_auto_scroll();
                 MessageResponse::Consumed
             }
-            SPACE_BAR => {
+            Some(Action::LogsToggleAutoscroll) => {
                 self.activate_auto_scroll();
                 MessageResponse::Consumed
             }
@@ -154,7 +141,7 @@ impl Page for Logs {
         }
         self.auto_scroll = true;
         if let Some(logs) = &self.logs {
-            let mut logs_stream = logs.get_log_stream(&self.docker, 50);
+            let mut logs_stream = logs.get_log_stream(&self.docker, 50, cx.log_since);
             let tx = self.tx.clone();
             let log_messages = self.log_messages.clone();
             self.log_streamer_handle = Some(tokio::spawn(async move {
@@ -169,16 +156,38 @@ impl Page for Logs {
             bail!("unable to stream logs without logs to stream");
         }
 
-        if let Some(t) = cx.next() {
-            self.next = Some(t)
-        }
-
         Ok(())
     }
 
     fn get_help(&self) -> Arc<Mutex<PageHelp>> {
         self.page_help.clone()
     }
+
+    fn set_config(&mut self, config: Box<Config>) {
+        self.config = config;
+        self.page_help = Arc::new(Mutex::new(self.current_page_help()));
+    }
+
+    /// Nothing is recorded while following the logs, so that they are followed
+    /// again on returning to the page
+    fn view(&self) -> Option<PageView> {
+        (!self.auto_scroll).then(|| PageView::from_list(&self.list_state))
+    }
+
+    /// The logs are streamed in again after the view is restored, so the selection
+    /// is left for the list to keep within the logs as they arrive
+    fn restore_view(&mut self, view: PageView) {
+        self.deactivate_auto_scroll();
+        self.list_state = ListState::default()
+            .with_offset(view.offset)
+            .with_selected(view.selected);
+    }
+
+    fn is_streaming(&self) -> bool {
+        self.log_streamer_handle
+            .as_ref()
+            .is_some_and(|h| !h.is_finished())
+    }
 }
 
 #[async_trait::async_trait]
@@ -1,5 +1,8 @@
+pub mod activity;
 pub mod attach;
 pub mod containers;
 pub mod describe;
 pub mod images;
 pub mod logs;
+pub mod prune;
+pub mod split;
@@ -0,0 +1,458 @@
+use std::sync::{Arc, Mutex};
+
+use bollard::Docker;
+use color_eyre::eyre::{Context, Result};
+use futures::lock::Mutex as FutureMutex;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Cell, Row, Table, TableState},
+    Frame,
+};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    callbacks::{prune::PruneOutcome, PruneResources},
+    components::{
+        alert_modal::{AlertModal, ModalState as AlertState},
+        boolean_modal::{BooleanModal, ModalState},
+        help::{PageHelp, PageHelpBuilder},
+    },
+    config::Config,
+    context::{AppContext, PageView},
+    docker::{
+        prune::{preview, PruneCandidate, PruneKind, PruneOptions},
+        util::{format_size, format_timestamp},
+    },
+    events::{message::MessageResponse, Key, Message, Mouse, Transition},
+    keymap::{Action, Input, Scope},
+    state::SharedSession,
+    traits::{Close, Component, ModalComponent, Page},
+};
+
+const NAME: &str = "Prune";
+
+#[derive(Debug)]
+enum ModalTypes {
+    Prune,
+    PruneReport,
+}
+
+/// Previews and prunes unused resources of a given kind
+#[derive(Debug)]
+pub struct Prune {
+    docker: Docker,
+    config: Box<Config>,
+    tx: Sender<Message<Key, Transition>>,
+    session: SharedSession,
+    page_help: Arc<Mutex<PageHelp>>,
+    options: PruneOptions,
+    candidates: Vec<PruneCandidate>,
+    table_state: TableState,
+    /// Where the table was last drawn, to find the row which is clicked
+    table_area: Rect,
+    filter_input: Option<String>,
+    status: Option<String>,
+    modal: Option<BooleanModal<ModalTypes>>,
+    alert: Option<AlertModal<ModalTypes>>,
+    outcome: Arc<Mutex<Option<PruneOutcome>>>,
+}
+
+#[async_trait::async_trait]
+impl Page for Prune {
+    async fn update(&mut self, input: Input) -> Result<MessageResponse> {
+        if let Some(m) = self.alert.as_mut() {
+            if let AlertState::Open(_) = m.state {
+                let Some(key) = input.key() else {
+                    return Ok(MessageResponse::NotConsumed);
+                };
+                let res = m.update(key).await;
+                if let AlertState::Closed = m.state {
+                    self.alert = None;
+                }
+                return res;
+            }
+        }
+
+        let outcome = self.outcome.lock().unwrap().take();
+        if let Some(outcome) = outcome {
+            let alert = match outcome {
+                Ok(report) => {
+                    let mut alert =
+                        AlertModal::new(report.title(), ModalTypes::PruneReport, self.config.theme);
+                    alert.initialise(report.message());
+                    alert
+                }
+                Err(e) => {
+                    let mut alert = AlertModal::new(
+                        "Prune failed".into(),
+                        ModalTypes::PruneReport,
+                        self.config.theme,
+                    );
+                    alert.initialise(e);
+                    alert
+                }
+            };
+            self.refresh().await;
+            self.alert = Some(alert);
+            return Ok(MessageResponse::Consumed);
+        }
+
+        if let Some(m) = self.modal.as_mut() {
+            if let ModalState::Open(_) = m.state {
+                let Some(key) = input.key() else {
+                    return Ok(MessageResponse::NotConsumed);
+                };
+                let res = m.update(key).await;
+                if let ModalState::Closed = m.state {
+                    self.modal = None;
+                }
+                return res;
+            }
+        }
+
+        if self.filter_input.is_some() {
+            let Some(key) = input.key() else {
+                return Ok(MessageResponse::NotConsumed);
+            };
+            return Ok(self.update_filter_input(key).await);
+        }
+
+        let result = match self.config.keybindings.action(Scope::Prune, input) {
+            Some(Action::Up) => {
+                let idx = self.table_state.selected().unwrap_or_default();
+                self.table_state.select(Some(idx.saturating_sub(1)));
+                MessageResponse::Consumed
+            }
+            Some(Action::Down) => {
+                let idx = self.table_state.selected().map_or(0, |i| i + 1);
+                self.table_state
+                    .select(Some(idx.min(self.candidates.len().saturating_sub(1))));
+                MessageResponse::Consumed
+            }
+            Some(Action::Top) => {
+                self.table_state.select(Some(0));
+                MessageResponse::Consumed
+            }
+            Some(Action::Bottom) => {
+                self.table_state
+                    .select(Some(self.candidates.len().saturating_sub(1)));
+                MessageResponse::Consumed
+            }
+            Some(Action::PruneNextKind) => {
+                self.options.kind = self.options.kind.next();
+                self.refresh().await;
+                MessageResponse::Consumed
+            }
+            Some(Action::PrunePreviousKind) => {
+                self.options.kind = self.options.kind.previous();
+                self.refresh().await;
+                MessageResponse::Consumed
+            }
+            Some(Action::PruneToggleAll) => {
+                if self.options.kind.scope(self.options.all).is_some() {
+                    self.options.all = !self.options.all;
+                    self.refresh().await;
+                }
+                MessageResponse::Consumed
+            }
+            Some(Action::PruneFilters) => {
+                self.filter_input = Some(self.options.filters_string());
+                MessageResponse::Consumed
+            }
+            Some(Action::PruneConfirm) => {
+                self.confirm_prune();
+                MessageResponse::Consumed
+            }
+            Some(Action::PruneBack) => {
+                self.tx.send(Message::Transition(Transition::Back)).await?;
+                MessageResponse::Consumed
+            }
+            _ => MessageResponse::NotConsumed,
+        };
+        Ok(result)
+    }
+
+    async fn initialise(&mut self, cx: AppContext) -> Result<()> {
+        if let Some(options) = cx.prune_options {
+            self.options = options;
+        }
+        self.refresh().await;
+        Ok(())
+    }
+
+    fn get_help(&self) -> Arc<Mutex<PageHelp>> {
+        self.page_help.clone()
+    }
+
+    fn set_config(&mut self, config: Box<Config>) {
+        self.page_help = Arc::new(Mutex::new(Self::build_page_help(config.clone())));
+        self.config = config;
+    }
+
+    fn is_capturing_input(&self) -> bool {
+        self.filter_input.is_some() || self.modal.is_some() || self.alert.is_some()
+    }
+
+    fn view(&self) -> Option<PageView> {
+        Some(PageView::from_table(&self.table_state))
+    }
+
+    fn restore_view(&mut self, view: PageView) {
+        view.restore_table(&mut self.table_state, self.candidates.len());
+    }
+
+    async fn mouse(&mut self, mouse: Mouse) -> Result<MessageResponse> {
+        let Some(idx) = mouse
+            .row_in(self.table_area, 1, self.table_state.offset())
+            .filter(|idx| *idx < self.candidates.len())
+        else {
+            return Ok(MessageResponse::NotConsumed);
+        };
+        self.table_state.select(Some(idx));
+        Ok(MessageResponse::Consumed)
+    }
+}
+
+#[async_trait::async_trait]
+impl Close for Prune {}
+
+impl Prune {
+    pub fn new(
+        docker: Docker,
+        tx: Sender<Message<Key, Transition>>,
+        config: Box<Config>,
+        session: SharedSession,
+    ) -> Self {
+        let page_help = Self::build_page_help(config.clone());
+
+        Self {
+            docker,
+            config,
+            tx,
+            session,
+            page_help: Arc::new(Mutex::new(page_help)),
+            options: PruneOptions::default(),
+            candidates: vec![],
+            table_state: TableState::default(),
+            table_area: Rect::default(),
+            filter_input: None,
+            status: None,
+            modal: None,
+            alert: None,
+            outcome: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn build_page_help(config: Box<Config>) -> PageHelp {
+        PageHelpBuilder::new(NAME.into(), config)
+            .add_action(Action::PruneBack, "back")
+            .add_actions(&[Action::PrunePreviousKind, Action::PruneNextKind], "kind")
+            .add_action(Action::PruneToggleAll, "dangling/all")
+            .add_action(Action::PruneFilters, "filters")
+            .add_action(Action::PruneConfirm, "prune")
+            .build()
+    }
+
+    /// Recomputes the preview; failures are shown in the status line rather than
+    /// closing the page, so that the user is able to pick different options
+    async fn refresh(&mut self) {
+        match preview(&self.docker, &self.options)
+            .await
+            .context("unable to preview prune")
+        {
+            Ok(candidates) => {
+                self.candidates = candidates;
+                self.status = None;
+            }
+            Err(e) => {
+                self.candidates = vec![];
+                self.status = Some(format!("{e:#}"));
+            }
+        }
+        let idx = self.table_state.selected().unwrap_or_default();
+        self.table_state
+            .select(Some(idx.min(self.candidates.len().saturating_sub(1))));
+    }
+
+    async fn update_filter_input(&mut self, message: Key) -> MessageResponse {
+        let Some(input) = self.filter_input.as_mut() else {
+            return MessageResponse::NotConsumed;
+        };
+        match message {
+            Key::Char(c) => input.push(c),
+            Key::Backspace => {
+                input.pop();
+            }
+            Key::Esc => {
+                self.filter_input = None;
+                self.status = None;
+            }
+            Key::Enter => match PruneOptions::parse_filters(input) {
+                Ok(filters) => {
+                    self.options.filters = filters;
+                    self.filter_input = None;
+                    self.refresh().await;
+                }
+                Err(e) => self.status = Some(format!("{e}")),
+            },
+            _ => return MessageResponse::NotConsumed,
+        }
+        MessageResponse::Consumed
+    }
+
+    /// The total size of the resources to be removed, where known
+    fn reclaimable(&self) -> Option<i64> {
+        let sizes = self
+            .candidates
+            .iter()
+            .filter_map(|c| c.size)
+            .collect::<Vec<i64>>();
+        if sizes.is_empty() {
+            None
+        } else {
+            Some(sizes.iter().sum())
+        }
+    }
+
+    fn summary(&self) -> String {
+        let kind = self.options.kind;
+        let scope = match kind.scope(self.options.all) {
+            Some(scope) => format!("{scope} "),
+            None => String::new(),
+        };
+        let mut summary = format!("{} {scope}{kind} will be removed", self.candidates.len());
+        if let Some(size) = self.reclaimable() {
+            summary.push_str(&format!(", freeing up to {}", format_size(size)));
+        }
+        summary
+    }
+
+    fn confirm_prune(&mut self) {
+        if self.candidates.is_empty() {
+            self.status = Some(format!("There are no {} to prune", self.options.kind));
+            return;
+        }
+
+        let mut message = format!("{}.", self.summary());
+        if !self.options.filters.is_empty() {
+            message.push_str(&format!("\n\nFilters: {}", self.options.filters_string()));
+        }
+        message.push_str("\n\nAre you sure you wish to continue?");
+
+        let cb = Arc::new(FutureMutex::new(PruneResources::new(
+            self.docker.clone(),
+            self.config.docker_path.clone(),
+            self.options.clone(),
+            self.outcome.clone(),
+            self.session.clone(),
+            self.tx.clone(),
+        )));
+
+        let mut modal =
+            BooleanModal::<ModalTypes>::new("Prune".into(), ModalTypes::Prune, self.config.theme);
+        modal.initialise(message, Some(cb));
+        self.modal = Some(modal);
+    }
+
+    fn kinds_line(&self) -> Line<'static> {
+        let mut spans = vec![];
+        for (idx, kind) in PruneKind::ALL.iter().enumerate() {
+            if idx > 0 {
+                spans.push(Span::from(" │ "));
+            }
+            let span = Span::from(kind.key());
+            spans.push(if *kind == self.options.kind {
+                span.style(self.config.theme.selected())
+            } else {
+                span
+            });
+        }
+        if let Some(scope) = self.options.kind.scope(self.options.all) {
+            spans.push(Span::from(format!("  [{scope}]")).style(self.config.theme.footer()));
+        }
+        Line::from(spans)
+    }
+}
+
+impl Component for Prune {
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        let [kinds, filters, table_area, summary] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        f.render_widget(self.kinds_line(), kinds);
+
+        let hint_style = self.config.theme.footer();
+        let filters_line = if let Some(input) = &self.filter_input {
+            let mut spans = vec![
+                Span::from("filters: "),
+                Span::from(input.clone()),
+                Span::from("█").style(self.config.theme.cursor()),
+            ];
+            if let Some(status) = &self.status {
+                spans.push(Span::from(format!("  {status}")).style(self.config.theme.error()));
+            }
+            Line::from(spans)
+        } else if let Some(status) = &self.status {
+            Line::from(Span::from(status.clone()).style(self.config.theme.error()))
+        } else if self.options.filters.is_empty() {
+            Line::from(Span::from("no filters").style(hint_style))
+        } else {
+            Line::from(vec![
+                Span::from("filters: ").style(hint_style),
+                Span::from(self.options.filters_string()),
+            ])
+        };
+        f.render_widget(filters_line, filters);
+
+        let rows = self.candidates.iter().map(|c| {
+            Row::new(vec![
+                Cell::from(c.name.clone()),
+                Cell::from(short_id(&c.id)),
+                Cell::from(c.created.map(format_timestamp).unwrap_or_default()),
+                Cell::from(c.size.map(format_size).unwrap_or_default()),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Length(14),
+                Constraint::Length(20),
+                Constraint::Length(12),
+            ],
+        )
+        .header(
+            Row::new(vec!["Name", "ID", "Created", "Size"]).style(self.config.theme.table_header()),
+        )
+        .highlight_style(self.config.theme.selected());
+        self.table_area = table_area;
+        f.render_stateful_widget(table, table_area, &mut self.table_state);
+
+        f.render_widget(
+            Line::from(Span::from(self.summary()).style(hint_style)),
+            summary,
+        );
+
+        if let Some(m) = self.modal.as_mut() {
+            if let ModalState::Open(_) = m.state {
+                m.draw(f, area)
+            }
+        }
+
+        if let Some(m) = self.alert.as_mut() {
+            if let AlertState::Open(_) = m.state {
+                m.draw(f, area)
+            }
+        }
+    }
+}
+
+fn short_id(id: &str) -> String {
+    id.trim_start_matches("sha256:").chars().take(12).collect()
+}
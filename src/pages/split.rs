@@ -0,0 +1,186 @@
+use std::sync::{Arc, Mutex};
+
+use bollard::Docker;
+use color_eyre::eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    Frame,
+};
+
+use crate::{
+    commands::Command,
+    components::{
+        help::PageHelp,
+        preview::{Preview, PreviewKind},
+    },
+    config::{Config, PreviewLayout},
+    context::{AppContext, PageView},
+    events::{message::MessageResponse, Mouse},
+    keymap::{Action, Input, Scope},
+    state::{PreviewState, SharedSession},
+    traits::{Close, Component, Page},
+};
+
+/// The narrowest the page can be for the preview to go to the right of the table
+/// when the layout is `auto`
+const MIN_WIDTH_BESIDE: u16 = 140;
+
+/// A page made up of a page listing resources along with a preview of whichever
+/// resource is selected in it.  Everything other than the preview is handled by the
+/// inner page, so the split is invisible to it.  Whether the preview is open, and
+/// what it shows, is kept in the session so that it is the same on every page
+#[derive(Debug)]
+pub struct Split {
+    config: Box<Config>,
+    session: SharedSession,
+    page: Box<dyn Page>,
+    preview: Preview,
+    open: bool,
+}
+
+#[async_trait::async_trait]
+impl Page for Split {
+    async fn update(&mut self, input: Input) -> Result<MessageResponse> {
+        if !self.page.is_capturing_input() {
+            match self.config.keybindings.action(Scope::Preview, input) {
+                Some(Action::PreviewToggle) => {
+                    self.open = !self.open;
+                    self.save();
+                    self.follow();
+                    return Ok(MessageResponse::Consumed);
+                }
+                Some(Action::PreviewCycle) if self.open => {
+                    self.preview.cycle();
+                    self.save();
+                    self.follow();
+                    return Ok(MessageResponse::Consumed);
+                }
+                _ => {}
+            }
+        }
+
+        let result = self.page.update(input).await;
+        self.follow();
+        result
+    }
+
+    async fn initialise(&mut self, cx: AppContext) -> Result<()> {
+        self.page.initialise(cx).await?;
+        self.follow();
+        Ok(())
+    }
+
+    fn get_help(&self) -> Arc<Mutex<PageHelp>> {
+        self.page.get_help()
+    }
+
+    fn set_config(&mut self, config: Box<Config>) {
+        self.page.set_config(config.clone());
+        self.preview.set_config(config.clone());
+        self.config = config;
+    }
+
+    fn is_capturing_input(&self) -> bool {
+        self.page.is_capturing_input()
+    }
+
+    async fn command(&mut self, command: Command) -> Result<()> {
+        self.page.command(command).await?;
+        self.follow();
+        Ok(())
+    }
+
+    fn view(&self) -> Option<PageView> {
+        self.page.view()
+    }
+
+    fn restore_view(&mut self, view: PageView) {
+        self.page.restore_view(view);
+        self.follow();
+    }
+
+    fn selection(&self) -> Option<AppContext> {
+        self.page.selection()
+    }
+
+    fn is_streaming(&self) -> bool {
+        self.preview.is_running() || self.page.is_streaming()
+    }
+
+    async fn mouse(&mut self, mouse: Mouse) -> Result<MessageResponse> {
+        let result = self.page.mouse(mouse).await;
+        self.follow();
+        result
+    }
+}
+
+#[async_trait::async_trait]
+impl Close for Split {
+    async fn close(&mut self) -> Result<()> {
+        self.preview.stop();
+        self.page.close().await
+    }
+}
+
+impl Split {
+    pub fn new(
+        page: Box<dyn Page>,
+        docker: Docker,
+        config: Box<Config>,
+        session: SharedSession,
+    ) -> Self {
+        let state = session.lock().unwrap().preview().unwrap_or(PreviewState {
+            open: config.preview.open,
+            kind: PreviewKind::default(),
+        });
+
+        Self {
+            preview: Preview::new(docker, config.clone(), state.kind),
+            config,
+            session,
+            page,
+            open: state.open,
+        }
+    }
+
+    fn save(&self) {
+        self.session.lock().unwrap().set_preview(PreviewState {
+            open: self.open,
+            kind: self.preview.kind(),
+        });
+    }
+
+    /// Keeps the preview on the selected resource while it is open
+    fn follow(&mut self) {
+        if self.open {
+            self.preview.follow(self.page.selection());
+        } else {
+            self.preview.stop();
+        }
+    }
+}
+
+impl Component for Split {
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        if !self.open {
+            self.page.draw(f, area);
+            return;
+        }
+
+        let beside = match self.config.preview.layout {
+            PreviewLayout::Right => true,
+            PreviewLayout::Below => false,
+            PreviewLayout::Auto => area.width >= MIN_WIDTH_BESIDE,
+        };
+        let [page, preview] = if beside {
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .spacing(1)
+                .areas(area)
+        } else {
+            Layout::vertical([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(area)
+        };
+
+        self.page.draw(f, page);
+        self.preview.draw(f, preview);
+    }
+}
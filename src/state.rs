@@ -1,3 +1,13 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::{activity::Activity, components::preview::PreviewKind};
+
+/// The most activities which are kept; once reached, the oldest are forgotten
+const MAX_ACTIVITIES: usize = 200;
+
 // TODO: Merge mode and running to State { View, TextInput, Finishing ... }
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub enum Mode {
@@ -21,6 +31,8 @@ pub enum CurrentPage {
     Logs,
     Attach,
     DescribeContainer,
+    Prune,
+    Activity,
 }
 
 // impl Default for CurrentPage {
@@ -28,3 +40,82 @@ pub enum CurrentPage {
 //         Self::Containers(AppContext::default())
 //     }
 // }
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
+
+    pub fn indicator(self) -> &'static str {
+        match self {
+            Self::Ascending => "▲",
+            Self::Descending => "▼",
+        }
+    }
+}
+
+/// The column (identified by its key, eg `created`) a table is sorted by
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortOrder {
+    pub column: String,
+    pub direction: SortDirection,
+}
+
+/// Whether the preview is shown alongside the resource tables, and what it shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreviewState {
+    pub open: bool,
+    pub kind: PreviewKind,
+}
+
+/// State which outlives individual pages, and so persists for the duration of
+/// the session rather than being reset whenever a page is opened
+#[derive(Debug, Default)]
+pub struct Session {
+    sort_orders: HashMap<String, SortOrder>,
+    activities: Vec<Activity>,
+    /// Unset until the preview is first opened, closed or switched
+    preview: Option<PreviewState>,
+}
+
+impl Session {
+    pub fn sort_order(&self, page: &str) -> Option<SortOrder> {
+        self.sort_orders.get(page).cloned()
+    }
+
+    pub fn set_sort_order(&mut self, page: &str, order: SortOrder) {
+        self.sort_orders.insert(page.into(), order);
+    }
+
+    /// The actions performed during the session, oldest first
+    pub fn activities(&self) -> &[Activity] {
+        &self.activities
+    }
+
+    pub fn record(&mut self, activity: Activity) {
+        self.activities.push(activity);
+        if self.activities.len() > MAX_ACTIVITIES {
+            self.activities.remove(0);
+        }
+    }
+
+    pub fn preview(&self) -> Option<PreviewState> {
+        self.preview
+    }
+
+    pub fn set_preview(&mut self, preview: PreviewState) {
+        self.preview = Some(preview);
+    }
+}
+
+pub type SharedSession = Arc<Mutex<Session>>;
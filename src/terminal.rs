@@ -1,4 +1,5 @@
 use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
@@ -16,11 +17,22 @@ pub fn init() -> std::io::Result<Terminal<impl Backend>> {
 }
 
 pub fn restore() -> std::io::Result<()> {
+    stdout().execute(DisableMouseCapture)?;
     stdout().execute(LeaveAlternateScreen)?;
     disable_raw_mode()?;
     Ok(())
 }
 
+/// Starts or stops sending mouse events to ducker rather than to the terminal
+pub fn set_mouse_capture(enabled: bool) -> std::io::Result<()> {
+    if enabled {
+        stdout().execute(EnableMouseCapture)?;
+    } else {
+        stdout().execute(DisableMouseCapture)?;
+    }
+    Ok(())
+}
+
 pub fn init_panic_hook() {
     let original_hook = take_hook();
     set_hook(Box::new(move |panic_info| {
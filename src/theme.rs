@@ -0,0 +1,728 @@
+use std::{collections::BTreeMap, fmt, fs::File, io::BufReader, path::Path};
+
+use color_eyre::eyre::{bail, eyre, Context, Error, Result};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use serde::{
+    de::{self, value::MapAccessDeserializer, DeserializeSeed, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+
+/// The themes which ship with ducker; any other name refers to a file in the
+/// `themes` directory
+pub const BUILT_IN: [&str; 5] = ["default", "dark", "light", "solarized", "high-contrast"];
+
+/// Something in the UI which is styled by the theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    Title,
+    Help,
+    Background,
+    Footer,
+    Key,
+    Success,
+    Warning,
+    Error,
+    Info,
+    PositiveHighlight,
+    NegativeHighlight,
+    SearchMatch,
+    Selected,
+    TableHeader,
+    Prompt,
+    Hint,
+    Cursor,
+    Border,
+    ModalOptions,
+}
+
+impl Slot {
+    pub const ALL: [Slot; 19] = [
+        Self::Title,
+        Self::Help,
+        Self::Background,
+        Self::Footer,
+        Self::Key,
+        Self::Success,
+        Self::Warning,
+        Self::Error,
+        Self::Info,
+        Self::PositiveHighlight,
+        Self::NegativeHighlight,
+        Self::SearchMatch,
+        Self::Selected,
+        Self::TableHeader,
+        Self::Prompt,
+        Self::Hint,
+        Self::Cursor,
+        Self::Border,
+        Self::ModalOptions,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Title => "title",
+            Self::Help => "help",
+            Self::Background => "background",
+            Self::Footer => "footer",
+            Self::Key => "key",
+            Self::Success => "success",
+            Self::Warning => "warning",
+            Self::Error => "error",
+            Self::Info => "info",
+            Self::PositiveHighlight => "positive_highlight",
+            Self::NegativeHighlight => "negative_highlight",
+            Self::SearchMatch => "search_match",
+            Self::Selected => "selected",
+            Self::TableHeader => "table_header",
+            Self::Prompt => "prompt",
+            Self::Hint => "hint",
+            Self::Cursor => "cursor",
+            Self::Border => "border",
+            Self::ModalOptions => "modal_options",
+        }
+    }
+}
+
+impl std::str::FromStr for Slot {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|slot| slot.name() == s)
+            .ok_or_else(|| eyre!("unknown style slot \"{s}\""))
+    }
+}
+
+/// The text modifiers which can be given to a style slot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModifierName {
+    Bold,
+    Dim,
+    Italic,
+    Underlined,
+    SlowBlink,
+    RapidBlink,
+    Reversed,
+    Hidden,
+    CrossedOut,
+}
+
+impl From<ModifierName> for Modifier {
+    fn from(value: ModifierName) -> Self {
+        match value {
+            ModifierName::Bold => Modifier::BOLD,
+            ModifierName::Dim => Modifier::DIM,
+            ModifierName::Italic => Modifier::ITALIC,
+            ModifierName::Underlined => Modifier::UNDERLINED,
+            ModifierName::SlowBlink => Modifier::SLOW_BLINK,
+            ModifierName::RapidBlink => Modifier::RAPID_BLINK,
+            ModifierName::Reversed => Modifier::REVERSED,
+            ModifierName::Hidden => Modifier::HIDDEN,
+            ModifierName::CrossedOut => Modifier::CROSSED_OUT,
+        }
+    }
+}
+
+/// A style slot as written in config or a theme file.  Anything left unset keeps
+/// the value from the theme being extended; a bare colour, such as `"#96e072"`,
+/// sets only the foreground
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StyleConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fg: Option<Color>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg: Option<Color>,
+
+    /// Replaces all of the base style's modifiers when set, so `[]` removes them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modifiers: Option<Vec<ModifierName>>,
+}
+
+impl StyleConfig {
+    fn apply(&self, mut style: Style) -> Style {
+        if let Some(fg) = self.fg {
+            style.fg = Some(fg);
+        }
+        if let Some(bg) = self.bg {
+            style.bg = Some(bg);
+        }
+        if let Some(modifiers) = &self.modifiers {
+            style.add_modifier = modifiers
+                .iter()
+                .fold(Modifier::empty(), |acc, m| acc | Modifier::from(*m));
+            style.sub_modifier = Modifier::empty();
+        }
+        style
+    }
+}
+
+// Written by hand rather than as an untagged enum so that a bad colour is reported
+// as such, rather than as a value which matches neither form
+impl<'de> Deserialize<'de> for StyleConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct StyleVisitor;
+
+        impl<'de> Visitor<'de> for StyleVisitor {
+            type Value = StyleConfig;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a colour, or a map of fg, bg and modifiers")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<StyleConfig, E> {
+                Ok(StyleConfig {
+                    fg: Some(parse_colour(v)?),
+                    ..Default::default()
+                })
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                map: A,
+            ) -> std::result::Result<StyleConfig, A::Error> {
+                let fields = StyleFields::deserialize(MapAccessDeserializer::new(map))?;
+                Ok(StyleConfig {
+                    fg: fields.fg,
+                    bg: fields.bg,
+                    modifiers: fields.modifiers,
+                })
+            }
+        }
+
+        deserializer.deserialize_any(StyleVisitor)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StyleFields {
+    #[serde(default, deserialize_with = "deserialize_colour")]
+    fg: Option<Color>,
+    #[serde(default, deserialize_with = "deserialize_colour")]
+    bg: Option<Color>,
+    #[serde(default)]
+    modifiers: Option<Vec<ModifierName>>,
+}
+
+fn parse_colour<E: de::Error>(v: &str) -> std::result::Result<Color, E> {
+    v.parse::<Color>().map_err(|_| {
+        E::custom(format!(
+            "invalid colour \"{v}\"; expected a name (eg red), an index (eg 8) or a hex code (eg #96e072)"
+        ))
+    })
+}
+
+fn deserialize_colour<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<Color>, D::Error> {
+    let v = String::deserialize(deserializer)?;
+    parse_colour(&v).map(Some)
+}
+
+/// The `theme` section of the config: either the name of a theme, or a theme
+/// along with changes to some of its slots
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ThemeConfig {
+    Name(String),
+    Custom(CustomTheme),
+}
+
+impl<'de> Deserialize<'de> for ThemeConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct ThemeConfigVisitor;
+
+        impl<'de> Visitor<'de> for ThemeConfigVisitor {
+            type Value = ThemeConfig;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "the name of a theme, or a map of styles")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<ThemeConfig, E> {
+                Ok(ThemeConfig::Name(v.into()))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                map: A,
+            ) -> std::result::Result<ThemeConfig, A::Error> {
+                let mut name = None;
+                let mut use_theme = None;
+                let styles = visit_styles(map, &["name", "use_theme"], |key, map| match key {
+                    "name" => map.next_value().map(|v| name = Some(v)).map(|_| true),
+                    "use_theme" => map.next_value().map(|v| use_theme = Some(v)).map(|_| true),
+                    _ => Ok(false),
+                })?;
+                Ok(ThemeConfig::Custom(CustomTheme {
+                    name,
+                    use_theme,
+                    styles,
+                }))
+            }
+        }
+
+        deserializer.deserialize_any(ThemeConfigVisitor)
+    }
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self::Name("default".into())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CustomTheme {
+    /// The theme to start from; defaults to `default`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Before named themes existed this chose between the terminal's colours and
+    /// the colours in config, which were otherwise ignored
+    #[serde(skip_serializing_if = "Option::is_none")]
+    use_theme: Option<bool>,
+
+    #[serde(flatten)]
+    pub styles: BTreeMap<String, StyleConfig>,
+}
+
+/// A theme file in the `themes` directory
+#[derive(Debug, Clone)]
+struct ThemeFile {
+    /// The built-in theme to start from; defaults to `default`
+    extends: Option<String>,
+    styles: BTreeMap<String, StyleConfig>,
+}
+
+impl<'de> Deserialize<'de> for ThemeFile {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct ThemeFileVisitor;
+
+        impl<'de> Visitor<'de> for ThemeFileVisitor {
+            type Value = ThemeFile;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a map of styles")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                map: A,
+            ) -> std::result::Result<ThemeFile, A::Error> {
+                let mut extends = None;
+                let styles = visit_styles(map, &["extends"], |key, map| match key {
+                    "extends" => map.next_value().map(|v| extends = Some(v)).map(|_| true),
+                    _ => Ok(false),
+                })?;
+                Ok(ThemeFile { extends, styles })
+            }
+        }
+
+        deserializer.deserialize_map(ThemeFileVisitor)
+    }
+}
+
+/// Reads a map of style slots, offering each of the `other` keys to `visit_other`
+/// so that the map may hold other settings too.  The styles are read as they are
+/// found, rather than flattened, so that any error is reported at the right place
+/// in the file
+fn visit_styles<'de, A, F>(
+    mut map: A,
+    other: &[&str],
+    mut visit_other: F,
+) -> std::result::Result<BTreeMap<String, StyleConfig>, A::Error>
+where
+    A: MapAccess<'de>,
+    F: FnMut(&str, &mut A) -> std::result::Result<bool, A::Error>,
+{
+    let mut styles = BTreeMap::new();
+    while let Some(key) = map.next_key_seed(StyleKey { other })? {
+        if visit_other(&key, &mut map)? {
+            continue;
+        }
+        let style = map.next_value::<StyleConfig>()?;
+        styles.insert(key, style);
+    }
+    Ok(styles)
+}
+
+/// A key in a map of styles, which must either be a slot or one of `other`.  It is
+/// checked as it is read so that an unknown slot is reported on its own line
+struct StyleKey<'a> {
+    other: &'a [&'a str],
+}
+
+impl<'de> DeserializeSeed<'de> for StyleKey<'_> {
+    type Value = String;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<String, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de> Visitor<'de> for StyleKey<'_> {
+    type Value = String;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a style slot")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<String, E> {
+        if !self.other.contains(&v) {
+            v.parse::<Slot>().map_err(E::custom)?;
+        }
+        Ok(v.into())
+    }
+}
+
+/// The style of every slot, resolved from the config and any theme file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    title: Style,
+    help: Style,
+    background: Style,
+    footer: Style,
+    key: Style,
+    success: Style,
+    warning: Style,
+    error: Style,
+    info: Style,
+    positive_highlight: Style,
+    negative_highlight: Style,
+    search_match: Style,
+    selected: Style,
+    table_header: Style,
+    prompt: Style,
+    hint: Style,
+    cursor: Style,
+    border: Style,
+    modal_options: Style,
+}
+
+impl Theme {
+    /// Resolves the configured theme, looking for named themes which are not
+    /// built in within `dir`.  Colours are dropped when `NO_COLOR` is set
+    pub fn load(config: &ThemeConfig, dir: &Path) -> Result<Self> {
+        let mut theme = match config {
+            ThemeConfig::Name(name) => Self::named(name, dir)?,
+            ThemeConfig::Custom(custom) => match (&custom.name, custom.use_theme) {
+                (Some(name), _) => Self::named(name, dir)?.with_styles(&custom.styles)?,
+                (None, Some(false)) => Self::default(),
+                (None, Some(true)) => Self::dark().with_styles(&custom.styles)?,
+                (None, None) => Self::default().with_styles(&custom.styles)?,
+            },
+        };
+
+        if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            theme = theme.without_colour();
+        }
+        Ok(theme)
+    }
+
+    fn named(name: &str, dir: &Path) -> Result<Self> {
+        if let Some(theme) = Self::built_in(name) {
+            return Ok(theme);
+        }
+
+        let path = dir.join(format!("{name}.yaml"));
+        let Ok(f) = File::open(&path) else {
+            bail!(
+                "unknown theme \"{name}\"; expected one of {} or a file at {}",
+                BUILT_IN.join(", "),
+                path.display()
+            )
+        };
+        let file: ThemeFile = serde_yml::from_reader(BufReader::new(f))
+            .with_context(|| format!("unable to parse theme file {}", path.display()))?;
+
+        let base = file.extends.as_deref().unwrap_or("default");
+        let Some(theme) = Self::built_in(base) else {
+            bail!(
+                "theme \"{name}\" extends unknown theme \"{base}\"; expected one of {}",
+                BUILT_IN.join(", ")
+            )
+        };
+        theme
+            .with_styles(&file.styles)
+            .with_context(|| format!("invalid theme file {}", path.display()))
+    }
+
+    fn built_in(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "solarized" => Some(Self::solarized()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    fn with_styles(mut self, styles: &BTreeMap<String, StyleConfig>) -> Result<Self> {
+        for (slot, style) in styles {
+            let slot = self.slot_mut(slot.parse()?);
+            *slot = style.apply(*slot);
+        }
+        Ok(self)
+    }
+
+    fn without_colour(mut self) -> Self {
+        for slot in Slot::ALL {
+            let style = self.slot_mut(slot);
+            // Anything picked out by its background, such as the selected row, would
+            // otherwise become indistinguishable
+            if slot != Slot::Background && style.bg.is_some() {
+                style.add_modifier |= Modifier::REVERSED;
+            }
+            style.fg = None;
+            style.bg = None;
+            style.underline_color = None;
+        }
+        self
+    }
+
+    fn slot_mut(&mut self, slot: Slot) -> &mut Style {
+        match slot {
+            Slot::Title => &mut self.title,
+            Slot::Help => &mut self.help,
+            Slot::Background => &mut self.background,
+            Slot::Footer => &mut self.footer,
+            Slot::Key => &mut self.key,
+            Slot::Success => &mut self.success,
+            Slot::Warning => &mut self.warning,
+            Slot::Error => &mut self.error,
+            Slot::Info => &mut self.info,
+            Slot::PositiveHighlight => &mut self.positive_highlight,
+            Slot::NegativeHighlight => &mut self.negative_highlight,
+            Slot::SearchMatch => &mut self.search_match,
+            Slot::Selected => &mut self.selected,
+            Slot::TableHeader => &mut self.table_header,
+            Slot::Prompt => &mut self.prompt,
+            Slot::Hint => &mut self.hint,
+            Slot::Cursor => &mut self.cursor,
+            Slot::Border => &mut self.border,
+            Slot::ModalOptions => &mut self.modal_options,
+        }
+    }
+
+    /// The ducker font in the header
+    pub fn title(&self) -> Style {
+        self.title
+    }
+    /// The help prompts in the header and the key sequence popup
+    pub fn help(&self) -> Style {
+        self.help
+    }
+    /// The whole screen, drawn before anything else
+    pub fn background(&self) -> Style {
+        self.background
+    }
+    /// The footer and the hints shown below tables
+    pub fn footer(&self) -> Style {
+        self.footer
+    }
+    /// Keys in the describe tree
+    pub fn key(&self) -> Style {
+        self.key
+    }
+    pub fn success(&self) -> Style {
+        self.success
+    }
+    /// Something which may need attention, such as a warning notification
+    pub fn warning(&self) -> Style {
+        self.warning
+    }
+    pub fn error(&self) -> Style {
+        self.error
+    }
+    /// Something of note which is neither a success nor a failure
+    pub fn info(&self) -> Style {
+        self.info
+    }
+    /// Highlighting in a happy state, such as a running container
+    pub fn positive_highlight(&self) -> Style {
+        self.positive_highlight
+    }
+    /// Highlighting in a sad state, such as a stopped container
+    pub fn negative_highlight(&self) -> Style {
+        self.negative_highlight
+    }
+    /// Text matching a filter or search
+    pub fn search_match(&self) -> Style {
+        self.search_match
+    }
+    /// The selected row of a table or list
+    pub fn selected(&self) -> Style {
+        self.selected
+    }
+    pub fn table_header(&self) -> Style {
+        self.table_header
+    }
+    /// The command prompt symbol and the reverse search label
+    pub fn prompt(&self) -> Style {
+        self.prompt
+    }
+    /// Secondary text, such as the completion hint in the prompt
+    pub fn hint(&self) -> Style {
+        self.hint
+    }
+    /// The cursor of inline text inputs
+    pub fn cursor(&self) -> Style {
+        self.cursor
+    }
+    /// The borders of the prompt, modals and popups
+    pub fn border(&self) -> Style {
+        self.border
+    }
+    /// The options at the bottom of a modal
+    pub fn modal_options(&self) -> Style {
+        self.modal_options
+    }
+
+    /// The colours of the palette used to draw ducker's README
+    fn dark() -> Self {
+        let green = Color::Rgb(0x96, 0xe0, 0x72);
+        let red = Color::Rgb(0xee, 0x5d, 0x43);
+        let cyan = Color::Rgb(0x00, 0xe8, 0xc6);
+        let pink = Color::Rgb(0xff, 0x00, 0xaa);
+        let grey = Color::Rgb(0x3a, 0x3f, 0x4b);
+
+        Self {
+            title: Style::new().fg(green),
+            help: Style::new().fg(red).italic(),
+            background: Style::new().bg(Color::Rgb(0x23, 0x26, 0x2e)),
+            footer: Style::new().fg(cyan).italic(),
+            key: Style::new().fg(cyan),
+            success: Style::new().fg(green),
+            warning: Style::new().fg(Color::Rgb(0xff, 0xc6, 0x6d)),
+            error: Style::new().fg(red),
+            info: Style::new().fg(cyan),
+            positive_highlight: Style::new().fg(green),
+            negative_highlight: Style::new().fg(pink),
+            search_match: Style::new().fg(pink).bold().underlined(),
+            selected: Style::new().bg(grey).bold(),
+            prompt: Style::new().fg(green),
+            border: Style::new().fg(grey),
+            ..Self::default()
+        }
+    }
+
+    fn light() -> Self {
+        let green = Color::Rgb(0x2e, 0x7d, 0x32);
+        let red = Color::Rgb(0xc6, 0x28, 0x28);
+        let teal = Color::Rgb(0x00, 0x83, 0x8f);
+        let pink = Color::Rgb(0xad, 0x14, 0x57);
+        let grey = Color::Rgb(0xd0, 0xd7, 0xde);
+
+        Self {
+            title: Style::new().fg(green),
+            help: Style::new().fg(red).italic(),
+            background: Style::new()
+                .fg(Color::Rgb(0x24, 0x29, 0x2f))
+                .bg(Color::Rgb(0xfa, 0xfa, 0xfa)),
+            footer: Style::new().fg(teal).italic(),
+            key: Style::new().fg(teal),
+            success: Style::new().fg(green),
+            warning: Style::new().fg(Color::Rgb(0xb2, 0x6a, 0x00)),
+            error: Style::new().fg(red),
+            info: Style::new().fg(teal),
+            positive_highlight: Style::new().fg(green),
+            negative_highlight: Style::new().fg(pink),
+            search_match: Style::new().fg(pink).bold().underlined(),
+            selected: Style::new().bg(grey).bold(),
+            prompt: Style::new().fg(green),
+            border: Style::new().fg(Color::Rgb(0x8c, 0x95, 0x9f)),
+            ..Self::default()
+        }
+    }
+
+    fn solarized() -> Self {
+        let base01 = Color::Rgb(0x58, 0x6e, 0x75);
+        let yellow = Color::Rgb(0xb5, 0x89, 0x00);
+        let red = Color::Rgb(0xdc, 0x32, 0x2f);
+        let magenta = Color::Rgb(0xd3, 0x36, 0x82);
+        let blue = Color::Rgb(0x26, 0x8b, 0xd2);
+        let cyan = Color::Rgb(0x2a, 0xa1, 0x98);
+        let green = Color::Rgb(0x85, 0x99, 0x00);
+
+        Self {
+            title: Style::new().fg(yellow),
+            help: Style::new().fg(red).italic(),
+            background: Style::new()
+                .fg(Color::Rgb(0x83, 0x94, 0x96))
+                .bg(Color::Rgb(0x00, 0x2b, 0x36)),
+            footer: Style::new().fg(cyan).italic(),
+            key: Style::new().fg(blue),
+            success: Style::new().fg(green),
+            warning: Style::new().fg(yellow),
+            error: Style::new().fg(red),
+            info: Style::new().fg(blue),
+            positive_highlight: Style::new().fg(green),
+            negative_highlight: Style::new().fg(magenta),
+            search_match: Style::new().fg(magenta).bold().underlined(),
+            selected: Style::new().bg(Color::Rgb(0x07, 0x36, 0x42)).bold(),
+            prompt: Style::new().fg(yellow),
+            hint: Style::new().fg(base01),
+            border: Style::new().fg(base01),
+            ..Self::default()
+        }
+    }
+
+    fn high_contrast() -> Self {
+        Self {
+            title: Style::new().fg(Color::Yellow).bold(),
+            help: Style::new().fg(Color::White).bold(),
+            background: Style::new().fg(Color::White).bg(Color::Black),
+            footer: Style::new().fg(Color::White),
+            key: Style::new().fg(Color::LightCyan).bold(),
+            success: Style::new().fg(Color::LightGreen).bold(),
+            warning: Style::new().fg(Color::LightYellow).bold(),
+            error: Style::new().fg(Color::LightRed).bold(),
+            info: Style::new().fg(Color::LightCyan).bold(),
+            positive_highlight: Style::new().fg(Color::LightGreen),
+            negative_highlight: Style::new().fg(Color::LightMagenta),
+            search_match: Style::new().fg(Color::Black).bg(Color::Yellow).bold(),
+            selected: Style::new().fg(Color::Black).bg(Color::White).bold(),
+            table_header: Style::new().bold().underlined(),
+            prompt: Style::new().fg(Color::Yellow).bold(),
+            hint: Style::new().fg(Color::Gray),
+            border: Style::new().fg(Color::White),
+            modal_options: Style::new().bold(),
+            ..Self::default()
+        }
+    }
+}
+
+/// The terminal's own colours
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            title: Style::new().fg(Color::Green),
+            help: Style::new().fg(Color::Red).italic(),
+            background: Style::new().bg(Color::Reset),
+            footer: Style::new().fg(Color::Cyan).italic(),
+            key: Style::new().fg(Color::Cyan),
+            success: Style::new().fg(Color::Green),
+            warning: Style::new().fg(Color::Yellow),
+            error: Style::new().fg(Color::Red),
+            info: Style::new().fg(Color::Cyan),
+            positive_highlight: Style::new().fg(Color::Green),
+            negative_highlight: Style::new().fg(Color::Magenta),
+            search_match: Style::new().fg(Color::Magenta).bold().underlined(),
+            selected: Style::new().reversed(),
+            table_header: Style::new().bold(),
+            prompt: Style::new().fg(Color::Green),
+            hint: Style::new().dim(),
+            cursor: Style::new().slow_blink(),
+            border: Style::new(),
+            modal_options: Style::new().italic(),
+        }
+    }
+}
@@ -4,20 +4,57 @@ use std::{
 };
 
 use async_trait::async_trait;
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{bail, Result};
 
 use crate::{
+    commands::Command,
     components::help::PageHelp,
-    context::AppContext,
-    events::{message::MessageResponse, Key},
+    config::Config,
+    context::{AppContext, PageView},
+    events::{message::MessageResponse, Mouse},
+    keymap::Input,
     traits::Component,
 };
 
 #[async_trait]
 pub trait Page: Component + Close + Debug + Send + Sync {
-    async fn update(&mut self, message: Key) -> Result<MessageResponse>;
+    async fn update(&mut self, input: Input) -> Result<MessageResponse>;
     async fn initialise(&mut self, cx: AppContext) -> Result<()>;
     fn get_help(&self) -> Arc<Mutex<PageHelp>>;
+    /// Applies a config which has been reloaded, keeping the page's state
+    fn set_config(&mut self, config: Box<Config>);
+    /// Whether the page is taking text input or showing a modal, in which case keys
+    /// are passed straight to it rather than being treated as part of a sequence
+    fn is_capturing_input(&self) -> bool {
+        false
+    }
+    /// Runs a command entered at the prompt which acts on the page itself, eg `:sort`
+    async fn command(&mut self, command: Command) -> Result<()> {
+        bail!("\"{}\" is not supported on this page", command.name())
+    }
+    /// The page's selection and scroll position, recorded in the navigation history
+    /// when leaving the page.  Pages without anything to restore return `None`
+    fn view(&self) -> Option<PageView> {
+        None
+    }
+    /// Restores a view previously returned by `view`, once the page has been
+    /// initialised again
+    fn restore_view(&mut self, _view: PageView) {}
+    /// The context of the selected resource, used to preview it.  Pages which don't
+    /// list resources return `None`
+    fn selection(&self) -> Option<AppContext> {
+        None
+    }
+    /// Whether the page has a stream open to the daemon, eg of a container's logs,
+    /// which it keeps open while in a background tab
+    fn is_streaming(&self) -> bool {
+        false
+    }
+    /// Handles a click within the page, eg to select a row of a table.  Scrolling
+    /// is turned into moving up or down before it reaches the page
+    async fn mouse(&mut self, _mouse: Mouse) -> Result<MessageResponse> {
+        Ok(MessageResponse::NotConsumed)
+    }
 }
 
 #[async_trait]
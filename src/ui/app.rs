@@ -1,24 +1,34 @@
 use bollard::Docker;
-use color_eyre::eyre::{Context, Result};
+use color_eyre::eyre::{Context, Error, Result};
 use ratatui::{
     layout::{Constraint, Layout, Rect},
-    style::Style,
     widgets::Block,
     Frame,
 };
 use tokio::sync::mpsc::Sender;
 
 use crate::{
+    activity::{error_chain, Activity},
+    commands::Command,
     components::{
         alert_modal::{AlertModal, ModalState},
+        debug_overlay::DebugOverlay,
         footer::Footer,
         header::Header,
         input_field::InputField,
         resize_notice::ResizeScreen,
+        toasts::Toasts,
+        which_key::WhichKey,
     },
     config::Config,
-    events::{key::Key, message::MessageResponse, Message, Transition},
-    state::{self, Running},
+    docker::store::ResourceStore,
+    events::{
+        key::Key, message::MessageResponse, Message, Mouse, MouseKind, Notification, Transition,
+    },
+    keymap::{Action, Input, Scope},
+    logging::TraceLog,
+    state::{self, Running, SharedSession},
+    terminal,
     traits::{Component, ModalComponent},
     ui::page_manager::PageManager,
 };
@@ -26,6 +36,7 @@ use crate::{
 #[derive(Debug)]
 enum ModalType {
     AlertModal,
+    ConfigError,
 }
 
 #[derive(Debug)]
@@ -39,7 +50,12 @@ pub struct App {
     page_manager: PageManager,
     footer: Footer,
     input_field: InputField,
+    which_key: WhichKey,
+    toasts: Toasts,
+    debug_overlay: DebugOverlay,
     modal: Option<AlertModal<ModalType>>,
+    session: SharedSession,
+    store: ResourceStore,
 }
 
 impl App {
@@ -47,14 +63,28 @@ impl App {
         tx: Sender<Message<Key, Transition>>,
         docker: Docker,
         config: Config,
+        trace_log: TraceLog,
     ) -> Result<Self> {
         let config = Box::new(config);
 
         let page = state::CurrentPage::default();
 
-        let body = PageManager::new(page.clone(), tx.clone(), docker, config.clone())
-            .await
-            .context("unable to create new body component")?;
+        let session = SharedSession::default();
+
+        // Owned here rather than by the pages, so that it carries on refreshing as
+        // pages are switched
+        let store = ResourceStore::start(docker.clone(), tx.clone());
+
+        let body = PageManager::new(
+            page.clone(),
+            tx.clone(),
+            docker.clone(),
+            config.clone(),
+            session.clone(),
+            store.clone(),
+        )
+        .await
+        .context("unable to create new body component")?;
 
         let app = Self {
             running: state::Running::default(),
@@ -62,11 +92,16 @@ impl App {
             mode: state::Mode::default(),
             blocked: true,
             resize_screen: ResizeScreen::new(config.clone()),
-            title: Header::new(config.clone()),
+            title: Header::new(config.clone(), store.clone()),
             page_manager: body,
             footer: Footer::new(config.clone()),
-            input_field: InputField::new(tx, config.prompt),
+            input_field: InputField::new(tx, store.clone(), &config)?,
+            which_key: WhichKey::new(config.clone()),
+            toasts: Toasts::new(config.clone()),
+            debug_overlay: DebugOverlay::new(config.clone(), trace_log),
             modal: None,
+            session,
+            store,
         };
         Ok(app)
     }
@@ -96,12 +131,56 @@ impl App {
         };
 
         res.unwrap_or_else(|e| {
-            self.handle_error("Error".into(), format!("{e}"));
+            self.handle_error(e);
             MessageResponse::NotConsumed
         })
     }
 
+    /// Handles a click or scroll.  Clicking on a key in the help or the footer fires
+    /// its action, while anything else is passed to the page.  The mouse is ignored
+    /// while the prompt, a modal or the page's own text input is open
+    pub async fn mouse(&mut self, mouse: Mouse) -> MessageResponse {
+        if self.blocked || self.mode != state::Mode::View || self.page_manager.is_capturing_input()
+        {
+            return MessageResponse::NotConsumed;
+        }
+        if let Some(m) = self.modal.as_ref() {
+            if let ModalState::Open(_) = m.state {
+                return MessageResponse::NotConsumed;
+            }
+        }
+        self.which_key.clear();
+
+        let action = match mouse.kind {
+            MouseKind::Click => self
+                .footer
+                .action_at(mouse)
+                .or_else(|| self.page_manager.help_action_at(mouse)),
+            _ => None,
+        };
+        let result = match action {
+            Some(action) => self.handle_input(action.into()).await,
+            None => self.page_manager.mouse(mouse).await,
+        };
+        result.unwrap_or_else(|e| {
+            self.handle_error(e);
+            MessageResponse::NotConsumed
+        })
+    }
+
+    /// Whether mouse events should be sent to ducker, as of the latest config
+    pub fn mouse_capture(&self) -> bool {
+        self.config.mouse
+    }
+
     pub async fn transition(&mut self, transition: Transition) -> MessageResponse {
+        // Handled even when a modal is open, so that fixing a broken config also
+        // dismisses the error it caused
+        if transition == Transition::ReloadConfig {
+            self.reload_config();
+            return MessageResponse::Consumed;
+        }
+
         if let Some(m) = self.modal.as_mut() {
             if let ModalState::Open(_) = m.state {
                 return MessageResponse::NotConsumed;
@@ -116,56 +195,168 @@ impl App {
                 self.set_mode(state::Mode::View);
                 MessageResponse::Consumed
             }
+            Transition::ToggleDebug => {
+                self.debug_overlay.toggle();
+                MessageResponse::Consumed
+            }
             _ => self
                 .page_manager
                 .transition(transition)
                 .await
                 .unwrap_or_else(|e| {
-                    self.handle_error("Error".into(), format!("{e}"));
+                    self.handle_error(e);
                     MessageResponse::NotConsumed
                 }),
         }
     }
 
     async fn update_view_mode(&mut self, message: Key) -> Result<MessageResponse> {
-        if let MessageResponse::Consumed = self.page_manager.update(message).await? {
+        // Keys which may be the start of a sequence are held back until the sequence
+        // is complete, unless the page is taking text input
+        let inputs = if self.page_manager.is_capturing_input() {
+            self.which_key.clear();
+            vec![message.into()]
+        } else {
+            self.which_key.update(self.page_manager.scope(), message)
+        };
+        if inputs.is_empty() {
             return Ok(MessageResponse::Consumed);
         }
 
-        match message {
-            Key::Char(':') => {
+        let mut result = MessageResponse::NotConsumed;
+        for input in inputs {
+            if self.handle_input(input).await?.is_consumed() {
+                result = MessageResponse::Consumed;
+            }
+        }
+        Ok(result)
+    }
+
+    async fn handle_input(&mut self, input: Input) -> Result<MessageResponse> {
+        if let MessageResponse::Consumed = self.page_manager.update(input).await? {
+            return Ok(MessageResponse::Consumed);
+        }
+
+        match self.config.keybindings.action(Scope::Global, input) {
+            Some(Action::Command) => {
                 self.set_mode(state::Mode::TextInput);
                 Ok(MessageResponse::Consumed)
             }
-            Key::Char('Q') | Key::Char('q') => {
+            Some(Action::Quit) => {
                 self.running = Running::Done;
                 Ok(MessageResponse::Consumed)
             }
+            Some(Action::Back) => self.page_manager.transition(Transition::Back).await,
+            Some(Action::Forward) => self.page_manager.transition(Transition::Forward).await,
+            Some(Action::TabNew) => {
+                self.page_manager
+                    .transition(Transition::RunCommand(Command::TabNew(None)))
+                    .await
+            }
+            Some(Action::TabNext) => self.page_manager.transition(Transition::NextTab).await,
+            Some(Action::TabPrevious) => {
+                self.page_manager.transition(Transition::PreviousTab).await
+            }
+            Some(Action::TabClose) => self.page_manager.transition(Transition::CloseTab).await,
             _ => Ok(MessageResponse::NotConsumed),
         }
     }
 
     async fn update_text_mode(&mut self, message: Key) -> Result<MessageResponse> {
-        let result = match message {
-            Key::Esc => {
+        // The input field gets the first chance at escape, so that it can cancel a
+        // history search without leaving the prompt
+        let result = match self.input_field.update(message).await? {
+            MessageResponse::NotConsumed if message == Key::Esc => {
                 self.set_mode(state::Mode::View);
                 MessageResponse::Consumed
             }
-            _ => self.input_field.update(message).await.unwrap(),
+            result => result,
         };
         Ok(result)
     }
 
     fn set_mode(&mut self, mode: state::Mode) {
         self.mode = mode.clone();
+        self.which_key.clear();
         match mode {
-            state::Mode::TextInput => self.input_field.initialise(),
+            state::Mode::TextInput => self.input_field.initialise(self.page_manager.columns()),
             state::Mode::View => {}
         }
     }
 
-    fn handle_error(&mut self, title: String, msg: String) {
-        let mut modal = AlertModal::new(title, ModalType::AlertModal);
+    /// Rebuilds the config from its layers and applies it to every component.  If the new
+    /// config is invalid the current one is kept and the error is shown instead
+    fn reload_config(&mut self) {
+        let mut config = match Config::new(self.config.overrides.clone()) {
+            Ok(config) => Box::new(config),
+            Err(e) => {
+                let mut modal = AlertModal::new(
+                    "Invalid config".into(),
+                    ModalType::ConfigError,
+                    self.config.theme,
+                );
+                modal.initialise(format!("{e:#}\n\nThe previous config is still in use."));
+                self.modal = Some(modal);
+                return;
+            }
+        };
+        tracing::info!("config reloaded");
+        // The connection to the daemon is only made at startup
+        config.docker_path = self.config.docker_path.clone();
+        if config.mouse != self.config.mouse {
+            if let Err(e) = terminal::set_mouse_capture(config.mouse) {
+                tracing::warn!(error = %e, "unable to set mouse capture");
+            }
+        }
+
+        if self
+            .modal
+            .as_ref()
+            .is_some_and(|m| matches!(m.discriminator, ModalType::ConfigError))
+        {
+            self.modal = None;
+        }
+
+        self.resize_screen = ResizeScreen::new(config.clone());
+        self.title = Header::new(config.clone(), self.store.clone());
+        self.footer = Footer::new(config.clone());
+        self.input_field.set_config(&config);
+        self.which_key.set_config(config.clone());
+        self.toasts.set_config(config.clone());
+        self.debug_overlay.set_config(config.clone());
+        self.page_manager.set_config(config.clone());
+        self.config = config;
+
+        self.notify(Notification::info("config reloaded"));
+    }
+
+    /// Shows a notification without interrupting the user; errors which need to
+    /// be acknowledged are shown in a modal instead
+    pub fn notify(&mut self, notification: Notification) {
+        self.toasts.push(notification);
+    }
+
+    /// Notifies the user of an error sent from a background task, and records it in
+    /// the session's activity log
+    pub fn notify_error(&mut self, message: String) {
+        tracing::error!(error = message, "background task failed");
+        let activity = Activity::error(vec![message]);
+        self.notify(activity.notification());
+        self.session.lock().unwrap().record(activity);
+    }
+
+    /// Shows the error in a modal, and records it in the session's activity log so
+    /// that it can be looked at again once the modal is dismissed
+    fn handle_error(&mut self, error: Error) {
+        tracing::error!(error = format!("{error:#}"), "action failed");
+        let chain = error_chain(&error);
+        let mut msg = format!("{error}");
+        if chain.len() > 1 {
+            msg.push_str("\n\nSee :activity for the full error.");
+        }
+        self.session.lock().unwrap().record(Activity::error(chain));
+
+        let mut modal = AlertModal::new("Error".into(), ModalType::AlertModal, self.config.theme);
         modal.initialise(msg);
         self.modal = Some(modal)
     }
@@ -174,10 +365,7 @@ impl App {
         // Short circuits drawing the app if the frame is too small;
         let area: Rect = f.size();
 
-        f.render_widget(
-            Block::new().style(Style::new().bg(self.config.theme.background())),
-            area,
-        );
+        f.render_widget(Block::new().style(self.config.theme.background()), area);
 
         if area.height < self.resize_screen.min_height || area.width < self.resize_screen.min_width
         {
@@ -193,17 +381,18 @@ impl App {
 
         let page: Rect;
         let footer: Rect;
+        let mut text_input: Option<Rect> = None;
         match self.mode {
             state::Mode::TextInput => {
-                let text_input: Rect;
+                let input: Rect;
                 layout = Layout::vertical([
                     Constraint::Length(5),
                     Constraint::Length(3),
                     Constraint::Min(0),
                     Constraint::Length(1),
                 ]);
-                [top, text_input, page, footer] = layout.areas(f.size());
-                self.input_field.draw(f, text_input);
+                [top, input, page, footer] = layout.areas(f.size());
+                text_input = Some(input);
             }
             _ => {
                 layout = Layout::vertical([
@@ -220,9 +409,17 @@ impl App {
 
         self.title.draw(f, title);
         self.page_manager.draw(f, page);
+        self.debug_overlay.draw(f, page);
+        self.which_key.draw(f, page);
+        self.toasts.draw(f, page);
         self.page_manager.draw_help(f, right_space);
         self.footer.draw(f, footer);
 
+        // Drawn after the page so that the completion dropdown overlays it
+        if let Some(text_input) = text_input {
+            self.input_field.draw(f, text_input);
+        }
+
         if let Some(m) = self.modal.as_mut() {
             if let ModalState::Open(_) = m.state {
                 m.draw(f, area)
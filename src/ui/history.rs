@@ -0,0 +1,144 @@
+use crate::{
+    context::{AppContext, PageView},
+    state::CurrentPage,
+};
+
+/// The most pages which are remembered in each direction
+const MAX_ENTRIES: usize = 50;
+
+/// A visited page, along with everything needed to open it again as it was
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub page: CurrentPage,
+    pub cx: AppContext,
+    /// The name shown for the page, eg `Describe (image: nginx:latest)`
+    pub name: String,
+    pub view: Option<PageView>,
+}
+
+/// The pages visited before and after the current page, as in a web browser.
+/// Opening a new page discards anything which could have been gone forward to
+#[derive(Debug, Default)]
+pub struct History {
+    back: Vec<HistoryEntry>,
+    forward: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// The history of a page opened from another page outside of its own history,
+    /// such as in a new tab, so that going back returns to where it was opened from
+    pub fn opened_from(entry: HistoryEntry) -> Self {
+        Self {
+            back: vec![entry],
+            forward: vec![],
+        }
+    }
+
+    /// Records the page being left in order to open a new one
+    pub fn push(&mut self, entry: HistoryEntry) {
+        self.back.push(entry);
+        if self.back.len() > MAX_ENTRIES {
+            self.back.remove(0);
+        }
+        self.forward.clear();
+    }
+
+    /// Takes the previous page, recording the current page, if given, so that it can
+    /// be gone forward to
+    pub fn back(&mut self, current: Option<HistoryEntry>) -> Option<HistoryEntry> {
+        let entry = self.back.pop()?;
+        self.forward.extend(current);
+        Some(entry)
+    }
+
+    /// Takes the next page, recording the current page, if given, so that it can be
+    /// gone back to
+    pub fn forward(&mut self, current: Option<HistoryEntry>) -> Option<HistoryEntry> {
+        let entry = self.forward.pop()?;
+        self.back.extend(current);
+        Some(entry)
+    }
+
+    /// The pages before the current page, oldest first
+    pub fn previous(&self) -> &[HistoryEntry] {
+        &self.back
+    }
+
+    /// The pages after the current page, nearest first
+    pub fn next(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.forward.iter().rev()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(page: CurrentPage) -> HistoryEntry {
+        HistoryEntry {
+            name: format!("{page:?}"),
+            page,
+            cx: AppContext::default(),
+            view: None,
+        }
+    }
+
+    fn names<'a>(entries: impl IntoIterator<Item = &'a HistoryEntry>) -> Vec<&'a str> {
+        entries.into_iter().map(|e| e.name.as_str()).collect()
+    }
+
+    #[test]
+    fn goes_back_and_forward() {
+        let mut history = History::default();
+        history.push(entry(CurrentPage::Containers));
+        history.push(entry(CurrentPage::Images));
+
+        // On the describe page
+        let back = history.back(Some(entry(CurrentPage::DescribeContainer)));
+        assert_eq!(back.unwrap().page, CurrentPage::Images);
+        assert_eq!(names(history.previous()), ["Containers"]);
+        assert_eq!(names(history.next()), ["DescribeContainer"]);
+
+        let forward = history.forward(Some(entry(CurrentPage::Images)));
+        assert_eq!(forward.unwrap().page, CurrentPage::DescribeContainer);
+        assert_eq!(names(history.previous()), ["Containers", "Images"]);
+        assert!(history.forward(None).is_none());
+    }
+
+    #[test]
+    fn goes_back_to_the_page_it_was_opened_from() {
+        let mut history = History::opened_from(entry(CurrentPage::Images));
+        assert_eq!(names(history.previous()), ["Images"]);
+        assert_eq!(history.next().count(), 0);
+
+        let back = history.back(Some(entry(CurrentPage::DescribeContainer)));
+        assert_eq!(back.unwrap().page, CurrentPage::Images);
+        assert!(history.back(None).is_none());
+        assert_eq!(names(history.next()), ["DescribeContainer"]);
+    }
+
+    #[test]
+    fn opening_a_page_discards_the_forward_history() {
+        let mut history = History::default();
+        history.push(entry(CurrentPage::Containers));
+        history.back(Some(entry(CurrentPage::Images)));
+        assert_eq!(history.next().count(), 1);
+
+        history.push(entry(CurrentPage::Containers));
+        assert_eq!(history.next().count(), 0);
+    }
+
+    #[test]
+    fn forgets_the_oldest_pages() {
+        let mut history = History::default();
+        history.push(entry(CurrentPage::Prune));
+        for _ in 0..MAX_ENTRIES {
+            history.push(entry(CurrentPage::Containers));
+        }
+        assert_eq!(history.previous().len(), MAX_ENTRIES);
+        assert!(history
+            .previous()
+            .iter()
+            .all(|e| e.page == CurrentPage::Containers));
+    }
+}
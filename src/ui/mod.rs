@@ -1,4 +1,5 @@
 pub mod app;
+pub mod history;
 pub mod page_manager;
 
 pub use app::App;
@@ -1,31 +1,98 @@
+use std::ops::Range;
+
 use bollard::Docker;
-use color_eyre::eyre::{Context, Result};
+use color_eyre::eyre::{bail, Context, Result};
 use ratatui::{
     layout::{Alignment, Margin, Rect},
-    widgets::{block::Title, Block, Padding},
+    text::{Line, Span},
+    widgets::{
+        block::{Position, Title},
+        Block, Padding,
+    },
     Frame,
 };
 use tokio::sync::mpsc::Sender;
+use tracing::instrument;
 
 use crate::{
+    commands::Command,
     config::Config,
     context::AppContext,
-    events::{message::MessageResponse, Key, Message, Transition},
+    docker::{
+        container::{ContainerField, DockerContainer},
+        image::{DockerImage, ImageField},
+        network::DockerNetwork,
+        store::ResourceStore,
+        util::parse_time,
+        volume::DockerVolume,
+    },
+    events::{message::MessageResponse, Key, Message, Mouse, MouseKind, Transition},
+    keymap::{Action, Input, Scope},
     pages::{
-        attach::Attach, containers::Containers, describe::DescribeContainer, images::Images,
-        logs::Logs,
+        activity::Activity, attach::Attach, containers::Containers, describe::DescribeContainer,
+        images::Images, logs::Logs, prune::Prune, split::Split,
     },
-    state,
+    state::{self, SharedSession},
     traits::{Component, Page},
+    ui::history::{History, HistoryEntry},
 };
 
+/// A page open in a tab, along with the pages visited before and after it within
+/// the tab
 #[derive(Debug)]
-pub struct PageManager {
-    config: Box<Config>,
+struct Tab {
     current_page: state::CurrentPage,
     page: Box<dyn Page>,
+    /// The context the page was opened with, so that it can be opened again from
+    /// the history
+    cx: AppContext,
+    history: History,
+    /// The page as it was when it was closed to stop its streams in the background,
+    /// so that it can be opened again when the tab is next shown
+    suspended: Option<HistoryEntry>,
+    /// When the tab was last shown, relative to the other tabs
+    shown: u64,
+}
+
+impl Tab {
+    fn name(&self) -> String {
+        self.page.get_help().lock().unwrap().get_name()
+    }
+
+    /// Describes the page for the history.  Attaching to a container is left out, so
+    /// that going back or forward never execs into it again
+    fn entry(&self) -> Option<HistoryEntry> {
+        if self.current_page == state::CurrentPage::Attach {
+            return None;
+        }
+        Some(HistoryEntry {
+            page: self.current_page.clone(),
+            cx: self.cx.clone(),
+            name: self.name(),
+            view: self.page.view(),
+        })
+    }
+}
+
+/// Holds the open tabs, each with its own page and history.  Pages in background
+/// tabs are left open, so that eg the logs of a container keep streaming in
+#[derive(Debug)]
+pub struct PageManager {
+    config: Box<Config>,
     tx: Sender<Message<Key, Transition>>,
     docker: Docker,
+    session: SharedSession,
+    store: ResourceStore,
+    tabs: Vec<Tab>,
+    /// The index of the tab being shown
+    active: usize,
+    /// Counts the times a tab has been shown, to order the tabs by when they were
+    /// last shown
+    shown: u64,
+    /// Whether the history is shown as a breadcrumb along the bottom of the page
+    show_history: bool,
+    /// Where the page was last drawn, including its border
+    area: Rect,
 }
 
 impl PageManager {
@@ -34,15 +101,40 @@ impl PageManager {
         tx: Sender<Message<Key, Transition>>,
         docker: Docker,
         config: Box<Config>,
+        session: SharedSession,
+        store: ResourceStore,
     ) -> Result<Self> {
-        let containers = Box::new(Containers::new(docker.clone(), tx.clone(), config.clone()));
+        let containers = Box::new(Split::new(
+            Box::new(Containers::new(
+                docker.clone(),
+                tx.clone(),
+                config.clone(),
+                session.clone(),
+                store.clone(),
+            )),
+            docker.clone(),
+            config.clone(),
+            session.clone(),
+        ));
 
         let mut page_manager = Self {
             config,
-            current_page: page,
-            page: containers,
             tx,
             docker,
+            session,
+            store,
+            tabs: vec![Tab {
+                current_page: page,
+                page: containers,
+                cx: AppContext::default(),
+                history: History::default(),
+                suspended: None,
+                shown: 0,
+            }],
+            active: 0,
+            shown: 0,
+            show_history: false,
+            area: Rect::default(),
         };
 
         page_manager
@@ -54,10 +146,22 @@ impl PageManager {
     }
 
     pub async fn init(&mut self) -> Result<()> {
-        self.page.initialise(AppContext::default()).await?;
+        self.tab_mut()
+            .page
+            .initialise(AppContext::default())
+            .await?;
         Ok(())
     }
 
+    fn tab(&self) -> &Tab {
+        &self.tabs[self.active]
+    }
+
+    fn tab_mut(&mut self) -> &mut Tab {
+        &mut self.tabs[self.active]
+    }
+
+    #[instrument(level = "debug", skip(self))]
     pub async fn transition(&mut self, transition: Transition) -> Result<MessageResponse> {
         let result = match transition {
             Transition::ToImagePage(cx) => {
@@ -84,13 +188,182 @@ impl PageManager {
                     .await?;
                 MessageResponse::Consumed
             }
+            Transition::ToPrunePage(cx) => {
+                self.set_current_page(state::CurrentPage::Prune, cx).await?;
+                MessageResponse::Consumed
+            }
+            Transition::ToActivityPage(cx) => {
+                self.set_current_page(state::CurrentPage::Activity, cx)
+                    .await?;
+                MessageResponse::Consumed
+            }
+            Transition::RunCommand(command) => {
+                self.run_command(command).await?;
+                MessageResponse::Consumed
+            }
+            Transition::Back => {
+                self.back().await?;
+                MessageResponse::Consumed
+            }
+            Transition::Forward => {
+                self.forward().await?;
+                MessageResponse::Consumed
+            }
+            Transition::NextTab => {
+                self.show_tab((self.active + 1) % self.tabs.len()).await?;
+                MessageResponse::Consumed
+            }
+            Transition::PreviousTab => {
+                let len = self.tabs.len();
+                self.show_tab((self.active + len - 1) % len).await?;
+                MessageResponse::Consumed
+            }
+            Transition::CloseTab => {
+                self.close_tab().await?;
+                MessageResponse::Consumed
+            }
             _ => MessageResponse::NotConsumed,
         };
         Ok(result)
     }
 
-    pub async fn update(&mut self, message: Key) -> Result<MessageResponse> {
-        self.page.update(message).await
+    /// Applies a reloaded config to the page in every tab and to any page opened later
+    pub fn set_config(&mut self, config: Box<Config>) {
+        for tab in &mut self.tabs {
+            tab.page.set_config(config.clone());
+        }
+        self.config = config;
+    }
+
+    /// The columns of the table on the current page, if it has one
+    pub fn columns(&self) -> Vec<String> {
+        match self.tab().current_page {
+            state::CurrentPage::Containers => self
+                .config
+                .columns
+                .containers
+                .iter()
+                .filter_map(|c| c.field.parse::<ContainerField>().ok())
+                .map(|f| f.key())
+                .collect(),
+            state::CurrentPage::Images => self
+                .config
+                .columns
+                .images
+                .iter()
+                .filter_map(|c| c.field.parse::<ImageField>().ok())
+                .map(|f| f.key())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    pub async fn update(&mut self, input: Input) -> Result<MessageResponse> {
+        self.tab_mut().page.update(input).await
+    }
+
+    /// The scope of the keybindings which are active on the current page
+    pub fn scope(&self) -> Scope {
+        match self.tab().current_page {
+            state::CurrentPage::Containers => Scope::Containers,
+            state::CurrentPage::Images => Scope::Images,
+            state::CurrentPage::Logs => Scope::Logs,
+            state::CurrentPage::Attach => Scope::Attach,
+            state::CurrentPage::DescribeContainer => Scope::Describe,
+            state::CurrentPage::Prune => Scope::Prune,
+            state::CurrentPage::Activity => Scope::Activity,
+        }
+    }
+
+    pub fn is_capturing_input(&self) -> bool {
+        self.tab().page.is_capturing_input()
+    }
+
+    /// The action of the key under the mouse in the help for the current page
+    pub fn help_action_at(&self, mouse: Mouse) -> Option<Action> {
+        self.tab().page.get_help().lock().unwrap().action_at(mouse)
+    }
+
+    /// Handles a click or scroll on the current page; scrolling moves up or down
+    /// through whatever the page shows
+    pub async fn mouse(&mut self, mouse: Mouse) -> Result<MessageResponse> {
+        if !mouse.is_in(self.area) {
+            return Ok(MessageResponse::NotConsumed);
+        }
+        match mouse.kind {
+            MouseKind::ScrollUp => self.update(Action::Up.into()).await,
+            MouseKind::ScrollDown => self.update(Action::Down.into()).await,
+            _ => self.tab_mut().page.mouse(mouse).await,
+        }
+    }
+
+    /// Runs a command from the prompt; commands which target a specific resource
+    /// open the relevant page for it, any others are handled by the current page
+    #[instrument(level = "debug", skip(self))]
+    async fn run_command(&mut self, command: Command) -> Result<()> {
+        match command {
+            Command::Logs { container, since } => {
+                let container = DockerContainer::find(&self.docker, &container).await?;
+                let log_since = since.as_deref().map(parse_time).transpose()?;
+                self.navigate(
+                    state::CurrentPage::Logs,
+                    AppContext {
+                        docker_container: Some(container),
+                        log_since,
+                        ..Default::default()
+                    },
+                )
+                .await
+            }
+            Command::Exec { container, command } => {
+                let container = DockerContainer::find(&self.docker, &container).await?;
+                if !container.running {
+                    bail!("container \"{}\" is not running", container.names);
+                }
+                self.navigate(
+                    state::CurrentPage::Attach,
+                    AppContext {
+                        docker_container: Some(container),
+                        exec_command: command,
+                        ..Default::default()
+                    },
+                )
+                .await
+            }
+            Command::Describe { target } => {
+                let cx = if let Ok(container) = DockerContainer::find(&self.docker, &target).await {
+                    AppContext {
+                        describable: Some(Box::new(container)),
+                        ..Default::default()
+                    }
+                } else if let Ok(image) = DockerImage::find(&self.docker, &target).await {
+                    AppContext {
+                        describable: Some(Box::new(image)),
+                        ..Default::default()
+                    }
+                } else if let Ok(network) = DockerNetwork::find(&self.docker, &target).await {
+                    AppContext {
+                        describable: Some(Box::new(network)),
+                        ..Default::default()
+                    }
+                } else if let Ok(volume) = DockerVolume::find(&self.docker, &target).await {
+                    AppContext {
+                        describable: Some(Box::new(volume)),
+                        ..Default::default()
+                    }
+                } else {
+                    bail!("unable to find a container, image, network or volume matching \"{target}\"");
+                };
+                self.navigate(state::CurrentPage::DescribeContainer, cx)
+                    .await
+            }
+            Command::TabNew(command) => self.new_tab(command.map(|c| *c)).await,
+            Command::History => {
+                self.show_history = !self.show_history;
+                Ok(())
+            }
+            command => self.tab_mut().page.command(command).await,
+        }
     }
 
     async fn set_current_page(
@@ -98,79 +371,363 @@ impl PageManager {
         next_page: state::CurrentPage,
         cx: AppContext,
     ) -> Result<()> {
-        if next_page == self.current_page {
+        if next_page == self.tab().current_page {
             return Ok(());
         }
-        self.page
-            .close()
+        self.navigate(next_page, cx).await
+    }
+
+    /// Opens a page, recording the current page in the history
+    async fn navigate(&mut self, next_page: state::CurrentPage, cx: AppContext) -> Result<()> {
+        if let Some(entry) = self.tab().entry() {
+            self.tab_mut().history.push(entry);
+        }
+        self.open_page(next_page, cx).await
+    }
+
+    /// Returns to the previous page, if there is one
+    async fn back(&mut self) -> Result<()> {
+        let current = self.tab().entry();
+        match self.tab_mut().history.back(current) {
+            Some(entry) => self.restore(entry).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Undoes going back, if nothing else has been opened since
+    async fn forward(&mut self) -> Result<()> {
+        let current = self.tab().entry();
+        match self.tab_mut().history.forward(current) {
+            Some(entry) => self.restore(entry).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Opens a page from the history, putting back its selection and scroll position
+    async fn restore(&mut self, entry: HistoryEntry) -> Result<()> {
+        self.open_page(entry.page, entry.cx).await?;
+        if let Some(view) = entry.view {
+            self.tab_mut().page.restore_view(view);
+        }
+        Ok(())
+    }
+
+    /// Replaces the current page with a new instance of the given page, even if the
+    /// current page is of the same type.  The current page is not closed again if
+    /// it was already closed while its tab was in the background
+    #[instrument(level = "debug", skip(self, cx))]
+    async fn open_page(&mut self, next_page: state::CurrentPage, cx: AppContext) -> Result<()> {
+        tracing::debug!(from = ?self.tab().current_page, to = ?next_page, "opening page");
+        let tab = self.tab_mut();
+        if tab.suspended.take().is_none() {
+            tab.page.close().await.context("unable to close old page")?;
+        }
+
+        let page = self.create_page(&next_page);
+        let tab = self.tab_mut();
+        tab.current_page = next_page;
+        tab.cx = cx.clone();
+        tab.page = page;
+        tab.page
+            .initialise(cx)
             .await
-            .context("unable to close old page")?;
+            .context("unable to open new page")?;
 
-        self.current_page = next_page.clone();
+        Ok(())
+    }
 
-        match next_page {
+    fn create_page(&self, page: &state::CurrentPage) -> Box<dyn Page> {
+        match page {
             state::CurrentPage::Attach => {
-                self.page = Box::new(Attach::new(self.tx.clone(), self.config.clone()))
-            }
-            state::CurrentPage::Containers => {
-                self.page = Box::new(Containers::new(
-                    self.docker.clone(),
-                    self.tx.clone(),
-                    self.config.clone(),
-                ))
-            }
-            state::CurrentPage::Images => {
-                self.page = Box::new(Images::new(
-                    self.docker.clone(),
-                    self.tx.clone(),
-                    self.config.clone(),
-                ))
-            }
-            state::CurrentPage::Logs => {
-                self.page = Box::new(Logs::new(
-                    self.docker.clone(),
-                    self.tx.clone(),
-                    self.config.clone(),
-                ))
-            }
-            state::CurrentPage::DescribeContainer => {
-                self.page = Box::new(DescribeContainer::new(
-                    self.docker.clone(),
-                    self.tx.clone(),
-                    self.config.clone(),
-                ))
+                Box::new(Attach::new(self.tx.clone(), self.config.clone()))
+            }
+            state::CurrentPage::Containers => self.split(Box::new(Containers::new(
+                self.docker.clone(),
+                self.tx.clone(),
+                self.config.clone(),
+                self.session.clone(),
+                self.store.clone(),
+            ))),
+            state::CurrentPage::Images => self.split(Box::new(Images::new(
+                self.docker.clone(),
+                self.tx.clone(),
+                self.config.clone(),
+                self.session.clone(),
+                self.store.clone(),
+            ))),
+            state::CurrentPage::Logs => Box::new(Logs::new(
+                self.docker.clone(),
+                self.tx.clone(),
+                self.config.clone(),
+            )),
+            state::CurrentPage::DescribeContainer => Box::new(DescribeContainer::new(
+                self.docker.clone(),
+                self.tx.clone(),
+                self.config.clone(),
+            )),
+            state::CurrentPage::Prune => Box::new(Prune::new(
+                self.docker.clone(),
+                self.tx.clone(),
+                self.config.clone(),
+                self.session.clone(),
+            )),
+            state::CurrentPage::Activity => Box::new(Activity::new(
+                self.tx.clone(),
+                self.config.clone(),
+                self.session.clone(),
+            )),
+        }
+    }
+
+    /// Opens a tab after the current one and shows it.  Commands which open a page
+    /// open it in the tab, any others are run on the `Containers` page opened in the
+    /// tab.  Going back from a page opened by a command, eg `:tabnew logs web`, shows
+    /// the page the tab was opened from, or the containers if that can't be opened
+    /// again.  If the command fails the tab is closed again
+    async fn new_tab(&mut self, command: Option<Command>) -> Result<()> {
+        let opened_from = self.tab().entry();
+        let (page, cx, command) = match command {
+            None | Some(Command::Containers) => {
+                (state::CurrentPage::Containers, AppContext::default(), None)
             }
+            Some(Command::Images) => (state::CurrentPage::Images, AppContext::default(), None),
+            Some(Command::Activity) => (state::CurrentPage::Activity, AppContext::default(), None),
+            Some(Command::Prune(options)) => (
+                state::CurrentPage::Prune,
+                AppContext {
+                    prune_options: Some(options),
+                    ..Default::default()
+                },
+                None,
+            ),
+            command => (
+                state::CurrentPage::Containers,
+                AppContext::default(),
+                command,
+            ),
         };
 
-        self.page
+        let mut tab = Tab {
+            page: self.create_page(&page),
+            current_page: page,
+            cx: cx.clone(),
+            history: History::default(),
+            suspended: None,
+            shown: 0,
+        };
+        tab.page
             .initialise(cx)
             .await
-            .context("unable to open new page")?;
+            .context("unable to open new tab")?;
+        self.tabs.insert(self.active + 1, tab);
+        self.show_tab(self.active + 1).await?;
 
+        if let Some(command) = command {
+            if let Err(e) = Box::pin(self.run_command(command)).await {
+                self.close_tab().await?;
+                return Err(e);
+            }
+            // Replaces the `Containers` page the command was run from
+            let tab = self.tab_mut();
+            if let Some(entry) = opened_from.filter(|_| !tab.history.previous().is_empty()) {
+                tab.history = History::opened_from(entry);
+            }
+        }
         Ok(())
     }
 
+    /// Closes the current tab and shows the one before it, unless it is the only tab
+    async fn close_tab(&mut self) -> Result<()> {
+        if self.tabs.len() == 1 {
+            return Ok(());
+        }
+        let mut tab = self.tabs.remove(self.active);
+        tab.page.close().await.context("unable to close tab")?;
+        self.show_tab(self.active.saturating_sub(1)).await
+    }
+
+    /// Shows the tab with the given index, opening its page again if it was closed
+    /// while in the background
+    async fn show_tab(&mut self, idx: usize) -> Result<()> {
+        self.active = idx;
+        self.shown += 1;
+        self.tabs[idx].shown = self.shown;
+        if let Some(entry) = self.tabs[idx].suspended.clone() {
+            self.restore(entry).await?;
+        }
+        self.limit_streams().await
+    }
+
+    /// Closes the pages of the background tabs which were shown least recently, so
+    /// that at most `tabs.background_streams` of them are streaming.  They are
+    /// opened again as they were once their tab is shown
+    async fn limit_streams(&mut self) -> Result<()> {
+        let mut streaming = self
+            .tabs
+            .iter()
+            .enumerate()
+            .filter(|(idx, tab)| *idx != self.active && tab.page.is_streaming())
+            .map(|(idx, tab)| (tab.shown, idx))
+            .collect::<Vec<_>>();
+        streaming.sort_unstable_by(|a, b| b.cmp(a));
+
+        for (_, idx) in streaming
+            .into_iter()
+            .skip(self.config.tabs.background_streams)
+        {
+            let tab = &mut self.tabs[idx];
+            let Some(entry) = tab.entry() else {
+                continue;
+            };
+            tracing::debug!(tab = entry.name, "stopping streams of background tab");
+            tab.page
+                .close()
+                .await
+                .context("unable to close background tab")?;
+            tab.suspended = Some(entry);
+        }
+        Ok(())
+    }
+
+    /// Puts a page listing resources alongside a preview of its selection
+    fn split(&self, page: Box<dyn Page>) -> Box<dyn Page> {
+        Box::new(Split::new(
+            page,
+            self.docker.clone(),
+            self.config.clone(),
+            self.session.clone(),
+        ))
+    }
+
     pub fn draw_help(&mut self, f: &mut Frame<'_>, area: Rect) {
-        self.page.get_help().lock().unwrap().draw(f, area);
+        self.tab().page.get_help().lock().unwrap().draw(f, area);
+    }
+
+    /// The path taken to the current page, followed by any pages which can be gone
+    /// forward to.  The oldest pages are left out if it doesn't fit in `width`
+    fn breadcrumb(&self, width: usize) -> Line<'static> {
+        const SEPARATOR: &str = " › ";
+        let tab = self.tab();
+        let current = tab.name();
+        let current_idx = tab.history.previous().len();
+        let mut names = tab
+            .history
+            .previous()
+            .iter()
+            .map(|e| e.name.as_str())
+            .chain([current.as_str()])
+            .chain(tab.history.next().map(|e| e.name.as_str()))
+            .collect::<Vec<_>>();
+
+        let len = |names: &[&str]| {
+            names
+                .iter()
+                .map(|n| n.chars().count() + SEPARATOR.chars().count())
+                .sum::<usize>()
+        };
+        let mut skipped = 0;
+        while skipped < current_idx && len(&names) > width {
+            names.remove(0);
+            skipped += 1;
+        }
+
+        let mut spans = vec![Span::from(" ")];
+        if skipped > 0 {
+            spans.push(Span::from(format!("…{SEPARATOR}")));
+        }
+        for (idx, name) in names.into_iter().enumerate() {
+            if idx > 0 {
+                spans.push(Span::from(SEPARATOR));
+            }
+            let style = if idx + skipped == current_idx {
+                self.config.theme.title()
+            } else {
+                self.config.theme.footer()
+            };
+            spans.push(Span::styled(name.to_string(), style));
+        }
+        spans.push(Span::from(" "));
+        Line::from(spans)
+    }
+
+    /// The numbered names of the open tabs, with the current tab highlighted.  The
+    /// tabs furthest from the current tab are left out if it doesn't fit in `width`
+    fn tab_bar(&self, width: usize) -> Line<'static> {
+        const SEPARATOR: &str = " │ ";
+        let names = self
+            .tabs
+            .iter()
+            .enumerate()
+            .map(|(idx, tab)| format!("{} {}", idx + 1, tab.name()))
+            .collect::<Vec<_>>();
+
+        let len = |range: &Range<usize>| {
+            names[range.clone()]
+                .iter()
+                .map(|n| n.chars().count() + SEPARATOR.chars().count())
+                .sum::<usize>()
+        };
+        let mut range = 0..names.len();
+        while range.len() > 1 && len(&range) > width {
+            if self.active - range.start > range.end - 1 - self.active {
+                range.start += 1;
+            } else {
+                range.end -= 1;
+            }
+        }
+
+        let mut spans = vec![Span::from("< ")];
+        if range.start > 0 {
+            spans.push(Span::from(format!("…{SEPARATOR}")));
+        }
+        for (idx, name) in names.iter().enumerate().take(range.end).skip(range.start) {
+            if idx > range.start {
+                spans.push(Span::from(SEPARATOR));
+            }
+            let style = if idx == self.active {
+                self.config.theme.title()
+            } else {
+                self.config.theme.footer()
+            };
+            spans.push(Span::styled(name.clone(), style));
+        }
+        if range.end < names.len() {
+            spans.push(Span::from(format!("{SEPARATOR}…")));
+        }
+        spans.push(Span::from(" >"));
+        Line::from(spans)
     }
 }
 
 impl Component for PageManager {
     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
-        let title_message = self.page.get_help().lock().unwrap().get_name();
-
-        let title = Title::from(format!("< {} >", title_message)).alignment(Alignment::Center);
+        self.area = area;
+        let title = if self.tabs.len() > 1 {
+            Title::from(self.tab_bar(area.width.saturating_sub(8) as usize))
+        } else {
+            Title::from(format!("< {} >", self.tab().name()))
+        }
+        .alignment(Alignment::Center);
 
-        let block = Block::bordered()
+        let mut block = Block::bordered()
             .border_type(ratatui::widgets::BorderType::Plain)
             .title(title)
+            .border_style(self.config.theme.border())
             .padding(Padding::left(300));
+        if self.show_history {
+            let breadcrumb = self.breadcrumb(area.width.saturating_sub(4) as usize);
+            block = block.title(
+                Title::from(breadcrumb)
+                    .position(Position::Bottom)
+                    .alignment(Alignment::Left),
+            );
+        }
 
         f.render_widget(block, area);
 
         let inner_body_margin = Margin::new(2, 1);
         let body_inner = area.inner(inner_body_margin);
 
-        self.page.draw(f, body_inner);
+        self.tab_mut().page.draw(f, body_inner);
     }
 }
@@ -12,6 +12,7 @@ pub struct ModalWidget<'a> {
     title: Title<'a>,
     prompt: Paragraph<'a>,
     opts: Vec<Span<'a>>,
+    border_style: Style,
     width: u16,
     height: u16,
 }
@@ -25,6 +26,11 @@ impl<'a> ModalWidget<'a> {
             ..Default::default()
         }
     }
+
+    pub fn border_style(mut self, style: Style) -> Self {
+        self.border_style = style;
+        self
+    }
 }
 
 impl<'a> Default for ModalWidget<'a> {
@@ -33,6 +39,7 @@ impl<'a> Default for ModalWidget<'a> {
             title: Title::from(""),
             prompt: Paragraph::new(Text::from("")),
             opts: vec![],
+            border_style: Style::default(),
             width: 60,
             height: 10,
         }
@@ -45,7 +52,11 @@ impl<'a> Widget for ModalWidget<'a> {
         Self: Sized,
     {
         let width = self.width;
-        let height = self.height;
+
+        // Grow the modal to fit longer prompts; borders and padding take up 5 lines
+        let prompt = self.prompt.wrap(Wrap { trim: true });
+        let n_prompt_lines = prompt.line_count(width.saturating_sub(2)) as u16;
+        let height = self.height.max(n_prompt_lines + 5).min(area.height);
 
         let [_, area, _] = Layout::horizontal(vec![
             Constraint::Min(0),
@@ -63,7 +74,7 @@ impl<'a> Widget for ModalWidget<'a> {
 
         let block = Block::bordered()
             .title(self.title)
-            .border_style(Style::default())
+            .border_style(self.border_style)
             .style(Style::default());
 
         let inner_block = block.inner(area);
@@ -72,7 +83,7 @@ impl<'a> Widget for ModalWidget<'a> {
             Constraint::Length(UPPER_PAD_SIZE),
             Constraint::Min(0),
             Constraint::Length(MID_PAD_SIZE),
-            Constraint::Min(0),
+            Constraint::Length(1),
         ]);
 
         let [_, top, _, bottom] = vertical_layout.areas(inner_block);
@@ -80,7 +91,7 @@ impl<'a> Widget for ModalWidget<'a> {
         Clear.render(inner_block, buf);
         block.render(area, buf);
 
-        self.prompt.wrap(Wrap { trim: true }).render(top, buf);
+        prompt.render(top, buf);
 
         Line::from(self.opts).centered().render(bottom, buf);
     }